@@ -2,8 +2,11 @@ use makepad_widgets::*;
 
 pub mod breadcrumb;
 pub mod button;
+pub mod cascader;
 pub mod checkbox;
 pub mod collapse;
+pub mod context_menu;
+pub mod dialog;
 pub mod divider;
 pub mod drop_down;
 pub mod file_upload;
@@ -15,6 +18,7 @@ pub mod link;
 pub mod loading;
 pub mod menu;
 pub mod notification;
+pub mod pagination;
 pub mod popup;
 pub mod progress;
 pub mod radio;
@@ -28,6 +32,7 @@ pub mod table;
 pub mod tag;
 pub mod toggle;
 pub mod tool_btn;
+pub mod tooltip;
 pub mod view;
 pub mod window;
 pub mod colors;
@@ -71,11 +76,19 @@ live_design! {
     import crate::components::table::header::GTableHeaderBase;
     import crate::components::table::GTableBase;
     import crate::components::table::virt::GVTableBodyBase;
+    import crate::components::pagination::GPaginationBase;
     import crate::components::tool_btn::GToolButtonBase;
+    import crate::components::dialog::GDialogHostBase;
+    import crate::components::notification::GNotificationHostBase;
+    import crate::components::notification::center::GNotificationCenterHostBase;
+    import crate::components::notification::bell::GNotificationBellBase;
+    import crate::components::tooltip::GToolTipHostBase;
     import crate::components::window::GWindowBase;
     import crate::components::select::GSelectBase;
     import crate::components::select::item::GSelectItemBase;
     import crate::components::select::options::GSelectOptionsBase;
+    import crate::components::cascader::GCascaderBase;
+    import crate::components::context_menu::GContextMenuBase;
     import crate::components::tabbar::GTabbarBase;
     import crate::components::tabbar::item::GTabbarItemBase;
     import crate::components::router::GRouterBase;
@@ -190,8 +203,7 @@ live_design! {
         color: (COLOR_WHITE),
         font_family: (FONT_FAMILY),
         // top_drop: 1.0,
-        line_spacing: 1.5,
-        font_size: (FONT_SIZE),
+        // font_size and line_spacing are not set, so the tokens (see `ThemeTokens`) can fill them in
     }
     GLink = <GLinkBase>{
         height: Fit,
@@ -399,6 +411,11 @@ live_design! {
     GPopupContainer = <GPopupContainerBase>{
         height: Fill,
         width: Fill,
+        // the content scrolls when the popup is constrained (see `GDropDown::constrain_height`)
+        scroll_bars: <GScrollBars>{
+            show_scroll_x: false
+            show_scroll_y: true
+        }
     }
     GPopup = <GPopupBase>{
         mode: Popup,
@@ -665,6 +682,248 @@ live_design! {
             width: 200.0,
         },
     }
+    // ## GToolTipHost
+    // shows the `tooltip` text of GView/GButton, GWindow has one
+    GToolTipHost = <GToolTipHostBase>{
+        position: Top,
+        popup: <GToolTip>{
+            height: Fit,
+            width: Fit,
+            container: {
+                height: Fit,
+                width: Fit,
+                padding: {left: 8.0, right: 8.0, top: 10.0, bottom: 10.0},
+                text = <GLabel>{
+                    font_size: (FONT_SIZE_SMALL),
+                    text: "",
+                }
+            }
+        }
+    }
+    // ## GDialogHost
+    // shows the dialogs of `GDialog::alert/confirm/prompt`, GWindow has one
+    GDialogHost = <GDialogHostBase>{
+        popup: <GDialog>{
+            container: {
+                height: Fit,
+                width: 360.0,
+                flow: Down,
+                spacing: 12.0,
+                padding: 16.0,
+                theme: Dark,
+                background_visible: true,
+                border_radius: 6.0,
+                title = <GLabel>{
+                    font_size: 11.0,
+                    text: "",
+                }
+                message = <GLabel>{
+                    width: Fill,
+                    text: "",
+                }
+                input = <GInput>{
+                    visible: false,
+                    width: Fill,
+                }
+                buttons = <GHLayout>{
+                    height: Fit,
+                    spacing: 8.0,
+                    align: {x: 1.0, y: 0.5},
+                }
+            }
+        }
+        button: <GButton>{
+            theme: Dark,
+            border_width: 1.0,
+        }
+        primary_button: <GButton>{
+            theme: Primary,
+        }
+    }
+    // ## GNotificationHost
+    // shows the toasts of `GNotification::push`, GWindow has one
+    GNotificationHost = <GNotificationHostBase>{
+        position: TopRight,
+        popup: <GPopup>{
+            height: Fit,
+            width: Fit,
+            background_visible: false,
+            container: {
+                height: Fit,
+                width: 320.0,
+                flow: Down,
+                spacing: 8.0,
+            }
+        }
+        toast: <GView>{
+            height: Fit,
+            width: Fill,
+            flow: Down,
+            spacing: 6.0,
+            padding: 12.0,
+            border_radius: 6.0,
+            background_visible: true,
+            header = <GHLayout>{
+                height: Fit,
+                spacing: 8.0,
+                align: {x: 0.0, y: 0.5},
+                title = <GLabel>{
+                    width: Fill,
+                    font_size: 10.0,
+                    text: "",
+                }
+                close = <GIcon>{
+                    height: 10.0,
+                    width: 10.0,
+                    cursor: Hand,
+                    theme: Dark,
+                    color: #FFFFFF,
+                    stroke_width: 1.2,
+                    icon_type: Close,
+                }
+            }
+            body = <GLabel>{
+                width: Fill,
+                font_size: (FONT_SIZE_SMALL),
+                text: "",
+            }
+            actions = <GHLayout>{
+                height: Fit,
+                spacing: 8.0,
+                align: {x: 1.0, y: 0.5},
+            }
+        }
+        action_button: <GButton>{
+            theme: Dark,
+            padding: <GLOBAL_PADDING_SMALL>{},
+        }
+    }
+    // ## GNotificationCenterHost
+    // the drawer of `GNotification::open_center`, lists the history grouped by source
+    GNotificationCenterHost = <GNotificationCenterHostBase>{
+        position: Right,
+        proportion: 0.3,
+        default_source: "General",
+        popup: <GDrawer>{
+            container: {
+                flow: Down,
+                spacing: 8.0,
+                padding: 12.0,
+                header = <GHLayout>{
+                    height: Fit,
+                    spacing: 8.0,
+                    align: {x: 0.0, y: 0.5},
+                    title = <GLabel>{
+                        width: Fill,
+                        font_size: 12.0,
+                        text: "Notifications",
+                    }
+                    mark_all = <GButton>{
+                        theme: Dark,
+                        padding: <GLOBAL_PADDING_SMALL>{},
+                        slot: {
+                            font_size: (FONT_SIZE_SMALL),
+                            text: "Mark all read",
+                        }
+                    }
+                    clear = <GButton>{
+                        theme: Dark,
+                        padding: <GLOBAL_PADDING_SMALL>{},
+                        slot: {
+                            font_size: (FONT_SIZE_SMALL),
+                            text: "Clear",
+                        }
+                    }
+                }
+                empty = <GLabel>{
+                    font_size: (FONT_SIZE_SMALL),
+                    color: #667085,
+                    text: "No notifications",
+                }
+                list = <GView>{
+                    height: Fill,
+                    width: Fill,
+                    flow: Down,
+                    spacing: 4.0,
+                    background_visible: false,
+                    scroll_bars: <GScrollBars>{}
+                }
+            }
+        }
+        group: <GLabel>{
+            margin: {top: 8.0},
+            font_size: (FONT_SIZE_SMALL),
+            color: #667085,
+            text: "",
+        }
+        record: <GView>{
+            height: Fit,
+            width: Fill,
+            spacing: 8.0,
+            padding: 8.0,
+            border_radius: 4.0,
+            theme: Dark,
+            cursor: Hand,
+            background_visible: true,
+            dot = <GView>{
+                height: 8.0,
+                width: 8.0,
+                margin: {top: 6.0},
+                border_radius: 4.0,
+                background_visible: true,
+            }
+            content = <GVLayout>{
+                height: Fit,
+                spacing: 4.0,
+                title = <GLabel>{
+                    width: Fill,
+                    text: "",
+                }
+                body = <GLabel>{
+                    width: Fill,
+                    font_size: (FONT_SIZE_SMALL),
+                    text: "",
+                }
+                time = <GLabel>{
+                    font_size: (FONT_SIZE_SMALL),
+                    color: #667085,
+                    text: "",
+                }
+            }
+        }
+    }
+    // ## GNotificationBell
+    // a bell with the unread count, click it to toggle the notification center
+    GNotificationBell = <GNotificationBellBase>{
+        height: Fit,
+        width: Fit,
+        padding: 0,
+        cursor: Hand,
+        background_visible: false,
+        icon = <GIcon>{
+            height: 18.0,
+            width: 18.0,
+            theme: Dark,
+            color: #FFFFFF,
+            stroke_width: 1.2,
+            icon_type: Notice,
+        }
+        badge = <GView>{
+            visible: false,
+            height: 16.0,
+            width: Fit,
+            margin: {left: -8.0, top: -6.0},
+            padding: {left: 4.0, right: 4.0},
+            border_radius: 8.0,
+            theme: Error,
+            background_visible: true,
+            align: <ALIGN_CENTER_WALK>{},
+            count = <GLabel>{
+                font_size: 8.0,
+                text: "",
+            }
+        }
+    }
     GProgress = <GProgressBase>{
         height: 16.0,
         width: 300.0,
@@ -880,22 +1139,32 @@ live_design! {
         align: {
             x: 0.0, y: 0.0
         }
+        // set body height to Fill/Fixed to keep the header pinned while scrolling
+        scroll_bars: <GScrollBars>{}
     }
     GTHeader = <GTableHeaderBase>{}
-    GTable = <GTableBase>{
-        header: <GTHeader>{}
-        body: <GTBody>{}
-    }
     GVTBody = <GVTableBodyBase>{
-        height: Fit,
+        // the height must be bounded (Fill/Fixed), a Fit body draws every row
+        height: Fill,
         width: Fill,
         flow: Down,
         align: {
             x: 0.0, y: 0.0
         }
+        row_height: 36.0,
+        // row template, each cell should have a `text` child
+        // row: <GTRow>{
+        //     name = <GTCell>{width: 240.0, align: <ALIGN_LEFT_WALK>{}, text = <GLabel>{}}
+        //     size = <GTCell>{width: 120.0, text = <GLabel>{}}
+        // }
+        scroll_bars: <GScrollBars>{}
     }
     GWindow = <GWindowBase>{
         nav_control: <NavControl> {}
+        dialogs: <GDialogHost>{}
+        tooltips: <GToolTipHost>{}
+        notifications: <GNotificationHost>{}
+        notification_center: <GNotificationCenterHost>{}
         window_bar = <GHLayout>{
             height: 32.0,
             width: Fill,
@@ -1001,12 +1270,40 @@ live_design! {
     //         }
     //     }
     // }
-    GSelectItem = <GSelectItemBase>{}
+    GSelectItem = <GSelectItemBase>{
+        spacing: 6.0,
+        // leading icon of option which has `icon`
+        icon: <GIcon>{
+            height: 14.0,
+            width: 14.0,
+            theme: Dark,
+            stroke_width: 1.2,
+        }
+    }
     GSelectOptions = <GSelectOptionsBase>{
         height: 144.0,
         width: 180.0,
         padding: {left: 8.0, right: 8.0, top: 6.0, bottom: 6.0},
         flow: Down,
+        // group header
+        group_item: <GSelectItem>{
+            height: 28.0,
+            font_size: 8.0,
+            color: #667085,
+            background_visible: false,
+        }
+        // divider between groups
+        divider: <GDivider>{
+            height: 9.0,
+            width: Fill,
+            stroke_width: 1.0,
+        }
+        // shown while remote options are loading
+        loading: <GLoading>{
+            height: 24.0,
+            width: 24.0,
+            margin: {top: 6.0, bottom: 6.0, left: 8.0},
+        }
         scroll_bars: <GScrollBars>{
             show_scroll_x: false
             show_scroll_y: true
@@ -1022,6 +1319,292 @@ live_design! {
         flow: Down,
         select_item: <GSelectItem>{},
         select_options: <GSelectOptions>{}
+        // tag of selected options in `multiple: true` mode
+        select_tag: <GTag>{
+            theme: Info,
+            closeable: true,
+            border_radius: 2.0,
+            padding: {left: 6.0, right: 6.0, top: 2.0, bottom: 2.0},
+        }
+        // search input in `searchable: true` mode, shown in the trigger when opened
+        search_input: <GInput>{
+            height: Fill,
+            width: Fill,
+            border_width: 0.0,
+            placeholder: "Search",
+        }
+        empty_text: "No results",
+        create_text: "Create",
+    }
+    GCascader = <GCascaderBase>{
+        height: 36.0,
+        width: 240.0,
+        padding: 8.0,
+        spacing: 4.0,
+        flow: Right,
+        align: {x: 0.0, y: 0.5},
+        cursor: Hand,
+        font_family: (FONT_FAMILY),
+        placeholder: "Please select",
+        split_walk: {height: 12.0, width: 12.0},
+        popup: <GPopup>{
+            height: Fit,
+            width: Fit,
+            container: <GPopupContainer>{
+                height: Fit,
+                width: Fit,
+                flow: Right,
+                background_visible: true,
+                border_width: 1.0,
+                border_radius: 4.0,
+            }
+        }
+        // a column of options, the items are generated into it
+        column: <GView>{
+            height: 200.0,
+            width: 160.0,
+            flow: Down,
+            padding: {top: 4.0, bottom: 4.0},
+            border_width: 0.0,
+            border_radius: 0.0,
+            background_visible: false,
+            scroll_bars: <GScrollBars>{
+                show_scroll_x: false
+                show_scroll_y: true
+            }
+        }
+        column_item: <GView>{
+            height: 32.0,
+            width: Fill,
+            flow: Right,
+            spacing: 4.0,
+            padding: {left: 12.0, right: 8.0},
+            align: {x: 0.0, y: 0.5},
+            cursor: Hand,
+            animation_key: true,
+            border_width: 0.0,
+            border_radius: 0.0,
+            background_color: #FFFFFF,
+            hover_color: #F2F4F7,
+            focus_color: #F2F4F7,
+            text = <GLabel>{
+                width: Fill,
+                font_size: (FONT_SIZE_SMALL),
+            }
+            arrow = <GIcon>{
+                height: 10.0,
+                width: 10.0,
+                icon_type: Right,
+                theme: Info,
+                stroke_width: 1.2,
+            }
+            loading = <GLoading>{
+                visible: false,
+                height: 12.0,
+                width: 12.0,
+            }
+        }
+    }
+    // ## GContextMenu
+    // right-click (or long-press) the content to open the menu, the items are set by `set_items`
+    GContextMenu = <GContextMenuBase>{
+        height: Fit,
+        width: Fit,
+        padding: 0,
+        border_radius: 0,
+        border_width: 0,
+        background_visible: false,
+        spacing: 0,
+        margin: 0,
+        popup: <GPopup>{
+            height: Fit,
+            width: Fit,
+            theme: Dark,
+            container: <GPopupContainer>{
+                height: Fit,
+                width: 220.0,
+                flow: Down,
+                padding: 4.0,
+                theme: Dark,
+                background_visible: true,
+                border_width: 1.0,
+                border_radius: 4.0,
+            }
+        }
+        item: <GView>{
+            height: 32.0,
+            width: Fill,
+            flow: Right,
+            spacing: 8.0,
+            padding: {left: 12.0, right: 8.0},
+            align: {x: 0.0, y: 0.5},
+            cursor: Hand,
+            animation_key: true,
+            border_width: 0.0,
+            border_radius: 2.0,
+            background_color: (COLOR_DARK_500),
+            hover_color: (COLOR_DARK_100),
+            focus_color: (COLOR_DARK_100),
+            text = <GLabel>{
+                width: Fill,
+                font_size: (FONT_SIZE_SMALL),
+                text: "",
+            }
+            shortcut = <GLabel>{
+                font_size: (FONT_SIZE_SMALL),
+                text: "",
+            }
+            arrow = <GIcon>{
+                height: 10.0,
+                width: 10.0,
+                icon_type: Right,
+                color: #98A2B3,
+                stroke_width: 1.2,
+            }
+        }
+        divider: <GDivider>{
+            height: 9.0,
+            width: Fill,
+            theme: Dark,
+            stroke_width: 1.0,
+        }
+    }
+    GPagination = <GPaginationBase>{
+        height: Fit,
+        width: Fit,
+        spacing: 8.0,
+        align: {x: 0.0, y: 0.5},
+        total = <GLabel>{
+            text: "",
+            font_size: (FONT_SIZE_SMALL),
+        }
+        prev = <GIcon>{
+            cursor: Hand,
+            theme: Dark,
+            height: 16.0,
+            width: 16.0,
+            stroke_width: 1.2,
+            icon_type: Left,
+        }
+        pages = <GHLayout>{
+            height: Fit,
+            width: Fit,
+            spacing: 4.0,
+            align: {x: 0.0, y: 0.5},
+        }
+        next = <GIcon>{
+            cursor: Hand,
+            theme: Dark,
+            height: 16.0,
+            width: 16.0,
+            stroke_width: 1.2,
+            icon_type: Right,
+        }
+        size = <GSelect>{
+            height: 32.0,
+            width: 110.0,
+        }
+        jump_wrap = <GHLayout>{
+            height: Fit,
+            width: Fit,
+            spacing: 6.0,
+            align: {x: 0.0, y: 0.5},
+            jump_label = <GLabel>{
+                text: "Go to",
+                font_size: (FONT_SIZE_SMALL),
+            }
+            jump = <GInput>{
+                height: 32.0,
+                width: 56.0,
+                placeholder: "",
+            }
+        }
+        page_item: <GButton>{
+            height: 28.0,
+            width: Fit,
+            padding: {left: 8.0, right: 8.0},
+            border_width: 0.0,
+            background_visible: false,
+            slot: <GLabel>{
+                text: "1",
+                font_size: (FONT_SIZE_SMALL),
+            }
+        }
+    }
+    GTable = <GTableBase>{
+        header: <GTHeader>{}
+        body: <GTBody>{}
+        body_virtual: <GVTBody>{}
+        // set `pageable: true` to slice the rows (virtual mode) by page
+        pagination: <GPagination>{
+            width: Fill,
+            align: {x: 1.0, y: 0.5},
+            padding: {top: 8.0, bottom: 8.0},
+        }
+        // set `filter: Text | Values | Range` on the header cells to show the filter icon
+        filter_popup: <GPopup>{
+            height: Fit,
+            width: 220.0,
+            container: <GPopupContainer>{
+                height: Fit,
+                width: Fill,
+                flow: Down,
+                spacing: 8.0,
+                padding: 8.0,
+                background_visible: true,
+                border_radius: 4.0,
+                text_panel = <GVLayout>{
+                    height: Fit,
+                    text = <GInput>{
+                        height: 32.0,
+                        width: Fill,
+                        placeholder: "Contains",
+                    }
+                }
+                values_panel = <GVLayout>{
+                    height: Fit,
+                    spacing: 4.0,
+                }
+                range_panel = <GHLayout>{
+                    height: Fit,
+                    spacing: 6.0,
+                    align: {x: 0.0, y: 0.5},
+                    min = <GInput>{
+                        height: 32.0,
+                        width: Fill,
+                        placeholder: "Min",
+                    }
+                    max = <GInput>{
+                        height: 32.0,
+                        width: Fill,
+                        placeholder: "Max",
+                    }
+                }
+                actions = <GHLayout>{
+                    height: Fit,
+                    spacing: 6.0,
+                    align: {x: 1.0, y: 0.5},
+                    clear = <GButton>{
+                        theme: Dark,
+                        height: 28.0,
+                        slot: <GLabel>{
+                            text: "Clear",
+                            font_size: (FONT_SIZE_SMALL),
+                        }
+                    }
+                    apply = <GButton>{
+                        height: 28.0,
+                        slot: <GLabel>{
+                            text: "Apply",
+                            font_size: (FONT_SIZE_SMALL),
+                        }
+                    }
+                }
+            }
+        }
+        filter_value: <GCheckBox>{
+            font_size: (FONT_SIZE_SMALL),
+        }
     }
     GTabbarItem = <GTabbarItemBase>{
         icon_slot: <GSvg>{
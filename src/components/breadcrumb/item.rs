@@ -5,7 +5,7 @@ use crate::{
     active_event, animatie_fn, default_handle_animation, default_hit_finger_down, default_hit_finger_up_some, default_hit_hover_in, default_hit_hover_out, event_option, play_animation, ref_area, ref_area_ext, ref_event_option, ref_play_animation, ref_redraw, ref_render, set_event, set_scope_path, set_text_and_visible_fn, shader::{
         draw_split::{DrawGSplit, GSplitType},
         draw_text::DrawGText,
-    }, themes::Themes, utils::{get_font_family, set_cursor, ThemeColor}, widget_area, widget_origin_fn
+    }, sync_theme, themes::Themes, utils::{get_font_family, set_cursor, ThemeColor}, widget_area, widget_origin_fn
 };
 
 use super::event::*;
@@ -136,10 +136,13 @@ pub struct GBreadCrumbItem {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GBreadCrumbItem {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
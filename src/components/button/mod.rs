@@ -6,11 +6,18 @@ pub use register::register;
 
 use crate::utils::{set_cursor, BoolToF32, ThemeColor};
 use crate::{
-    active_event, animatie_fn, default_handle_animation, default_hit_finger_down, default_hit_finger_up, default_hit_hover_in, default_hit_hover_out, event_option, play_animation, ref_area, ref_event_option, ref_play_animation, ref_redraw, ref_render, set_event, set_scope_path, widget_area
+    active_event, animatie_fn, default_handle_animation, default_hit_finger_down, default_hit_finger_up, default_hit_hover_in, default_hit_hover_out, event_option, play_animation, ref_area, ref_event_option, ref_play_animation, ref_redraw, ref_render, set_event, set_scope_path, sync_theme, widget_area
+};
+use crate::{
+    shader::draw_view::DrawGView,
+    themes::{
+        contrast_border, scale_layout, scale_walk, size_scale, token_or, GSize, GTheme, Themes,
+    },
 };
-use crate::{shader::draw_view::DrawGView, themes::Themes};
 use makepad_widgets::*;
 
+use super::{label::GLabelWidgetRefExt, tooltip::GToolTipHost};
+
 live_design! {
     import makepad_draw::shader::std::*;
     GLOBAL_DURATION = 0.25
@@ -87,17 +94,18 @@ live_design! {
 /// | macro  | prop               | description                               | type             | default   |
 /// |--------|--------------------|-------------------------------------------|------------------|-----------|
 /// | live   | theme              | Theme of the button                       | `Themes`         |           |
+/// | live   | size               | Size preset (Small, Medium, Large)        | `GSize`          | `Medium`  |
 /// | live   | background_color   | Background color of the button            | `Option<Vec4>`   | `None`    |
 /// | live   | background_visible | Visibility of the background              | `bool`           | `true`    |
 /// | live   | hover_color        | Color of the button when hovered          | `Option<Vec4>`   | `None`    |
 /// | live   | focus_color        | Color of the button when focused          | `Option<Vec4>`   | `None`    |
 /// | live   | shadow_color       | Color of the shadow                       | `Option<Vec4>`   | `None`    |
-/// | live   | spread_radius      | Spread radius of the shadow               | `f32`            | `0.0`     |
-/// | live   | blur_radius        | Blur radius of the shadow                 | `f32`            | `4.8`     |
+/// | live   | spread_radius      | Spread radius of the shadow, `None` uses the token or `0.0` | `Option<f32>` | `None` |
+/// | live   | blur_radius        | Blur radius of the shadow, `None` uses the token or `4.8` | `Option<f32>` | `None` |
 /// | live   | shadow_offset      | Offset of the shadow                      | `Vec2`           |           |
 /// | live   | border_color       | Color of the border                       | `Option<Vec4>`   | `None`    |
-/// | live   | border_width       | Width of the border                       | `f32`            | `0.0`     |
-/// | live   | border_radius      | Radius of the border's corners            | `f32`            | `2.0`     |
+/// | live   | border_width       | Width of the border, `None` uses the token or `0.0` | `Option<f32>` | `None` |
+/// | live   | border_radius      | Radius of the border's corners, `None` uses the token or `2.0` | `Option<f32>` | `None` |
 /// | live   | cursor             | Mouse cursor when hovering over the button| `Option<MouseCursor>`|        |
 /// | live   | visible            | Whether the button is visible             | `bool`           | `true`    |
 /// | live   | grab_key_focus     | Whether the button grabs keyboard focus   | `bool`           | `true`    |
@@ -113,10 +121,19 @@ live_design! {
 /// | layout | `align`             | Alignment for content                    | `Align`            | `Align::default()` |
 /// | layout | `flow`              | Flow direction of the content            | `Flow`             | `Flow::default()` |
 /// | layout | `spacing`           | Spacing between elements                 | `f64`              | `0.0`    |
+/// the built-in defaults of the props which are not set, unless a token is loaded (see `token_or`)
+const BORDER_WIDTH: f32 = 0.0;
+const BORDER_RADIUS: f32 = 2.0;
+const SPREAD_RADIUS: f32 = 0.0;
+const BLUR_RADIUS: f32 = 4.8;
+
 #[derive(Live, Widget)]
 pub struct GButton {
     #[live]
     pub theme: Themes,
+    /// the size preset, the label slot follows it
+    #[live]
+    pub size: GSize,
     #[live]
     pub background_color: Option<Vec4>,
     #[live(true)]
@@ -127,18 +144,18 @@ pub struct GButton {
     pub focus_color: Option<Vec4>,
     #[live]
     pub shadow_color: Option<Vec4>,
-    #[live(0.0)]
-    pub spread_radius: f32,
-    #[live(4.8)]
-    pub blur_radius: f32,
+    #[live]
+    pub spread_radius: Option<f32>,
+    #[live]
+    pub blur_radius: Option<f32>,
     #[live]
     pub shadow_offset: Vec2,
     #[live]
     pub border_color: Option<Vec4>,
-    #[live(0.0)]
-    pub border_width: f32,
-    #[live(2.0)]
-    pub border_radius: f32,
+    #[live]
+    pub border_width: Option<f32>,
+    #[live]
+    pub border_radius: Option<f32>,
     #[live]
     pub cursor: Option<MouseCursor>,
     // visible -------------------
@@ -151,6 +168,9 @@ pub struct GButton {
     pub slot: WidgetRef,
     #[live(false)]
     pub grab_key_focus: bool,
+    /// a text shown in a tooltip when hovering, see `GToolTipHost`
+    #[live]
+    pub tooltip: String,
     // animator -----------------
     #[live(true)]
     pub animation_key: bool,
@@ -168,6 +188,8 @@ pub struct GButton {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GButton {
@@ -198,11 +220,15 @@ impl Widget for GButton {
         self.handle_widget_event(cx, event, hit, focus_area)
     }
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
 
-        let _ = self.draw_button.begin(cx, walk, self.layout);
+        let scale = size_scale(self.size);
+        let _ = self
+            .draw_button
+            .begin(cx, scale_walk(walk, scale), scale_layout(self.layout, scale));
 
         if self.slot.is_visible() {
             let slot_walk = self.slot.walk(cx);
@@ -261,6 +287,29 @@ impl GButton {
         let border_color = self.border_color.get(self.theme, 600);
         let shadow_color = self.shadow_color.get(self.theme, 700);
         let background_visible = self.background_visible.to_f32();
+        // ------------------ tokens (see `ThemeTokens`) ---------------------------------
+        let tokens = GTheme::tokens();
+        let border_width = token_or(self.border_width, BORDER_WIDTH, tokens.border_width);
+        let border_radius = token_or(self.border_radius, BORDER_RADIUS, tokens.border_radius);
+        let spread_radius = token_or(self.spread_radius, SPREAD_RADIUS, tokens.spread_radius);
+        let blur_radius = token_or(self.blur_radius, BLUR_RADIUS, tokens.blur_radius);
+        // ------------------ size (see `GSize`) -----------------------------------------
+        let border_radius = border_radius * size_scale(self.size) as f32;
+        // ------------------ contrast check (see `GTheme::set_contrast_check`) ------------
+        // the label follows the size of the button,
+        // the label is checked on the background, the button gets the border if it fails
+        let fails = match self.slot.as_glabel().borrow_mut() {
+            Some(mut label) => {
+                if label.size != self.size {
+                    label.size = self.size;
+                    label.render(cx);
+                }
+                label.contrast_background = self.background_visible.then_some(bg_color);
+                !label.check_contrast()
+            }
+            None => false,
+        };
+        let (border_color, border_width) = contrast_border(fails, border_color, border_width);
         // apply over props to draw_button ----------------------------------------------
         self.draw_button.apply_over(
             cx,
@@ -268,14 +317,14 @@ impl GButton {
                 background_color: (bg_color),
                 background_visible: (background_visible),
                 border_color: (border_color),
-                border_width: (self.border_width),
-                border_radius: (self.border_radius),
+                border_width: (border_width),
+                border_radius: (border_radius),
                 focus_color: (focus_color),
                 hover_color: (hover_color),
                 shadow_color: (shadow_color),
                 shadow_offset: (self.shadow_offset),
-                spread_radius: (self.spread_radius),
-                blur_radius: (self.blur_radius)
+                spread_radius: (spread_radius),
+                blur_radius: (blur_radius)
             },
         );
     }
@@ -295,9 +344,15 @@ impl GButton {
                 default_hit_finger_down!(self, cx, focus_area, e);
             }
             Hit::FingerHoverIn(e) => {
+                if !self.tooltip.is_empty() {
+                    GToolTipHost::show(cx, self.area(), &self.tooltip);
+                }
                 default_hit_hover_in!(self, cx, e);
             }
             Hit::FingerHoverOut(e) => {
+                if !self.tooltip.is_empty() {
+                    GToolTipHost::hide(cx, self.area());
+                }
                 default_hit_hover_out!(self, cx, e);
             }
             Hit::FingerUp(e) => {
@@ -0,0 +1,27 @@
+use makepad_widgets::{ActionDefaultRef, DefaultNone};
+
+#[derive(Debug, Clone, DefaultNone)]
+pub enum GCascaderEvent {
+    /// a leaf is picked
+    Changed(GCascaderChangedParam),
+    /// a lazy node is picked and there is no loader (or the loader back `None`),
+    /// answer with `GCascaderRef::set_children`
+    LoadChildren(GCascaderLoadParam),
+    None,
+}
+
+#[derive(Clone, Debug)]
+pub struct GCascaderChangedParam {
+    /// index of each level
+    pub path: Vec<usize>,
+    /// text of each level
+    pub texts: Vec<String>,
+    /// values of each level if `emit_path` is true, otherwise only the value of the leaf
+    pub value: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GCascaderLoadParam {
+    pub path: Vec<usize>,
+    pub value: String,
+}
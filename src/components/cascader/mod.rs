@@ -0,0 +1,601 @@
+mod event;
+mod register;
+pub mod types;
+
+pub use event::*;
+pub use register::register;
+
+use makepad_widgets::*;
+use types::{CascaderNode, CascaderTree};
+
+use crate::{
+    event_option, ref_event_option, set_scope_path,
+    shader::{
+        draw_split::{DrawGSplit, GSplitType},
+        draw_text::DrawGText,
+        draw_view::DrawGView,
+        manual::Position,
+    },
+    sync_theme,
+    themes::Themes,
+    utils::{get_font_family, set_cursor, BoolToF32, ThemeColor},
+    widget_area,
+};
+
+use super::{
+    drop_down::PopupMenuGlobal,
+    icon::GIconWidgetRefExt,
+    label::GLabelWidgetRefExt,
+    loading::GLoadingWidgetRefExt,
+    popup::{GPopup, PopupStackGlobal},
+    view::GViewWidgetRefExt,
+};
+
+live_design! {
+    GCascaderBase = {{GCascader}}{}
+}
+
+/// A loader which provides the children of a lazy node.
+/// Return `None` if the children can not be provided now,
+/// then `GCascaderEvent::LoadChildren` will be called and you should use `set_children` later.
+pub type CascaderLoader = Box<dyn FnMut(&CascaderNode) -> Option<Vec<CascaderNode>>>;
+
+/// # GCascader
+/// A select for hierarchical options (region → city → district).
+/// The trigger shows the picked path (`A / B / C`, the separator is `split_type`),
+/// the popup shows a column for each level of the active path, picking a parent shows its children.
+///
+/// - Columns are generated from `column` (a GView template) and items from `column_item`,
+/// each item should have a `text` (GLabel), an `arrow` (GIcon) and a `loading` (GLoading) child.
+/// - A lazy node loads its children when picked, by the loader (`set_loader`) or by
+/// `GCascaderEvent::LoadChildren` + `set_children`.
+/// - `emit_path`: the value is the values of each level (true) or only the value of the leaf (false).
+#[derive(Live, Widget)]
+pub struct GCascader {
+    #[live]
+    pub theme: Themes,
+    #[live]
+    pub color: Option<Vec4>,
+    #[live]
+    pub placeholder_color: Option<Vec4>,
+    #[live]
+    pub background_color: Option<Vec4>,
+    #[live(true)]
+    pub background_visible: bool,
+    #[live]
+    pub hover_color: Option<Vec4>,
+    #[live]
+    pub focus_color: Option<Vec4>,
+    #[live]
+    pub border_color: Option<Vec4>,
+    #[live(1.0)]
+    pub border_width: f32,
+    #[live(2.0)]
+    pub border_radius: f32,
+    #[live]
+    pub cursor: Option<MouseCursor>,
+    #[live(10.0)]
+    pub font_size: f64,
+    #[live]
+    pub font_family: LiveDependency,
+    #[live]
+    pub placeholder: String,
+    // split ----------------------
+    #[live(GSplitType::Spliter)]
+    pub split_type: GSplitType,
+    #[live]
+    pub split_color: Option<Vec4>,
+    #[live]
+    pub split_walk: Walk,
+    // draw -----------------------
+    #[redraw]
+    #[live]
+    pub draw_cascader: DrawGView,
+    #[live]
+    pub draw_text: DrawGText,
+    #[live]
+    pub draw_split: DrawGSplit,
+    #[walk]
+    pub walk: Walk,
+    #[layout]
+    pub layout: Layout,
+    // popup ----------------------
+    #[live]
+    pub popup: Option<LivePtr>,
+    /// column template (GView)
+    #[live]
+    pub column: Option<LivePtr>,
+    /// item template (GView) of columns
+    #[live]
+    pub column_item: Option<LivePtr>,
+    /// text color of the items
+    #[live]
+    pub item_color: Option<Vec4>,
+    /// text color of the items in the active path
+    #[live]
+    pub active_color: Option<Vec4>,
+    #[live]
+    pub disabled_color: Option<Vec4>,
+    #[live(6.0)]
+    pub offset: f32,
+    #[live]
+    pub opened: bool,
+    #[rust]
+    redraw_popup: bool,
+    // data -----------------------
+    /// the value is the values of each level, or only the value of the leaf
+    #[live(true)]
+    pub emit_path: bool,
+    #[rust]
+    pub tree: CascaderTree,
+    /// path of the picked leaf
+    #[rust]
+    pub selected: Vec<usize>,
+    /// path shown in the popup
+    #[rust]
+    pub active: Vec<usize>,
+    /// path of the node which is loading children
+    #[rust]
+    pub loading: Option<Vec<usize>>,
+    #[rust]
+    pub loader: Option<CascaderLoader>,
+    #[live(true)]
+    pub visible: bool,
+    #[live(true)]
+    pub event_key: bool,
+    #[rust]
+    pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
+}
+
+impl Widget for GCascader {
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
+        if !self.visible {
+            return DrawStep::done();
+        }
+        self.set_scope_path(&scope.path);
+        let _ = self.draw_cascader.begin(cx, walk, self.layout);
+        let font = get_font_family(&self.font_family, cx);
+        self.draw_text.text_style.font = font;
+        let texts = self.tree.texts(&self.selected);
+        let align = Align { x: 0.0, y: 0.5 };
+        if texts.is_empty() {
+            let color = self.draw_text.color;
+            self.draw_text.color = self.placeholder_color.use_or("#98A2B3");
+            self.draw_text
+                .draw_walk(cx, Walk::fit(), align, &self.placeholder);
+            self.draw_text.color = color;
+        } else {
+            for (index, text) in texts.iter().enumerate() {
+                if index > 0 {
+                    self.draw_split.draw_walk(cx, self.split_walk);
+                }
+                self.draw_text.draw_walk(cx, Walk::fit(), align, text);
+            }
+        }
+        self.draw_cascader.end(cx);
+
+        if self.opened {
+            self.draw_popup(cx, scope);
+        }
+        DrawStep::done()
+    }
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.opened {
+            self.handle_popup(cx, event, scope);
+        }
+        match event.hits_with_sweep_area(cx, self.area(), self.area()) {
+            Hit::FingerHoverIn(_) => {
+                set_cursor(cx, self.cursor.as_ref());
+            }
+            Hit::FingerUp(f) if f.is_over => {
+                if self.opened {
+                    self.close(cx);
+                } else {
+                    self.open(cx);
+                }
+            }
+            _ => (),
+        }
+    }
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+impl LiveHook for GCascader {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if !self.visible {
+            return;
+        }
+        if let (Some(popup), true) = (self.popup, apply.from.is_from_doc()) {
+            let global = cx.global::<PopupMenuGlobal>().clone();
+            let mut global_map = global.map.borrow_mut();
+            global_map.retain(|k, _| cx.live_registry.borrow().generation_valid(*k));
+            global_map.get_or_insert(cx, popup, |cx| GPopup::new_from_ptr(cx, Some(popup)));
+        }
+        self.render(cx);
+    }
+}
+
+impl GCascader {
+    pub fn render(&mut self, cx: &mut Cx) {
+        let bg_color = self.background_color.use_or("#ffffff");
+        let hover_color = self.hover_color.use_or("#ffffff");
+        let focus_color = self.focus_color.use_or("#ffffff");
+        let border_color = self.border_color.get(self.theme, 600);
+        let background_visible = self.background_visible.to_f32();
+        let color = self.color.use_or("#344054");
+        let split_color = self.split_color.use_or("#98A2B3");
+        self.draw_cascader.apply_over(
+            cx,
+            live! {
+                background_color: (bg_color),
+                background_visible: (background_visible),
+                border_color: (border_color),
+                border_width: (self.border_width),
+                border_radius: (self.border_radius),
+                focus_color: (focus_color),
+                hover_color: (hover_color),
+            },
+        );
+        self.draw_text.apply_over(
+            cx,
+            live! {
+                color: (color),
+                text_style: {
+                    font_size: (self.font_size),
+                }
+            },
+        );
+        self.draw_split.apply_over(
+            cx,
+            live! {
+                color: (split_color),
+            },
+        );
+        self.draw_split.apply_split_type(self.split_type.clone());
+        self.draw_cascader.redraw(cx);
+    }
+    set_scope_path!();
+    widget_area! {
+        area, draw_cascader
+    }
+    event_option! {
+        changed: GCascaderEvent::Changed => GCascaderChangedParam,
+        load_children: GCascaderEvent::LoadChildren => GCascaderLoadParam
+    }
+    pub fn redraw(&mut self, cx: &mut Cx) {
+        self.draw_cascader.redraw(cx);
+    }
+    pub fn open(&mut self, cx: &mut Cx) {
+        if self.opened {
+            return;
+        }
+        self.opened = true;
+        self.active = self.selected.clone();
+        self.sync_columns(cx);
+        self.draw_cascader.apply_over(cx, live! {focus: 1.0});
+        self.redraw(cx);
+        if let Some(popup) = self.popup {
+            let global = cx.global::<PopupMenuGlobal>().clone();
+            let mut map = global.map.borrow_mut();
+            if let Some(popup) = map.get_mut(&popup) {
+                popup.raise();
+            }
+        }
+        PopupStackGlobal::open(cx, self.widget_uid(), self.area());
+    }
+    pub fn close(&mut self, cx: &mut Cx) {
+        if !self.opened {
+            return;
+        }
+        self.opened = false;
+        self.draw_cascader.apply_over(cx, live! {focus: 0.0});
+        self.redraw(cx);
+        PopupStackGlobal::close(cx, self.widget_uid(), self.area());
+    }
+    /// texts of the picked path
+    pub fn texts(&self) -> Vec<String> {
+        self.tree.texts(&self.selected)
+    }
+    /// values of the picked path (`emit_path`) or the value of the picked leaf
+    pub fn value(&self) -> Vec<String> {
+        let values = self.tree.values(&self.selected);
+        if self.emit_path {
+            values
+        } else {
+            values.last().cloned().into_iter().collect()
+        }
+    }
+    /// pick by value, the same form as `value`, return false if not found
+    pub fn set_value(&mut self, cx: &mut Cx, value: &[String]) -> bool {
+        let path = if self.emit_path {
+            self.tree.find_path(value)
+        } else {
+            value.first().and_then(|value| self.tree.find_leaf(value))
+        };
+        match path {
+            Some(path) => {
+                self.selected = path;
+                self.redraw(cx);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn set_tree(&mut self, cx: &mut Cx, roots: Vec<CascaderNode>) {
+        self.tree = CascaderTree::new(roots);
+        self.selected.clear();
+        self.active.clear();
+        self.loading = None;
+        self.sync_columns(cx);
+        self.redraw(cx);
+    }
+    /// set the children of a lazy node, if the node is picked and has no children it is selected
+    pub fn set_children(&mut self, cx: &mut Cx, path: &[usize], children: Vec<CascaderNode>) {
+        if !self.tree.set_children(path, children) {
+            return;
+        }
+        if self.loading.as_deref() == Some(path) {
+            self.loading = None;
+        }
+        let is_leaf = self.tree.get(path).map_or(false, |node| node.is_leaf());
+        if self.active == path && is_leaf {
+            self.select(cx);
+        } else {
+            self.sync_columns(cx);
+        }
+    }
+    pub fn active_changed(&mut self, cx: &mut Cx) {
+        if self.event_key {
+            if let Some(path) = self.scope_path.as_ref() {
+                cx.widget_action(
+                    self.widget_uid(),
+                    path,
+                    GCascaderEvent::Changed(GCascaderChangedParam {
+                        path: self.selected.clone(),
+                        texts: self.texts(),
+                        value: self.value(),
+                    }),
+                );
+            }
+        }
+    }
+    /// ## Pick an item
+    /// a parent shows its children, a lazy node loads its children, a leaf is selected
+    fn pick(&mut self, cx: &mut Cx, level: usize, index: usize) {
+        let mut active = self.active[..level.min(self.active.len())].to_vec();
+        active.push(index);
+        let (lazy, leaf) = match self.tree.get(&active) {
+            Some(node) if !node.disabled => (node.lazy, node.is_leaf()),
+            _ => return,
+        };
+        self.active = active;
+        if leaf {
+            self.select(cx);
+            return;
+        }
+        if lazy {
+            self.load_children(cx);
+        } else {
+            self.sync_columns(cx);
+        }
+    }
+    /// load the children of the active node by the loader or `LoadChildren` event
+    fn load_children(&mut self, cx: &mut Cx) {
+        let path = self.active.clone();
+        let node = match self.tree.get(&path) {
+            Some(node) => node,
+            None => return,
+        };
+        let value = node.value.to_string();
+        if let Some(children) = self.loader.as_mut().and_then(|loader| loader(node)) {
+            self.set_children(cx, &path, children);
+            return;
+        }
+        self.loading.replace(path.clone());
+        self.sync_columns(cx);
+        if self.event_key {
+            if let Some(scope_path) = self.scope_path.as_ref() {
+                cx.widget_action(
+                    self.widget_uid(),
+                    scope_path,
+                    GCascaderEvent::LoadChildren(GCascaderLoadParam { path, value }),
+                );
+            }
+        }
+    }
+    fn select(&mut self, cx: &mut Cx) {
+        self.selected = self.active.clone();
+        self.close(cx);
+        self.redraw(cx);
+        self.active_changed(cx);
+    }
+    /// ## Sync columns
+    /// generate the columns of the active path into the popup container
+    fn sync_columns(&mut self, cx: &mut Cx) {
+        let popup = match self.popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let container = match map.get_mut(&popup) {
+            Some(popup) => popup.get_mut(),
+            None => return,
+        };
+        let item_color = self.item_color.use_or("#344054");
+        let active_color = self.active_color.use_or("#06AED4");
+        let disabled_color = self.disabled_color.use_or("#D0D5DD");
+        let columns = self.tree.columns(&self.active);
+        container.children.truncate(columns.len());
+        for (level, nodes) in columns.iter().enumerate() {
+            if container.children.get(level).is_none() {
+                let column = WidgetRef::new_from_ptr(cx, self.column);
+                container.children.push((LiveId(level as u64), column));
+            }
+            let column = container.children[level].1.as_gview();
+            let mut column = match column.borrow_mut() {
+                Some(column) => column,
+                None => continue,
+            };
+            column.children.truncate(nodes.len());
+            for (index, node) in nodes.iter().enumerate() {
+                if column.children.get(index).is_none() {
+                    let item = WidgetRef::new_from_ptr(cx, self.column_item);
+                    column.children.push((LiveId(index as u64), item));
+                }
+                let item = &column.children[index].1;
+                let is_active = self.active.get(level) == Some(&index);
+                let loading = is_active
+                    && self.loading.as_deref() == Some(&self.active[..=level]);
+                let color = if node.disabled {
+                    disabled_color
+                } else if is_active {
+                    active_color
+                } else {
+                    item_color
+                };
+                let text = item.glabel(id!(text));
+                text.set_text(&node.text);
+                text.apply_over(cx, live! {color: (color)});
+                if let Some(mut arrow) = item.gicon(id!(arrow)).borrow_mut() {
+                    arrow.visible = !node.is_leaf() && !loading;
+                }
+                if let Some(mut spinner) = item.gloading(id!(loading)).borrow_mut() {
+                    spinner.visible = loading;
+                    if loading {
+                        spinner.open(cx);
+                    } else {
+                        spinner.close(cx);
+                    }
+                }
+            }
+        }
+        container.redraw(cx);
+        self.redraw_popup = true;
+    }
+    fn handle_popup(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let popup = match self.popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        // check the stack before the popups opened from this one handle the event
+        let uid = self.widget_uid();
+        let is_top = PopupStackGlobal::is_top(cx, uid);
+        let in_above = match event {
+            Event::MouseDown(e) => PopupStackGlobal::above_contains_pos(cx, uid, e.abs),
+            _ => false,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        let area = self.area();
+        let actions = cx.capture_actions(|cx| popup.handle_event_with(cx, event, scope, area));
+        let mut picked = None;
+        if !actions.is_empty() {
+            for (level, (_, column)) in popup.get().children.iter().enumerate() {
+                if let Some(column) = column.as_gview().borrow() {
+                    for (index, (_, item)) in column.children.iter().enumerate() {
+                        if item.as_gview().clicked(&actions).is_some() {
+                            picked.replace((level, index));
+                        }
+                    }
+                }
+            }
+        }
+        let outside = match event {
+            Event::MouseDown(e) => {
+                !popup.container_contains_pos(cx, e.abs)
+                    && !area.rect(cx).contains(e.abs)
+                    && !in_above
+            }
+            // only the topmost popup is closed
+            Event::KeyDown(e) => e.key_code == KeyCode::Escape && is_top,
+            _ => false,
+        };
+        drop(map);
+        if let Some((level, index)) = picked {
+            self.pick(cx, level, index);
+        }
+        if outside {
+            self.close(cx);
+        }
+    }
+    /// draw the popup under the trigger
+    fn draw_popup(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        let popup = match self.popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        popup.begin(cx);
+        popup.draw_container(
+            cx,
+            scope,
+            Some(Position::BottomLeft),
+            0.0,
+            &mut self.redraw_popup,
+        );
+        let area = self.area().rect(cx);
+        let shift = dvec2(0.0, area.size.y + self.offset as f64);
+        popup.end(cx, scope, self.area(), shift);
+        PopupStackGlobal::set_areas(cx, self.widget_uid(), vec![popup.container_area()]);
+    }
+}
+
+impl GCascaderRef {
+    ref_event_option! {
+        changed => GCascaderChangedParam,
+        load_children => GCascaderLoadParam
+    }
+    pub fn set_tree(&self, cx: &mut Cx, roots: Vec<CascaderNode>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_tree(cx, roots);
+        }
+    }
+    pub fn set_children(&self, cx: &mut Cx, path: &[usize], children: Vec<CascaderNode>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_children(cx, path, children);
+        }
+    }
+    pub fn set_loader<F>(&self, loader: F)
+    where
+        F: FnMut(&CascaderNode) -> Option<Vec<CascaderNode>> + 'static,
+    {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.loader.replace(Box::new(loader));
+        }
+    }
+    pub fn value(&self) -> Vec<String> {
+        self.borrow().map(|c_ref| c_ref.value()).unwrap_or_default()
+    }
+    pub fn texts(&self) -> Vec<String> {
+        self.borrow().map(|c_ref| c_ref.texts()).unwrap_or_default()
+    }
+    pub fn set_value(&self, cx: &mut Cx, value: &[String]) -> bool {
+        self.borrow_mut()
+            .map_or(false, |mut c_ref| c_ref.set_value(cx, value))
+    }
+    pub fn open(&self, cx: &mut Cx) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.open(cx);
+        }
+    }
+    pub fn close(&self, cx: &mut Cx) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.close(cx);
+        }
+    }
+}
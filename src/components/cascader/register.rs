@@ -0,0 +1,5 @@
+use makepad_widgets::Cx;
+
+pub fn register(cx: &mut Cx) {
+    crate::components::cascader::live_design(cx);
+}
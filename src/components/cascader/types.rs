@@ -0,0 +1,188 @@
+/// # Cascader Node
+/// A node of the cascader tree, build it with the builder methods:
+/// ```rust
+/// let node = CascaderNode::new("Zhejiang", "zj").with_children(vec![
+///     CascaderNode::new("Hangzhou", "hz").with_lazy(true),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CascaderNode {
+    pub text: String,
+    pub value: String,
+    pub children: Vec<CascaderNode>,
+    /// the children are loaded when the node is picked (see `GCascaderEvent::LoadChildren`)
+    pub lazy: bool,
+    /// a disabled node can not be picked
+    pub disabled: bool,
+}
+
+impl CascaderNode {
+    pub fn new(text: &str, value: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+    pub fn with_children(mut self, children: Vec<CascaderNode>) -> Self {
+        self.children = children;
+        self
+    }
+    pub fn with_lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+    /// a leaf has no children and nothing to load
+    pub fn is_leaf(&self) -> bool {
+        !self.lazy && self.children.is_empty()
+    }
+}
+
+impl From<(&str, &str)> for CascaderNode {
+    fn from(value: (&str, &str)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+/// # Cascader Tree
+/// The data model of GCascader, a node is located by its path (index in each level)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CascaderTree {
+    pub roots: Vec<CascaderNode>,
+}
+
+impl CascaderTree {
+    pub fn new(roots: Vec<CascaderNode>) -> Self {
+        Self { roots }
+    }
+    pub fn get(&self, path: &[usize]) -> Option<&CascaderNode> {
+        let (first, rest) = path.split_first()?;
+        rest.iter()
+            .try_fold(self.roots.get(*first)?, |node, index| node.children.get(*index))
+    }
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut CascaderNode> {
+        let (first, rest) = path.split_first()?;
+        rest.iter().try_fold(self.roots.get_mut(*first)?, |node, index| {
+            node.children.get_mut(*index)
+        })
+    }
+    /// the nodes along the path, stop at the first invalid index
+    pub fn nodes(&self, path: &[usize]) -> Vec<&CascaderNode> {
+        let mut nodes = vec![];
+        let mut level = &self.roots;
+        for index in path {
+            match level.get(*index) {
+                Some(node) => {
+                    nodes.push(node);
+                    level = &node.children;
+                }
+                None => break,
+            }
+        }
+        nodes
+    }
+    /// ## Columns
+    /// the option columns of the active path: the roots, then the children of each active node
+    pub fn columns(&self, active: &[usize]) -> Vec<&[CascaderNode]> {
+        let mut columns = vec![self.roots.as_slice()];
+        for node in self.nodes(active) {
+            if node.children.is_empty() {
+                break;
+            }
+            columns.push(node.children.as_slice());
+        }
+        columns
+    }
+    pub fn texts(&self, path: &[usize]) -> Vec<String> {
+        self.nodes(path)
+            .iter()
+            .map(|node| node.text.to_string())
+            .collect()
+    }
+    pub fn values(&self, path: &[usize]) -> Vec<String> {
+        self.nodes(path)
+            .iter()
+            .map(|node| node.value.to_string())
+            .collect()
+    }
+    /// set the loaded children of a lazy node, return false if the node does not exist
+    pub fn set_children(&mut self, path: &[usize], children: Vec<CascaderNode>) -> bool {
+        match self.get_mut(path) {
+            Some(node) => {
+                node.children = children;
+                node.lazy = false;
+                true
+            }
+            None => false,
+        }
+    }
+    /// find the path by the values of each level
+    pub fn find_path(&self, values: &[String]) -> Option<Vec<usize>> {
+        let mut path = vec![];
+        let mut level = &self.roots;
+        for value in values {
+            let index = level.iter().position(|node| node.value == *value)?;
+            path.push(index);
+            level = &level[index].children;
+        }
+        Some(path)
+    }
+    /// find the path of the first leaf which has the value (depth first)
+    pub fn find_leaf(&self, value: &str) -> Option<Vec<usize>> {
+        fn find(nodes: &[CascaderNode], value: &str, path: &mut Vec<usize>) -> bool {
+            for (index, node) in nodes.iter().enumerate() {
+                path.push(index);
+                if (node.is_leaf() && node.value == value) || find(&node.children, value, path) {
+                    return true;
+                }
+                path.pop();
+            }
+            false
+        }
+        let mut path = vec![];
+        find(&self.roots, value, &mut path).then_some(path)
+    }
+}
+
+#[cfg(test)]
+mod test_cascader {
+    use super::*;
+
+    fn tree() -> CascaderTree {
+        CascaderTree::new(vec![
+            CascaderNode::new("Zhejiang", "zj").with_children(vec![
+                CascaderNode::new("Hangzhou", "hz").with_children(vec![("Xihu", "xh").into()]),
+                CascaderNode::new("Ningbo", "nb").with_lazy(true),
+            ]),
+            ("Jiangsu", "js").into(),
+        ])
+    }
+
+    #[test]
+    fn columns() {
+        let tree = tree();
+        assert_eq!(tree.columns(&[]).len(), 1);
+        let columns = tree.columns(&[0, 0]);
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[2][0].text, "Xihu");
+        // a lazy node has no column before its children are loaded
+        assert_eq!(tree.columns(&[0, 1]).len(), 2);
+        assert_eq!(tree.texts(&[0, 0, 0]), vec!["Zhejiang", "Hangzhou", "Xihu"]);
+    }
+
+    #[test]
+    fn find() {
+        let mut tree = tree();
+        let values: Vec<String> = vec!["zj".to_string(), "hz".to_string(), "xh".to_string()];
+        assert_eq!(tree.find_path(&values), Some(vec![0, 0, 0]));
+        assert_eq!(tree.find_leaf("xh"), Some(vec![0, 0, 0]));
+        assert_eq!(tree.find_leaf("nb"), None);
+        assert!(tree.set_children(&[0, 1], vec![("Yinzhou", "yz").into()]));
+        assert_eq!(tree.find_leaf("yz"), Some(vec![0, 1, 0]));
+        assert!(tree.get(&[0, 1]).map_or(false, |node| !node.lazy));
+    }
+}
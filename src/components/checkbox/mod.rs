@@ -11,7 +11,7 @@ use crate::{
     active_event, animatie_fn, default_handle_animation, default_hit_hover_in, default_hit_hover_out, event_option, play_animation, ref_area, ref_area_ext, ref_event_option, ref_redraw, ref_render, set_event, set_scope_path, set_text_and_visible_fn, shader::{
         draw_check_box::DrawGCheckBox, draw_radio::GChooseType, draw_text::DrawGText,
         draw_view::DrawGView,
-    }, themes::Themes, utils::{get_font_family, set_cursor, BoolToF32, ThemeColor}, widget_area
+    }, sync_theme, themes::Themes, utils::{get_font_family, set_cursor, BoolToF32, ThemeColor}, widget_area
 };
 
 live_design! {
@@ -178,10 +178,13 @@ pub struct GCheckBox {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GCheckBox {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
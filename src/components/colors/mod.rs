@@ -3,7 +3,7 @@ mod register;
 
 pub use register::register;
 
-use crate::{shader::draw_view::DrawGView, themes::Themes};
+use crate::{shader::draw_view::DrawGView, sync_theme, themes::Themes};
 
 use super::{
     label::GLabelWidgetExt,
@@ -37,10 +37,13 @@ pub struct GColor {
     pub theme: Themes,
     #[live]
     pub item: Option<LivePtr>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GColor {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.is_visible() {
             return DrawStep::done();
         }
@@ -78,7 +81,7 @@ impl LiveHook for GColor {
                 .set_text_and_redraw(cx, &self.theme.to_string());
             self.header
                 .glabel(id!(theme_main))
-                .set_text_and_redraw(cx, &self.theme.hex(500));
+                .set_text_and_redraw(cx, &self.theme.hex_string(500));
         }
 
         if self.colors.is_visible() {
@@ -0,0 +1,16 @@
+use makepad_widgets::{ActionDefaultRef, DefaultNone, LiveId};
+
+#[derive(Debug, Clone, DefaultNone)]
+pub enum GContextMenuEvent {
+    /// an enabled leaf item is clicked
+    Selected(GContextMenuSelectedParam),
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub struct GContextMenuSelectedParam {
+    /// the id of the item
+    pub id: LiveId,
+    /// index of each level
+    pub path: Vec<usize>,
+}
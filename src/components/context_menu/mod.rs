@@ -0,0 +1,431 @@
+mod event;
+mod register;
+pub mod types;
+
+pub use event::*;
+pub use register::register;
+
+use makepad_widgets::*;
+use types::{find_path, get, get_mut, levels, ContextMenuItem};
+
+use crate::{
+    event_option, ref_event_option,
+    shader::manual::Position,
+    utils::ThemeColor,
+};
+
+use super::{
+    drop_down::{place, PlacementOptions},
+    icon::GIconWidgetRefExt,
+    label::GLabelWidgetRefExt,
+    popup::{GPopup, PopupStackGlobal},
+    view::{GView, GViewWidgetRefExt},
+};
+
+live_design! {
+    GContextMenuBase = {{GContextMenu}}{}
+}
+
+/// # GContextMenu
+/// Wrap any content, right-click (or long-press) it to open a menu at the pointer.
+/// The items are set by `set_items` (see `ContextMenuItem`),
+/// a sub menu flies out to the side of its item when hovered,
+/// selecting an enabled leaf posts `GContextMenuEvent::Selected` with the item id and closes the menu.
+///
+/// - Each level is a popup generated from `popup`, rows are generated from `item`
+/// (a GView with `text`, `shortcut` (GLabel) and `arrow` (GIcon) children) and `divider`.
+/// - A click outside or Escape closes the menu.
+#[derive(Live, Widget)]
+pub struct GContextMenu {
+    #[deref]
+    pub deref_widget: GView,
+    /// the popup template of each level
+    #[live]
+    pub popup: Option<LivePtr>,
+    /// row template (GView) of the items
+    #[live]
+    pub item: Option<LivePtr>,
+    /// row template of the separators
+    #[live]
+    pub divider: Option<LivePtr>,
+    #[live]
+    pub item_color: Option<Vec4>,
+    #[live]
+    pub shortcut_color: Option<Vec4>,
+    #[live]
+    pub disabled_color: Option<Vec4>,
+    #[live]
+    pub item_background_color: Option<Vec4>,
+    /// background of the item whose sub menu is open
+    #[live]
+    pub active_color: Option<Vec4>,
+    /// the gap between a sub menu and its item
+    #[live(2.0)]
+    pub sub_offset: f32,
+    /// the min distance between the menu and the window edge
+    #[live(8.0)]
+    pub edge_padding: f32,
+    /// open by long press (touch)
+    #[live(true)]
+    pub long_press: bool,
+    #[rust]
+    pub items: Vec<ContextMenuItem>,
+    /// path of the open sub menus (and the hovered leaf)
+    #[rust]
+    pub active: Vec<usize>,
+    #[rust]
+    pub opened: bool,
+    /// where the menu is opened
+    #[rust]
+    pub pos: DVec2,
+    #[rust]
+    menus: Vec<GPopup>,
+    /// the path of the parent item which each menu is generated for
+    #[rust]
+    built: Vec<Option<Vec<usize>>>,
+    #[rust(true)]
+    redraw_flag: bool,
+}
+
+impl LiveHook for GContextMenu {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) {
+        self.deref_widget.after_apply(cx, apply, index, nodes);
+        if apply.from.is_from_doc() {
+            // the templates may be changed, regenerate the menus when drawing
+            self.menus.clear();
+            self.built.clear();
+        }
+    }
+}
+
+impl Widget for GContextMenu {
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible {
+            return DrawStep::done();
+        }
+        let _ = self.deref_widget.draw_walk(cx, scope, walk);
+        if self.opened {
+            if self.menus.is_empty() {
+                self.sync_menus(cx);
+            }
+            self.draw_menus(cx, scope);
+        }
+        DrawStep::done()
+    }
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible {
+            return;
+        }
+        self.deref_widget.handle_event(cx, event, scope);
+        if self.opened {
+            self.handle_menus(cx, event, scope);
+        }
+        // the content may capture the finger, the menu still opens
+        match event.hits_with_capture_overload(cx, self.area(), true) {
+            Hit::FingerDown(e)
+                if e.device.mouse_button().is_some_and(|button| button.is_secondary()) =>
+            {
+                self.open_at(cx, e.abs);
+            }
+            Hit::FingerLongPress(e) if self.long_press => {
+                self.open_at(cx, e.abs);
+            }
+            _ => {}
+        }
+    }
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+impl GContextMenu {
+    event_option! {
+        selected: GContextMenuEvent::Selected => GContextMenuSelectedParam
+    }
+    fn area(&self) -> Area {
+        self.deref_widget.area
+    }
+    pub fn redraw(&mut self, cx: &mut Cx) {
+        self.deref_widget.redraw(cx);
+    }
+    /// open the menu at the position (absolute), nothing happens if there are no items
+    pub fn open_at(&mut self, cx: &mut Cx, pos: DVec2) {
+        if self.items.is_empty() {
+            return;
+        }
+        self.opened = true;
+        self.pos = pos;
+        self.active.clear();
+        self.sync_menus(cx);
+        for menu in self.menus.iter_mut() {
+            menu.raise();
+        }
+        PopupStackGlobal::open(cx, self.widget_uid(), self.area());
+    }
+    pub fn close(&mut self, cx: &mut Cx) {
+        if !self.opened {
+            return;
+        }
+        self.opened = false;
+        self.active.clear();
+        self.redraw(cx);
+        PopupStackGlobal::close(cx, self.widget_uid(), self.area());
+    }
+    pub fn set_items(&mut self, cx: &mut Cx, items: Vec<ContextMenuItem>) {
+        self.items = items;
+        self.active.clear();
+        self.built.clear();
+        self.menus.clear();
+        if self.opened {
+            self.sync_menus(cx);
+        }
+    }
+    /// enable or disable the item with the id, return false if not found
+    pub fn set_disabled(&mut self, cx: &mut Cx, id: LiveId, disabled: bool) -> bool {
+        let path = match find_path(&self.items, id) {
+            Some(path) => path,
+            None => return false,
+        };
+        if let Some(item) = get_mut(&mut self.items, &path) {
+            item.disabled = disabled;
+        }
+        // regenerate the menu which has the item
+        if let Some(built) = self.built.get_mut(path.len() - 1) {
+            built.take();
+        }
+        if self.active.starts_with(&path) {
+            self.active.truncate(path.len() - 1);
+        }
+        if self.opened {
+            self.sync_menus(cx);
+        }
+        true
+    }
+    /// ## Hover an item
+    /// a sub menu opens, a leaf closes the deeper sub menus
+    fn hover(&mut self, cx: &mut Cx, level: usize, index: usize) {
+        let mut active = self.active[..level.min(self.active.len())].to_vec();
+        active.push(index);
+        if active != self.active {
+            self.active = active;
+            self.sync_menus(cx);
+        }
+    }
+    fn select(&mut self, cx: &mut Cx, level: usize, index: usize) {
+        let mut path = self.active[..level.min(self.active.len())].to_vec();
+        path.push(index);
+        let item = match get(&self.items, &path) {
+            Some(item) => item,
+            None => return,
+        };
+        if !item.is_selectable() {
+            // click a sub menu item also opens it
+            self.hover(cx, level, index);
+            return;
+        }
+        let id = item.id;
+        self.close(cx);
+        if self.event_key {
+            if let Some(scope_path) = self.scope_path.as_ref() {
+                cx.widget_action(
+                    self.widget_uid(),
+                    scope_path,
+                    GContextMenuEvent::Selected(GContextMenuSelectedParam { id, path }),
+                );
+            }
+        }
+    }
+    /// ## Sync menus
+    /// generate a popup for each level of the active path,
+    /// rows are regenerated only if the parent item of the level is changed
+    fn sync_menus(&mut self, cx: &mut Cx) {
+        let item_color = self.item_color.use_or("#FFFFFF");
+        let shortcut_color = self.shortcut_color.use_or("#98A2B3");
+        let disabled_color = self.disabled_color.use_or("#667085");
+        let background_color = self.item_background_color.use_or("#22272F");
+        let active_color = self.active_color.use_or("#42464D");
+        let levels = levels(&self.items, &self.active);
+        self.menus.truncate(levels.len());
+        self.built.truncate(levels.len());
+        for (level, items) in levels.iter().enumerate() {
+            if self.menus.get(level).is_none() {
+                self.menus.push(GPopup::new_from_ptr(cx, self.popup));
+                self.built.push(None);
+            }
+            let parent = &self.active[..level];
+            let container = self.menus[level].get_mut();
+            if self.built[level].as_deref() != Some(parent) {
+                container.children.clear();
+                for (index, item) in items.iter().enumerate() {
+                    let ptr = if item.separator {
+                        self.divider
+                    } else {
+                        self.item
+                    };
+                    let row = WidgetRef::new_from_ptr(cx, ptr);
+                    if !item.separator {
+                        let text = row.glabel(id!(text));
+                        text.set_text(&item.text);
+                        let color = if item.disabled {
+                            disabled_color
+                        } else {
+                            item_color
+                        };
+                        text.apply_over(cx, live! {color: (color)});
+                        let shortcut = row.glabel(id!(shortcut));
+                        shortcut.set_text(&item.shortcut);
+                        shortcut.apply_over(cx, live! {color: (shortcut_color)});
+                        if let Some(mut shortcut) = shortcut.borrow_mut() {
+                            shortcut.visible = !item.shortcut.is_empty();
+                        }
+                        if let Some(mut arrow) = row.gicon(id!(arrow)).borrow_mut() {
+                            arrow.visible = item.is_sub_menu();
+                        }
+                        row.apply_over(cx, live! {animation_key: (!item.disabled)});
+                    }
+                    container.children.push((LiveId(index as u64), row));
+                }
+                self.built[level].replace(parent.to_vec());
+            }
+            // highlight the item whose sub menu is open
+            for (index, (_, row)) in container.children.iter().enumerate() {
+                if items.get(index).map_or(true, |item| item.separator) {
+                    continue;
+                }
+                let is_active = level + 1 < levels.len() && self.active.get(level) == Some(&index);
+                let color = if is_active {
+                    active_color
+                } else {
+                    background_color
+                };
+                row.apply_over(cx, live! {background_color: (color)});
+            }
+            container.redraw(cx);
+        }
+        self.redraw_flag = true;
+        self.redraw(cx);
+    }
+    fn handle_menus(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        // check the stack before the popups opened from the menus handle the event
+        let uid = self.widget_uid();
+        let is_top = PopupStackGlobal::is_top(cx, uid);
+        let in_above = match event {
+            Event::MouseDown(e) => PopupStackGlobal::above_contains_pos(cx, uid, e.abs),
+            _ => false,
+        };
+        let area = self.area();
+        let mut hovered = None;
+        let mut clicked = None;
+        for (level, menu) in self.menus.iter_mut().enumerate() {
+            let actions = cx.capture_actions(|cx| menu.handle_event_with(cx, event, scope, area));
+            if actions.is_empty() {
+                continue;
+            }
+            for (index, (_, row)) in menu.get().children.iter().enumerate() {
+                let row = row.as_gview();
+                if row.hover_in(&actions).is_some() {
+                    hovered.replace((level, index));
+                }
+                if row.clicked(&actions).is_some() {
+                    clicked.replace((level, index));
+                }
+            }
+        }
+        let outside = match event {
+            Event::MouseDown(e) => {
+                !in_above
+                    && !self
+                        .menus
+                        .iter()
+                        .any(|menu| menu.container_contains_pos(cx, e.abs))
+            }
+            // only the topmost popup is closed
+            Event::KeyDown(e) => e.key_code == KeyCode::Escape && is_top,
+            _ => false,
+        };
+        if let Some((level, index)) = hovered {
+            self.hover(cx, level, index);
+        }
+        if let Some((level, index)) = clicked {
+            self.select(cx, level, index);
+        }
+        if outside {
+            self.close(cx);
+        }
+    }
+    /// ## Draw menus
+    /// the first menu is placed at the pointer, a sub menu at the side of its item,
+    /// both flip to the other side and shift to stay in the window
+    fn draw_menus(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        let viewport = Rect {
+            pos: DVec2::default(),
+            size: cx.current_pass_size(),
+        };
+        let mut anchor = Rect {
+            pos: self.pos,
+            size: DVec2::default(),
+        };
+        let mut offset = 0.0;
+        for (level, menu) in self.menus.iter_mut().enumerate() {
+            let mut redraw = self.redraw_flag;
+            menu.begin(cx);
+            menu.draw_container(cx, scope, None, 0.0, &mut redraw);
+            let size = menu.container_area().rect(cx).size;
+            let placement = place(
+                Position::RightTop,
+                anchor,
+                size,
+                viewport,
+                &PlacementOptions {
+                    offset,
+                    offset_x: 0.0,
+                    offset_y: 0.0,
+                    flip: true,
+                    shift: true,
+                    constrain_height: false,
+                    arrow: false,
+                    padding: self.edge_padding as f64,
+                },
+            );
+            menu.end(cx, scope, Area::Empty, anchor.pos + placement.shift);
+            // the next menu flies out from the active item
+            match self
+                .active
+                .get(level)
+                .and_then(|index| menu.get().children.get(*index))
+            {
+                Some((_, row)) => anchor = row.area().rect(cx),
+                None => break,
+            }
+            offset = self.sub_offset as f64;
+        }
+        self.redraw_flag = false;
+        let areas = self.menus.iter().map(|menu| menu.container_area()).collect();
+        PopupStackGlobal::set_areas(cx, self.widget_uid(), areas);
+    }
+}
+
+impl GContextMenuRef {
+    ref_event_option! {
+        selected => GContextMenuSelectedParam
+    }
+    pub fn set_items(&self, cx: &mut Cx, items: Vec<ContextMenuItem>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_items(cx, items);
+        }
+    }
+    pub fn set_disabled(&self, cx: &mut Cx, id: LiveId, disabled: bool) -> bool {
+        self.borrow_mut()
+            .map_or(false, |mut c_ref| c_ref.set_disabled(cx, id, disabled))
+    }
+    pub fn open_at(&self, cx: &mut Cx, pos: DVec2) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.open_at(cx, pos);
+        }
+    }
+    pub fn close(&self, cx: &mut Cx) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.close(cx);
+        }
+    }
+}
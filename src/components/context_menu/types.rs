@@ -0,0 +1,164 @@
+use makepad_widgets::LiveId;
+
+/// # Context Menu Item
+/// An item of GContextMenu, build it with the builder methods:
+/// ```rust
+/// let items = vec![
+///     ContextMenuItem::new(live_id!(copy), "Copy").with_shortcut("Ctrl+C"),
+///     ContextMenuItem::new(live_id!(paste), "Paste").with_disabled(true),
+///     ContextMenuItem::separator(),
+///     ContextMenuItem::new(live_id!(share), "Share").with_children(vec![
+///         ContextMenuItem::new(live_id!(mail), "Mail"),
+///     ]),
+/// ];
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ContextMenuItem {
+    pub id: LiveId,
+    pub text: String,
+    /// the keyboard shortcut hint shown at the right side (only a hint, not bound)
+    pub shortcut: String,
+    /// a disabled item can not be selected and its sub menu does not open
+    pub disabled: bool,
+    /// a separator line, the other fields are ignored
+    pub separator: bool,
+    /// the items of the sub menu
+    pub children: Vec<ContextMenuItem>,
+}
+
+impl ContextMenuItem {
+    pub fn new(id: LiveId, text: &str) -> Self {
+        Self {
+            id,
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+    pub fn separator() -> Self {
+        Self {
+            separator: true,
+            ..Default::default()
+        }
+    }
+    pub fn with_shortcut(mut self, shortcut: &str) -> Self {
+        self.shortcut = shortcut.to_string();
+        self
+    }
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+    pub fn with_children(mut self, children: Vec<ContextMenuItem>) -> Self {
+        self.children = children;
+        self
+    }
+    pub fn is_sub_menu(&self) -> bool {
+        !self.separator && !self.children.is_empty()
+    }
+    /// an enabled leaf, selecting it closes the menu
+    pub fn is_selectable(&self) -> bool {
+        !self.separator && !self.disabled && self.children.is_empty()
+    }
+}
+
+/// the item at the path (index in each level)
+pub fn get<'a>(items: &'a [ContextMenuItem], path: &[usize]) -> Option<&'a ContextMenuItem> {
+    let (first, rest) = path.split_first()?;
+    rest.iter()
+        .try_fold(items.get(*first)?, |item, index| item.children.get(*index))
+}
+
+pub fn get_mut<'a>(
+    items: &'a mut [ContextMenuItem],
+    path: &[usize],
+) -> Option<&'a mut ContextMenuItem> {
+    let (first, rest) = path.split_first()?;
+    rest.iter()
+        .try_fold(items.get_mut(*first)?, |item, index| item.children.get_mut(*index))
+}
+
+/// the path of the first item with the id (depth first)
+pub fn find_path(items: &[ContextMenuItem], id: LiveId) -> Option<Vec<usize>> {
+    for (index, item) in items.iter().enumerate() {
+        if item.separator {
+            continue;
+        }
+        if item.id == id {
+            return Some(vec![index]);
+        }
+        if let Some(mut path) = find_path(&item.children, id) {
+            path.insert(0, index);
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// ## Levels
+/// the menus of the active path: the roots, then the sub menu of each active item,
+/// stop at the first item which is not an enabled sub menu
+pub fn levels<'a>(items: &'a [ContextMenuItem], active: &[usize]) -> Vec<&'a [ContextMenuItem]> {
+    let mut levels = vec![items];
+    let mut level = items;
+    for index in active {
+        match level.get(*index) {
+            Some(item) if item.is_sub_menu() && !item.disabled => {
+                level = item.children.as_slice();
+                levels.push(level);
+            }
+            _ => break,
+        }
+    }
+    levels
+}
+
+#[cfg(test)]
+mod test_context_menu {
+    use super::*;
+
+    fn items() -> Vec<ContextMenuItem> {
+        vec![
+            ContextMenuItem::new(LiveId(1), "Copy").with_shortcut("Ctrl+C"),
+            ContextMenuItem::separator(),
+            ContextMenuItem::new(LiveId(2), "Share").with_children(vec![
+                ContextMenuItem::new(LiveId(3), "Mail"),
+                ContextMenuItem::new(LiveId(4), "More").with_children(vec![
+                    ContextMenuItem::new(LiveId(5), "Print").with_disabled(true),
+                ]),
+            ]),
+            ContextMenuItem::new(LiveId(6), "Export")
+                .with_disabled(true)
+                .with_children(vec![ContextMenuItem::new(LiveId(7), "PDF")]),
+        ]
+    }
+
+    #[test]
+    fn kinds() {
+        let items = items();
+        assert!(items[0].is_selectable());
+        assert!(!items[1].is_selectable() && !items[1].is_sub_menu());
+        assert!(items[2].is_sub_menu() && !items[2].is_selectable());
+        assert!(!get(&items, &[2, 1, 0]).unwrap().is_selectable());
+    }
+
+    #[test]
+    fn paths() {
+        let items = items();
+        assert_eq!(get(&items, &[2, 0]).map(|item| item.id), Some(LiveId(3)));
+        assert!(get(&items, &[0, 0]).is_none());
+        assert_eq!(find_path(&items, LiveId(5)), Some(vec![2, 1, 0]));
+        assert_eq!(find_path(&items, LiveId(8)), None);
+    }
+
+    #[test]
+    fn open_levels() {
+        let items = items();
+        assert_eq!(levels(&items, &[]).len(), 1);
+        assert_eq!(levels(&items, &[0]).len(), 1);
+        let open = levels(&items, &[2, 1]);
+        assert_eq!(open.len(), 3);
+        assert_eq!(open[2][0].id, LiveId(5));
+        // a disabled sub menu does not open
+        assert_eq!(levels(&items, &[3]).len(), 1);
+    }
+}
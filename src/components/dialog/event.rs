@@ -0,0 +1,44 @@
+use makepad_widgets::Actions;
+
+/// The answer of a dialog opened by `GDialog::alert/confirm/prompt`
+#[derive(Debug, Clone, PartialEq)]
+pub enum GDialogResult {
+    /// index of the clicked button
+    Button(usize),
+    /// the text of a prompt, when the last button is clicked or return is pressed
+    Prompt(String),
+    /// closed without an answer (click the mask or press Escape)
+    Dismissed,
+}
+
+/// The action posted when a dialog is answered, match it with `GDialogHandle::result`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GDialogAction {
+    pub id: u64,
+    pub result: GDialogResult,
+}
+
+/// # Dialog Handle
+/// returned by `GDialog::alert/confirm/prompt`, the result arrives as an action
+/// ```rust
+/// let handle = GDialog::confirm(cx, "Delete", "Are you sure?", &["Cancel", "Delete"]);
+/// // in handle_actions
+/// if let Some(GDialogResult::Button(1)) = handle.result(actions) {
+///     // delete ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GDialogHandle {
+    pub id: u64,
+}
+
+impl GDialogHandle {
+    pub fn result(&self, actions: &Actions) -> Option<GDialogResult> {
+        actions.iter().find_map(|action| {
+            action
+                .downcast_ref::<GDialogAction>()
+                .filter(|action| action.id == self.id)
+                .map(|action| action.result.clone())
+        })
+    }
+}
@@ -0,0 +1,315 @@
+mod event;
+mod register;
+pub mod types;
+
+pub use event::*;
+pub use register::register;
+
+use std::{cell::RefCell, rc::Rc};
+
+use makepad_widgets::*;
+use types::{DialogQueue, GDialogKind, GDialogRequest};
+
+use super::{
+    button::GButtonWidgetRefExt,
+    drop_down::{next_focus, PopupMenuGlobal},
+    input::GInputWidgetExt,
+    label::GLabelWidgetExt,
+    popup::{GPopup, PopupStackGlobal},
+    view::GViewWidgetExt,
+};
+
+live_design! {
+    GDialogHostBase = {{GDialogHost}}{}
+}
+
+#[derive(Default, Clone)]
+pub struct GDialogGlobal {
+    pub queue: Rc<RefCell<DialogQueue>>,
+}
+
+/// # Programmatic Dialog
+/// Open a dialog from rust without declaring it in `live_design!`,
+/// the dialog is shown by the `GDialogHost` of `GWindow` (one by one)
+/// and the answer arrives as a `GDialogAction`, see `GDialogHandle::result`
+pub struct GDialog;
+
+impl GDialog {
+    pub fn open(cx: &mut Cx, request: GDialogRequest) -> GDialogHandle {
+        let global = cx.global::<GDialogGlobal>().clone();
+        let id = global.queue.borrow_mut().push(request);
+        cx.redraw_all();
+        GDialogHandle { id }
+    }
+    /// a message with an `OK` button
+    pub fn alert(cx: &mut Cx, title: &str, message: &str) -> GDialogHandle {
+        Self::open(
+            cx,
+            GDialogRequest {
+                kind: GDialogKind::Alert,
+                title: title.to_string(),
+                message: message.to_string(),
+                buttons: vec!["OK".to_string()],
+                ..Default::default()
+            },
+        )
+    }
+    /// a message with buttons, the last one is the primary button.
+    /// empty buttons means `["Cancel", "OK"]`
+    pub fn confirm(cx: &mut Cx, title: &str, message: &str, buttons: &[&str]) -> GDialogHandle {
+        let buttons = if buttons.is_empty() {
+            vec!["Cancel".to_string(), "OK".to_string()]
+        } else {
+            buttons.iter().map(|button| button.to_string()).collect()
+        };
+        Self::open(
+            cx,
+            GDialogRequest {
+                kind: GDialogKind::Confirm,
+                title: title.to_string(),
+                message: message.to_string(),
+                buttons,
+                ..Default::default()
+            },
+        )
+    }
+    /// a message with an input, `OK` answers with `GDialogResult::Prompt(text)`
+    pub fn prompt(cx: &mut Cx, title: &str, message: &str, value: &str) -> GDialogHandle {
+        Self::open(
+            cx,
+            GDialogRequest {
+                kind: GDialogKind::Prompt,
+                title: title.to_string(),
+                message: message.to_string(),
+                buttons: vec!["Cancel".to_string(), "OK".to_string()],
+                value: value.to_string(),
+                ..Default::default()
+            },
+        )
+    }
+    /// close a dialog which is not answered, nothing is posted
+    pub fn close(cx: &mut Cx, handle: GDialogHandle) {
+        let global = cx.global::<GDialogGlobal>().clone();
+        global.queue.borrow_mut().close(handle.id);
+        cx.redraw_all();
+    }
+}
+
+/// # Dialog Host
+/// Draw the dialogs of `GDialog` with the popup (a `GDialog` popup) in `PopupMenuGlobal`,
+/// the popup container should have `title`, `message`, `input` and `buttons`
+#[derive(Live, LiveRegister)]
+pub struct GDialogHost {
+    #[live]
+    pub popup: Option<LivePtr>,
+    #[live]
+    pub button: Option<LivePtr>,
+    #[live]
+    pub primary_button: Option<LivePtr>,
+    /// click the mask to dismiss the dialog
+    #[live(true)]
+    pub mask_closable: bool,
+    #[rust]
+    pub current: Option<GDialogRequest>,
+    #[rust(true)]
+    pub redraw_flag: bool,
+}
+
+impl LiveHook for GDialogHost {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if self.popup.is_none() || !apply.from.is_from_doc() {
+            return;
+        }
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut global_map = global.map.borrow_mut();
+        global_map.retain(|k, _| cx.live_registry.borrow().generation_valid(*k));
+        let popup = self.popup.unwrap();
+        global_map.get_or_insert(cx, popup, |cx| GPopup::new_from_ptr(cx, Some(popup)));
+    }
+}
+
+impl GDialogHost {
+    /// show the next dialog in the queue
+    fn next(&mut self, cx: &mut Cx) {
+        let global = cx.global::<GDialogGlobal>().clone();
+        let mut queue = global.queue.borrow_mut();
+        if let Some(current) = self.current.as_ref() {
+            if !queue.take_closed(current.id) {
+                return;
+            }
+            // closed by `GDialog::close`
+            self.current = None;
+            self.unlock(cx);
+        }
+        let request = queue.pop();
+        drop(queue);
+        if let Some(request) = request {
+            self.sync(cx, &request);
+            self.current.replace(request);
+            self.redraw_flag = true;
+        }
+    }
+    /// fill the popup container with the request
+    fn sync(&mut self, cx: &mut Cx, request: &GDialogRequest) {
+        let popup = match self.popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let container = match map.get_mut(&popup) {
+            Some(popup) => popup.get_mut(),
+            None => return,
+        };
+        let title = container.glabel(id!(title));
+        title.set_text(&request.title);
+        if let Some(mut title) = title.borrow_mut() {
+            title.visible = !request.title.is_empty();
+        }
+        container.glabel(id!(message)).set_text(&request.message);
+        let input = container.ginput(id!(input));
+        input.set_text(&request.value);
+        if let Some(mut input) = input.borrow_mut() {
+            input.visible = request.kind == GDialogKind::Prompt;
+        }
+        if let Some(mut buttons) = container.gview(id!(buttons)).borrow_mut() {
+            buttons.children.clear();
+            for (index, text) in request.buttons.iter().enumerate() {
+                let ptr = if Some(index) == request.primary() {
+                    self.primary_button
+                } else {
+                    self.button
+                };
+                let button = WidgetRef::new_from_ptr(cx, ptr);
+                if let Some(button) = button.as_gbutton().borrow() {
+                    button.slot.set_text(text);
+                }
+                buttons.children.push((LiveId(index as u64), button));
+            }
+        }
+        container.redraw(cx);
+    }
+    /// the id of the dialog in `PopupStack`, the host lives in the app so its address does not change
+    fn uid(&self) -> WidgetUid {
+        WidgetUid(self as *const Self as u64)
+    }
+    /// leave the popup stack, the popup below (the dialog may be opened from a popup) gets the lock back
+    fn unlock(&mut self, cx: &mut Cx) {
+        let area = self.popup.and_then(|popup| {
+            let global = cx.global::<PopupMenuGlobal>().clone();
+            let map = global.map.borrow();
+            map.get(&popup).map(|popup| popup.area())
+        });
+        PopupStackGlobal::close(cx, self.uid(), area.unwrap_or(Area::Empty));
+    }
+    /// post the result and close the dialog
+    fn answer(&mut self, cx: &mut Cx, result: GDialogResult) {
+        if let Some(request) = self.current.take() {
+            cx.action(GDialogAction {
+                id: request.id,
+                result,
+            });
+            cx.redraw_all();
+        }
+    }
+    pub fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let (popup, request) = match (self.popup, self.current.as_ref()) {
+            (Some(popup), Some(request)) => (popup, request.clone()),
+            _ => return,
+        };
+        let uid = self.uid();
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        let area = popup.area();
+        let actions = cx.capture_actions(|cx| popup.handle_event_with(cx, event, scope, area));
+        let mut result = None;
+        if !actions.is_empty() {
+            let container = popup.get();
+            let input = container.ginput(id!(input));
+            if let Some(buttons) = container.gview(id!(buttons)).borrow() {
+                for (index, (_, button)) in buttons.children.iter().enumerate() {
+                    if button.as_gbutton().clicked(&actions).is_some() {
+                        result.replace(request.answer(index, &input.text()));
+                    }
+                }
+            }
+            if let (Some(text), Some(primary)) = (input.returned(&actions), request.primary()) {
+                result.replace(request.answer(primary, &text));
+            }
+        }
+        // the dialog is modal: Tab cycles in the dialog and Escape dismisses it
+        if let Event::KeyDown(e) = event {
+            match e.key_code {
+                KeyCode::Tab => {
+                    let stops = popup.get().focus_stops();
+                    let current = stops.iter().position(|area| cx.has_key_focus(*area));
+                    if let Some(next) = next_focus(current, stops.len(), e.modifiers.shift) {
+                        cx.set_key_focus(stops[next]);
+                    }
+                }
+                // a popup opened from the dialog (above it) closes first
+                KeyCode::Escape if PopupStackGlobal::is_top(cx, uid) => {
+                    result.replace(GDialogResult::Dismissed);
+                }
+                _ => {}
+            }
+        }
+        if let Event::MouseDown(e) = event {
+            if self.mask_closable
+                && result.is_none()
+                && !popup.container_contains_pos(cx, e.abs)
+                && !PopupStackGlobal::above_contains_pos(cx, uid, e.abs)
+            {
+                result.replace(GDialogResult::Dismissed);
+            }
+        }
+        drop(map);
+        if let Some(result) = result {
+            self.unlock(cx);
+            self.answer(cx, result);
+        }
+    }
+    pub fn draw(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        self.next(cx);
+        let popup = match (self.popup, self.current.is_some()) {
+            (Some(popup), true) => popup,
+            _ => return,
+        };
+        let uid = self.uid();
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        let is_new = self.redraw_flag;
+        if is_new {
+            popup.raise();
+        }
+        popup.begin(cx);
+        popup.draw_container(cx, scope, None, 0.0, &mut self.redraw_flag);
+        popup.end(cx, scope, Area::Empty, DVec2::default());
+        if is_new {
+            // the dialog is modal, only the popup gets the finger events
+            PopupStackGlobal::open(cx, uid, popup.area());
+            // focus the input of a prompt, otherwise the primary button
+            let stops = popup.get().focus_stops();
+            let is_prompt = self
+                .current
+                .as_ref()
+                .map_or(false, |request| request.kind == GDialogKind::Prompt);
+            let focus = if is_prompt { stops.first() } else { stops.last() };
+            if let Some(focus) = focus {
+                cx.set_key_focus(*focus);
+            }
+        }
+        PopupStackGlobal::set_areas(cx, uid, vec![popup.container_area()]);
+    }
+    pub fn is_open(&self) -> bool {
+        self.current.is_some()
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+
+use super::event::GDialogResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GDialogKind {
+    #[default]
+    Alert,
+    Confirm,
+    /// has an input, the last button answers with the text
+    Prompt,
+}
+
+/// A dialog waiting to be shown by `GDialogHost`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GDialogRequest {
+    pub id: u64,
+    pub kind: GDialogKind,
+    pub title: String,
+    pub message: String,
+    /// the last one is the primary button
+    pub buttons: Vec<String>,
+    /// the initial text of a prompt
+    pub value: String,
+}
+
+impl GDialogRequest {
+    /// the result of clicking the button at index
+    pub fn answer(&self, button: usize, text: &str) -> GDialogResult {
+        if self.kind == GDialogKind::Prompt && button + 1 == self.buttons.len() {
+            GDialogResult::Prompt(text.to_string())
+        } else {
+            GDialogResult::Button(button)
+        }
+    }
+    /// the index of the primary button
+    pub fn primary(&self) -> Option<usize> {
+        self.buttons.len().checked_sub(1)
+    }
+}
+
+/// The dialogs which are opened but not answered, shown one by one
+#[derive(Debug, Default)]
+pub struct DialogQueue {
+    pub next_id: u64,
+    pub requests: VecDeque<GDialogRequest>,
+    /// the shown dialogs closed by `GDialog::close`
+    pub closed: Vec<u64>,
+}
+
+impl DialogQueue {
+    pub fn push(&mut self, mut request: GDialogRequest) -> u64 {
+        self.next_id += 1;
+        request.id = self.next_id;
+        self.requests.push_back(request);
+        self.next_id
+    }
+    pub fn pop(&mut self) -> Option<GDialogRequest> {
+        self.requests.pop_front()
+    }
+    /// remove a dialog which is not shown yet, return false if it is not in the queue
+    pub fn remove(&mut self, id: u64) -> bool {
+        let len = self.requests.len();
+        self.requests.retain(|request| request.id != id);
+        self.requests.len() != len
+    }
+    /// remove the dialog, or mark it closed if it is shown
+    pub fn close(&mut self, id: u64) {
+        if !self.remove(id) && id <= self.next_id && !self.closed.contains(&id) {
+            self.closed.push(id);
+        }
+    }
+    /// check whether the shown dialog is closed
+    pub fn take_closed(&mut self, id: u64) -> bool {
+        let len = self.closed.len();
+        self.closed.retain(|closed| *closed != id);
+        self.closed.len() != len
+    }
+}
+
+#[cfg(test)]
+mod test_dialog {
+    use super::*;
+
+    fn request(kind: GDialogKind) -> GDialogRequest {
+        GDialogRequest {
+            kind,
+            buttons: vec!["Cancel".to_string(), "OK".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn answer() {
+        let confirm = request(GDialogKind::Confirm);
+        assert_eq!(confirm.answer(1, ""), GDialogResult::Button(1));
+        let prompt = request(GDialogKind::Prompt);
+        assert_eq!(prompt.answer(0, "name"), GDialogResult::Button(0));
+        assert_eq!(prompt.answer(1, "name"), GDialogResult::Prompt("name".to_string()));
+        assert_eq!(prompt.primary(), Some(1));
+    }
+
+    #[test]
+    fn queue() {
+        let mut queue = DialogQueue::default();
+        let first = queue.push(request(GDialogKind::Alert));
+        let second = queue.push(request(GDialogKind::Confirm));
+        assert_ne!(first, second);
+        assert!(queue.remove(second));
+        assert!(!queue.remove(second));
+        assert_eq!(queue.pop().map(|request| request.id), Some(first));
+        assert!(queue.pop().is_none());
+        // the shown one is closed later
+        queue.close(first);
+        assert!(queue.take_closed(first));
+        assert!(!queue.take_closed(first));
+    }
+}
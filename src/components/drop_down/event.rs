@@ -1,5 +1,5 @@
 use makepad_widgets::{
-    ActionDefaultRef, DefaultNone, FingerDownEvent, FingerHoverEvent, FingerUpEvent
+    ActionDefaultRef, DefaultNone, FingerDownEvent, FingerHoverEvent, FingerUpEvent, KeyEvent
 };
 
 #[derive(Clone, Debug, DefaultNone)]
@@ -19,6 +19,8 @@ pub enum GDropDownToggleKind {
     Click(FingerUpEvent),
     Hover(FingerHoverEvent),
     Press(FingerDownEvent),
+    /// Escape is pressed in a modal popup (Dialog, Drawer)
+    Escape(KeyEvent),
     // KetFocusLost(KeyFocusEvent),
     #[default]
     Other,
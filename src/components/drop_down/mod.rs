@@ -1,7 +1,9 @@
 mod event;
 mod register;
+mod types;
 
 pub use event::*;
+pub use types::*;
 // use event::*;
 use makepad_widgets::*;
 pub use register::register;
@@ -14,7 +16,7 @@ use crate::{
 use icon_atlas::RefCell;
 
 use super::{
-    popup::{GPopup, GPopupContainer},
+    popup::{GPopup, GPopupContainer, PopupStackGlobal},
     view::GView,
 };
 
@@ -42,6 +44,30 @@ pub struct GDropDown {
     pub offset_x: f32,
     #[live]
     pub offset_y: f32,
+    /// flip to the opposite side when the popup overflows the window
+    #[live(true)]
+    pub flip: bool,
+    /// shift the popup along the edge of the trigger to stay in the window
+    #[live(true)]
+    pub shift: bool,
+    /// constrain the height of the popup to the available room, the container scrolls the rest
+    #[live(true)]
+    pub constrain_height: bool,
+    /// let the arrow (ToolTip) point to the center of the trigger
+    #[live]
+    pub arrow: bool,
+    /// the min distance between the popup and the window edge
+    #[live(8.0)]
+    pub edge_padding: f32,
+    /// seconds to wait before opening (hover trigger)
+    #[live(0.0)]
+    pub show_delay: f64,
+    /// seconds to wait before closing when the pointer leaves both the trigger and the popup (hover trigger)
+    #[live(0.1)]
+    pub hide_delay: f64,
+    /// the popup is placed at the pointer and follows it (hover trigger)
+    #[live]
+    pub follow_cursor: bool,
     // visible -------------------
     #[live(true)]
     pub visible: bool,
@@ -54,6 +80,23 @@ pub struct GDropDown {
     pub close_mode: CloseMode,
     #[rust(true)]
     pub redraw_flag: bool,
+    /// the placement of the last draw, None before first draw after open
+    #[rust]
+    pub placement: Option<Placement>,
+    /// the size of the popup container without the constraint, measured in each draw
+    #[rust]
+    pub natural_size: DVec2,
+    /// move the key focus into the modal popup after it is drawn
+    #[rust]
+    pub focus_first: bool,
+    /// the delay timer of hover, `delay_open` tells what to do when it fires
+    #[rust]
+    pub delay_timer: Timer,
+    #[rust]
+    pub delay_open: bool,
+    /// the last pointer position over the trigger
+    #[rust]
+    pub cursor_pos: DVec2,
 }
 
 #[derive(Default, Clone)]
@@ -81,6 +124,10 @@ impl GDropDown {
     fn area(&self) -> Area {
         self.deref_widget.area
     }
+    /// the popup is anchored at the pointer: `follow_cursor` or the right click trigger
+    fn at_cursor(&self) -> bool {
+        self.follow_cursor || self.trigger_mode.is_right_click()
+    }
     pub fn changed(&mut self, actions: &Actions) -> Option<GDropDownChangedParam> {
         if let GDropDownEvent::Changed(e) = actions.find_widget_action(self.widget_uid()).cast() {
             Some(e)
@@ -102,10 +149,23 @@ impl GDropDown {
         // we don't need to care close mode here
         self.opened = false;
         self.redraw(cx);
-        cx.sweep_unlock(self.area());
+        PopupStackGlobal::close(cx, self.widget_uid(), self.area());
+        self.restore_focus(cx);
         self.active_toggled(cx, GDropDownToggleKind::Other);
         self.redraw_flag = true;
     }
+    fn placement_options(&self) -> PlacementOptions {
+        PlacementOptions {
+            offset: self.offset as f64,
+            offset_x: self.offset_x as f64,
+            offset_y: self.offset_y as f64,
+            flip: self.flip,
+            shift: self.shift,
+            constrain_height: self.constrain_height,
+            arrow: self.arrow,
+            padding: self.edge_padding as f64,
+        }
+    }
     pub fn toggle(&mut self, cx: &mut Cx) {
         if self.opened {
             self.close(cx);
@@ -119,8 +179,17 @@ impl GDropDown {
             return;
         }
         self.opened = true;
+        self.placement = None;
+        self.focus_first = self.is_modal();
         self.redraw(cx);
-        cx.sweep_lock(self.area());
+        if let Some(popup) = self.popup {
+            let global = cx.global::<PopupMenuGlobal>().clone();
+            let mut map = global.map.borrow_mut();
+            if let Some(popup) = map.get_mut(&popup) {
+                popup.raise();
+            }
+        }
+        PopupStackGlobal::open(cx, self.widget_uid(), self.area());
         self.active_toggled(cx, e_kind);
     }
     /// close the popup only inner control
@@ -129,25 +198,69 @@ impl GDropDown {
         if !self.opened {
             return;
         }
-        let mut flag = false;
-        match self.close_mode {
-            CloseMode::Out => {
-                if !is_in {
-                    flag = true;
-                }
-            }
-            CloseMode::Virtual => {
-                flag = false;
-            }
-        }
+        let flag = self.close_mode.close_on_out() && !is_in;
         if flag {
             self.opened = false;
             self.redraw(cx);
-            cx.sweep_unlock(self.area());
+            PopupStackGlobal::close(cx, self.widget_uid(), self.area());
+            self.restore_focus(cx);
             self.active_toggled(cx, e_kind);
         }
         self.redraw_flag = true;
     }
+    fn stop_delay(&mut self, cx: &mut Cx) {
+        if !self.delay_timer.is_empty() {
+            cx.stop_timer(self.delay_timer);
+            self.delay_timer = Timer::empty();
+        }
+    }
+    /// ## Hover leave
+    /// close (after `hide_delay`) when the pointer is out of both the trigger and the popup
+    fn hover_leave(&mut self, cx: &mut Cx, is_in: bool) {
+        if is_in {
+            self.stop_delay(cx);
+        } else if self.delay_timer.is_empty() {
+            if self.hide_delay > 0.0 {
+                self.delay_open = false;
+                self.delay_timer = cx.start_timeout(self.hide_delay);
+            } else {
+                self.close_inner(cx, GDropDownToggleKind::Other, false);
+            }
+        }
+    }
+    fn is_modal(&self) -> bool {
+        matches!(self.mode, PopupMode::Dialog | PopupMode::Drawer)
+    }
+    /// give the key focus back to the trigger when a modal popup is closed
+    fn restore_focus(&self, cx: &mut Cx) {
+        if self.is_modal() {
+            cx.set_key_focus(self.area());
+        }
+    }
+    /// ## Modal keyboard
+    /// Tab/Shift+Tab cycle in the popup, Escape closes it (see `CloseMode`),
+    /// return true if the key is handled
+    fn handle_modal_key(&mut self, cx: &mut Cx, e: &KeyEvent, stops: &[Area]) -> bool {
+        match e.key_code {
+            KeyCode::Tab => {
+                let current = stops.iter().position(|area| cx.has_key_focus(*area));
+                if let Some(next) = next_focus(current, stops.len(), e.modifiers.shift) {
+                    cx.set_key_focus(stops[next]);
+                }
+                true
+            }
+            KeyCode::Escape if self.close_mode.close_on_escape() => {
+                self.opened = false;
+                self.redraw(cx);
+                PopupStackGlobal::close(cx, self.widget_uid(), self.area());
+                self.restore_focus(cx);
+                self.active_toggled(cx, GDropDownToggleKind::Escape(e.clone()));
+                self.redraw_flag = true;
+                true
+            }
+            _ => false,
+        }
+    }
     fn active_toggled(&mut self, cx: &mut Cx, e_kind: GDropDownToggleKind) {
         cx.widget_action(
             self.widget_uid(),
@@ -203,80 +316,82 @@ impl Widget for GDropDown {
             let global = cx.global::<PopupMenuGlobal>().clone();
             let mut map = global.map.borrow_mut();
             let popup_menu = map.get_mut(&self.popup.unwrap()).unwrap();
+            let walk = popup_menu.walk;
+            if let Some(height) = self.placement.and_then(|placement| placement.max_height) {
+                popup_menu.walk.height = Size::Fixed(height);
+            }
             popup_menu.begin(cx);
+            popup_menu.walk = walk;
 
             match self.mode {
                 PopupMode::Popup | PopupMode::ToolTip => {
                     let area = self.area().rect(cx);
-                    let angle_offset = self.position.angle_offset(area.size);
+                    let (position, angle_offset, max_height) = match self.placement {
+                        Some(placement) => (
+                            placement.position,
+                            placement.angle_offset,
+                            placement.max_height,
+                        ),
+                        None => (self.position, self.position.angle_offset(area.size), None),
+                    };
                     popup_menu.draw_container(
                         cx,
                         scope,
-                        Some(self.position.clone()),
+                        Some(position),
                         angle_offset,
                         &mut self.redraw_flag,
                     );
-                    let container = popup_menu.container_area().rect(cx);
-                    let mut shift = match self.position {
-                        Position::Bottom => DVec2 {
-                            x: -container.size.x / 2.0 + area.size.x / 2.0,
-                            y: area.size.y + self.offset as f64,
-                        },
-                        Position::BottomLeft => DVec2 {
-                            x: 0.0,
-                            y: area.size.y + self.offset as f64,
-                        },
-                        Position::BottomRight => DVec2 {
-                            x: area.size.x - container.size.x,
-                            y: area.size.y + self.offset as f64,
-                        },
-                        Position::Top => DVec2 {
-                            x: 0.0 - area.size.x / 2.0,
-                            y: -self.offset as f64 - container.size.y,
-                        },
-                        Position::TopLeft => DVec2 {
-                            x: 0.0,
-                            y: -self.offset as f64 - container.size.y,
-                        },
-                        Position::TopRight => DVec2 {
-                            x: area.size.x - container.size.x,
-                            y: -self.offset as f64 - container.size.y,
-                        },
-                        Position::Left => DVec2 {
-                            x: -self.offset as f64 - container.size.x,
-                            y: area.size.y / 2.0 - container.size.y / 2.0,
-                        },
-                        Position::LeftTop => DVec2 {
-                            x: -self.offset as f64 - container.size.x,
-                            y: 0.0,
-                        },
-                        Position::LeftBottom => DVec2 {
-                            x: -self.offset as f64 - container.size.x,
-                            y: 0.0 - container.size.y + area.size.y,
-                        },
-                        Position::Right => DVec2 {
-                            x: area.size.x + self.offset as f64,
-                            y: area.size.y / 2.0 - container.size.y / 2.0,
-                        },
-                        Position::RightTop => DVec2 {
-                            x: area.size.x + self.offset as f64,
-                            y: 0.0,
-                        },
-                        Position::RightBottom => DVec2 {
-                            x: area.size.x + self.offset as f64,
-                            y: 0.0 - container.size.y + area.size.y,
-                        },
+                    // the natural size is measured in each frame, a constrained container
+                    // scrolls, so its content tells the height (unless the popup height is fixed)
+                    let mut size = popup_menu.container_area().rect(cx).size;
+                    if max_height.is_some() {
+                        size.y = match walk.height {
+                            Size::Fixed(height) => height,
+                            _ => popup_menu.get().content_height,
+                        };
+                    }
+                    self.natural_size = size;
+                    let viewport = Rect {
+                        pos: DVec2::default(),
+                        size: cx.current_pass_size(),
                     };
-
-                    shift.x += self.offset_x as f64;
-                    shift.y += self.offset_y as f64;
-
-                    popup_menu.end(cx, scope, self.area(), shift);
+                    // the shift is relative to the trigger, so a cursor anchor needs to add the distance
+                    let (anchor, base) = if self.at_cursor() {
+                        let anchor = Rect {
+                            pos: self.cursor_pos,
+                            size: DVec2::default(),
+                        };
+                        (anchor, self.cursor_pos - area.pos)
+                    } else {
+                        (area, DVec2::default())
+                    };
+                    let placement = place(
+                        self.position,
+                        anchor,
+                        size,
+                        viewport,
+                        &self.placement_options(),
+                    );
+                    popup_menu.end(cx, scope, self.area(), placement.shift + base);
+                    PopupStackGlobal::set_areas(cx, self.widget_uid(), vec![popup_menu.area()]);
+                    // position, arrow and height are drawn before the size is known,
+                    // so they take effect in the next frame
+                    if self.placement.map_or(true, |last| {
+                        last.position != placement.position
+                            || last.angle_offset != placement.angle_offset
+                            || last.max_height != placement.max_height
+                    }) {
+                        self.redraw_flag = true;
+                        self.redraw(cx);
+                    }
+                    self.placement.replace(placement);
                 }
 
                 PopupMode::Dialog => {
                     popup_menu.draw_container(cx, scope, None, 0.0, &mut false);
                     popup_menu.end(cx, scope, Area::Empty, DVec2::default());
+                    let areas = vec![popup_menu.container_area()];
+                    PopupStackGlobal::set_areas(cx, self.widget_uid(), areas);
                 }
                 PopupMode::Drawer => {
                     let _ = popup_menu.draw_container_drawer(
@@ -287,6 +402,14 @@ impl Widget for GDropDown {
                         &mut self.redraw_flag,
                     );
                     popup_menu.end(cx, scope, Area::Empty, DVec2::default());
+                    let areas = vec![popup_menu.container_area()];
+                    PopupStackGlobal::set_areas(cx, self.widget_uid(), areas);
+                }
+            }
+            if self.focus_first {
+                self.focus_first = false;
+                if let Some(first) = popup_menu.get().focus_stops().first() {
+                    cx.set_key_focus(*first);
                 }
             }
         }
@@ -295,19 +418,47 @@ impl Widget for GDropDown {
     }
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         if self.opened && self.popup.is_some() {
+            // check the stack before the popups opened from this one handle the event
+            let uid = self.widget_uid();
+            let is_top = PopupStackGlobal::is_top(cx, uid);
+            let in_above = match event {
+                Event::MouseDown(MouseDownEvent { abs, .. })
+                | Event::MouseMove(MouseMoveEvent { abs, .. }) => {
+                    PopupStackGlobal::above_contains_pos(cx, uid, *abs)
+                }
+                _ => false,
+            };
             let global = cx.global::<PopupMenuGlobal>().clone();
             let mut map = global.map.borrow_mut();
             let popup_menu = map.get_mut(&self.popup.unwrap()).unwrap();
             popup_menu.handle_event_with(cx, event, scope, self.area());
-            if let Event::MouseDown(e) = event {
+            if let Event::KeyDown(e) = event {
+                // only the topmost popup takes Tab and Escape
+                if self.is_modal() && is_top {
+                    let stops = popup_menu.get().focus_stops();
+                    if self.handle_modal_key(cx, e, &stops) {
+                        return;
+                    }
+                }
+            }
+            if let Event::MouseMove(e) = event {
+                if self.trigger_mode.is_hover() {
+                    // a following popup never takes the pointer
+                    let is_in = self.area().rect(cx).contains(e.abs)
+                        || (!self.follow_cursor && popup_menu.menu_contains_pos(cx, e.abs))
+                        || in_above;
+                    drop(map);
+                    self.hover_leave(cx, is_in);
+                }
+            } else if let Event::MouseDown(e) = event {
                 match self.mode {
                     PopupMode::Popup | PopupMode::ToolTip => {
-                        let is_in = popup_menu.menu_contains_pos(cx, e.abs);
+                        let is_in = popup_menu.menu_contains_pos(cx, e.abs) || in_above;
                         self.close_inner(cx, GDropDownToggleKind::Other, is_in);
                     }
 
                     PopupMode::Dialog | PopupMode::Drawer => {
-                        let is_in = popup_menu.container_contains_pos(cx, e.abs);
+                        let is_in = popup_menu.container_contains_pos(cx, e.abs) || in_above;
                         self.close_inner(cx, GDropDownToggleKind::Other, is_in);
                     }
                 }
@@ -315,6 +466,15 @@ impl Widget for GDropDown {
             }
         }
 
+        if self.delay_timer.is_event(event).is_some() {
+            self.delay_timer = Timer::empty();
+            if self.delay_open {
+                self.open_inner(cx, GDropDownToggleKind::Other);
+            } else {
+                self.close_inner(cx, GDropDownToggleKind::Other, false);
+            }
+        }
+
         match event.hits_with_sweep_area(cx, self.area(), self.area()) {
             // template remove -------------------------------------------------------------------
             // Hit::KeyFocus(_) => {
@@ -330,23 +490,52 @@ impl Widget for GDropDown {
                 cx.set_key_focus(self.area());
                 if self.trigger_mode.is_press() {
                     self.open_inner(cx, GDropDownToggleKind::Press(e));
+                } else if self.trigger_mode.is_right_click()
+                    && e.device.mouse_button().is_some_and(|button| button.is_secondary())
+                {
+                    self.cursor_pos = e.abs;
+                    self.open_inner(cx, GDropDownToggleKind::Press(e));
                 }
             }
             Hit::FingerHoverIn(e) => {
                 cx.set_cursor(MouseCursor::Hand);
                 if self.trigger_mode.is_hover() {
-                    self.open_inner(cx, GDropDownToggleKind::Hover(e));
+                    self.cursor_pos = e.abs;
+                    self.stop_delay(cx);
+                    if self.opened {
+                        // back before the hide delay ends
+                    } else if self.show_delay > 0.0 {
+                        self.delay_open = true;
+                        self.delay_timer = cx.start_timeout(self.show_delay);
+                    } else {
+                        self.open_inner(cx, GDropDownToggleKind::Hover(e));
+                    }
+                }
+            }
+            Hit::FingerHoverOver(e) => {
+                if self.trigger_mode.is_hover() {
+                    self.cursor_pos = e.abs;
+                    if self.opened && self.follow_cursor {
+                        self.redraw(cx);
+                    }
                 }
             }
             Hit::FingerHoverOut(f) => {
                 cx.set_cursor(MouseCursor::Default);
                 if self.trigger_mode.is_hover() {
-                    self.close_inner(cx, GDropDownToggleKind::Hover(f), false);
+                    if !self.opened {
+                        self.stop_delay(cx);
+                    } else if self.follow_cursor {
+                        self.close_inner(cx, GDropDownToggleKind::Hover(f), false);
+                    }
+                    // otherwise the pointer may move into the popup, see `hover_leave`
                 }
             }
             Hit::FingerUp(e) => {
                 if e.is_over && self.trigger_mode.is_click() {
                     self.open_inner(cx, GDropDownToggleKind::Click(e));
+                } else if self.trigger_mode.is_right_click() {
+                    // opened by the finger down, keep it open
                 } else {
                     // focus lost
                     self.close_inner(cx, GDropDownToggleKind::Other, false);
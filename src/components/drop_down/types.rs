@@ -0,0 +1,252 @@
+use makepad_widgets::*;
+
+use crate::shader::manual::Position;
+
+/// the min distance between the arrow and the corner of the popup
+const ARROW_EDGE: f64 = 12.0;
+
+/// # Placement Options
+/// how the popup reacts when it collides with the viewport
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlacementOptions {
+    /// the gap between the anchor and the popup
+    pub offset: f64,
+    pub offset_x: f64,
+    pub offset_y: f64,
+    /// flip to the opposite side if it has more room
+    pub flip: bool,
+    /// shift along the cross axis to stay in the viewport
+    pub shift: bool,
+    /// constrain the height of the popup to the available room
+    pub constrain_height: bool,
+    /// let the arrow point to the center of the anchor
+    pub arrow: bool,
+    /// the min distance between the popup and the viewport edge
+    pub padding: f64,
+}
+
+/// # Placement
+/// The result of `place`, the shift is relative to the top-left of the anchor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub position: Position,
+    pub shift: DVec2,
+    /// Some when the popup is higher than the available room
+    pub max_height: Option<f64>,
+    pub angle_offset: f32,
+}
+
+/// the shift of the popup relative to the top-left of the anchor (no collision handling)
+pub fn anchor_shift(position: Position, anchor: DVec2, size: DVec2, offset: f64) -> DVec2 {
+    match position {
+        Position::Bottom => dvec2((anchor.x - size.x) / 2.0, anchor.y + offset),
+        Position::BottomLeft => dvec2(0.0, anchor.y + offset),
+        Position::BottomRight => dvec2(anchor.x - size.x, anchor.y + offset),
+        Position::Top => dvec2((anchor.x - size.x) / 2.0, -offset - size.y),
+        Position::TopLeft => dvec2(0.0, -offset - size.y),
+        Position::TopRight => dvec2(anchor.x - size.x, -offset - size.y),
+        Position::Left => dvec2(-offset - size.x, (anchor.y - size.y) / 2.0),
+        Position::LeftTop => dvec2(-offset - size.x, 0.0),
+        Position::LeftBottom => dvec2(-offset - size.x, anchor.y - size.y),
+        Position::Right => dvec2(anchor.x + offset, (anchor.y - size.y) / 2.0),
+        Position::RightTop => dvec2(anchor.x + offset, 0.0),
+        Position::RightBottom => dvec2(anchor.x + offset, anchor.y - size.y),
+    }
+}
+
+/// the room between the anchor and the viewport edge at the side of the position
+fn room(position: Position, anchor: Rect, viewport: Rect, options: &PlacementOptions) -> f64 {
+    let room = match position {
+        Position::Top | Position::TopLeft | Position::TopRight => anchor.pos.y - viewport.pos.y,
+        Position::Bottom | Position::BottomLeft | Position::BottomRight => {
+            viewport.pos.y + viewport.size.y - anchor.pos.y - anchor.size.y
+        }
+        Position::Left | Position::LeftTop | Position::LeftBottom => anchor.pos.x - viewport.pos.x,
+        Position::Right | Position::RightTop | Position::RightBottom => {
+            viewport.pos.x + viewport.size.x - anchor.pos.x - anchor.size.x
+        }
+    };
+    room - options.offset - options.padding
+}
+
+/// clamp but prefer the min when the range is empty
+fn clamp_to(value: f64, min: f64, max: f64) -> f64 {
+    value.min(max).max(min)
+}
+
+/// ## Place the popup
+/// - anchor: the rect of the trigger
+/// - size: the natural size of the popup
+/// - viewport: the rect the popup should stay in
+pub fn place(
+    position: Position,
+    anchor: Rect,
+    size: DVec2,
+    viewport: Rect,
+    options: &PlacementOptions,
+) -> Placement {
+    let mut position = position;
+    let mut size = size;
+    let mut flipped = false;
+    if options.flip {
+        let main = if position.is_vertical() { size.y } else { size.x };
+        let current = room(position, anchor, viewport, options);
+        if main > current && room(position.flip(), anchor, viewport, options) > current {
+            position = position.flip();
+            flipped = true;
+        }
+    }
+
+    let mut max_height = None;
+    if options.constrain_height {
+        let limit = if position.is_vertical() {
+            room(position, anchor, viewport, options)
+        } else {
+            viewport.size.y - options.padding * 2.0
+        }
+        .max(0.0);
+        if size.y > limit {
+            size.y = limit;
+            max_height = Some(limit);
+        }
+    }
+
+    let mut shift = anchor_shift(position, anchor.size, size, options.offset);
+    // the offset along the main axis is mirrored with the side
+    let (offset_x, offset_y) = match (flipped, position.is_vertical()) {
+        (true, true) => (options.offset_x, -options.offset_y),
+        (true, false) => (-options.offset_x, options.offset_y),
+        _ => (options.offset_x, options.offset_y),
+    };
+    shift.x += offset_x;
+    shift.y += offset_y;
+
+    if options.shift {
+        if position.is_vertical() {
+            let min = viewport.pos.x + options.padding - anchor.pos.x;
+            let max = viewport.pos.x + viewport.size.x - options.padding - size.x - anchor.pos.x;
+            shift.x = clamp_to(shift.x, min, max);
+        } else {
+            let min = viewport.pos.y + options.padding - anchor.pos.y;
+            let max = viewport.pos.y + viewport.size.y - options.padding - size.y - anchor.pos.y;
+            shift.y = clamp_to(shift.y, min, max);
+        }
+    }
+
+    let (position, angle_offset) = if options.arrow {
+        let (center, start, len) = if position.is_vertical() {
+            (anchor.size.x / 2.0, shift.x, size.x)
+        } else {
+            (anchor.size.y / 2.0, shift.y, size.y)
+        };
+        let edge = ARROW_EDGE.min(len / 2.0);
+        (
+            position.to_start(),
+            clamp_to(center - start, edge, len - edge) as f32,
+        )
+    } else {
+        (position, position.angle_offset(anchor.size))
+    };
+
+    Placement {
+        position,
+        shift,
+        max_height,
+        angle_offset,
+    }
+}
+
+/// ## Focus Trap
+/// the index of the next focus stop when Tab (or Shift+Tab if backward) is pressed,
+/// the focus cycles and starts from the edge when no stop is focused
+pub fn next_focus(current: Option<usize>, len: usize, backward: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    Some(match (current, backward) {
+        (Some(index), false) => (index + 1) % len,
+        (Some(index), true) => (index + len - 1) % len,
+        (None, false) => 0,
+        (None, true) => len - 1,
+    })
+}
+
+#[cfg(test)]
+mod test_placement {
+    use super::*;
+
+    fn rect(x: f64, y: f64, w: f64, h: f64) -> Rect {
+        Rect {
+            pos: dvec2(x, y),
+            size: dvec2(w, h),
+        }
+    }
+
+    fn options() -> PlacementOptions {
+        PlacementOptions {
+            offset: 6.0,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            flip: true,
+            shift: true,
+            constrain_height: true,
+            arrow: false,
+            padding: 8.0,
+        }
+    }
+
+    #[test]
+    fn fits() {
+        let viewport = rect(0.0, 0.0, 800.0, 600.0);
+        let anchor = rect(100.0, 100.0, 80.0, 30.0);
+        let p = place(Position::BottomLeft, anchor, dvec2(200.0, 160.0), viewport, &options());
+        assert_eq!(p.position, Position::BottomLeft);
+        assert_eq!(p.shift, dvec2(0.0, 36.0));
+        assert_eq!(p.max_height, None);
+    }
+
+    #[test]
+    fn flip_and_shift() {
+        let viewport = rect(0.0, 0.0, 800.0, 600.0);
+        // near the bottom right corner
+        let anchor = rect(700.0, 520.0, 80.0, 30.0);
+        let p = place(Position::BottomLeft, anchor, dvec2(200.0, 160.0), viewport, &options());
+        assert_eq!(p.position, Position::TopLeft);
+        assert_eq!(p.shift.y, -166.0);
+        // right edge of the popup is 8.0 away from the viewport
+        assert_eq!(anchor.pos.x + p.shift.x + 200.0, 792.0);
+        // no flip: stay at the bottom but constrain the height
+        let no_flip = PlacementOptions {
+            flip: false,
+            ..options()
+        };
+        let p = place(Position::BottomLeft, anchor, dvec2(200.0, 160.0), viewport, &no_flip);
+        assert_eq!(p.position, Position::BottomLeft);
+        assert_eq!(p.max_height, Some(36.0));
+    }
+
+    #[test]
+    fn constrain_and_arrow() {
+        let viewport = rect(0.0, 0.0, 400.0, 300.0);
+        let anchor = rect(370.0, 160.0, 20.0, 20.0);
+        let arrow = PlacementOptions {
+            arrow: true,
+            ..options()
+        };
+        let p = place(Position::Bottom, anchor, dvec2(120.0, 400.0), viewport, &arrow);
+        // both sides are too small, keep the side with more room
+        assert_eq!(p.position, Position::TopLeft);
+        assert_eq!(p.max_height, Some(146.0));
+        // the arrow points to the anchor center after the shift
+        assert_eq!(anchor.pos.x + p.shift.x + p.angle_offset as f64, 380.0);
+    }
+
+    #[test]
+    fn focus_cycle() {
+        assert_eq!(next_focus(None, 0, false), None);
+        assert_eq!(next_focus(None, 3, false), Some(0));
+        assert_eq!(next_focus(None, 3, true), Some(2));
+        assert_eq!(next_focus(Some(2), 3, false), Some(0));
+        assert_eq!(next_focus(Some(0), 3, true), Some(2));
+    }
+}
@@ -24,6 +24,7 @@ use crate::{
         ui::DrawGIconUI,
         ApplyIconType,
     },
+    sync_theme,
     themes::Themes,
     utils::{set_cursor, ThemeColor},
     widget_area,
@@ -230,10 +231,13 @@ pub struct GIcon {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GIcon {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
@@ -8,7 +8,7 @@ use types::{Edit, EditKind, History};
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 use crate::{
-    animatie_fn, event_bool, event_option, ref_event_bool, ref_event_option, set_event, set_event_bool, shader::{draw_view::DrawGView, draw_text::DrawGText}, themes::Themes, utils::{get_font_family, BoolToF32, ThemeColor}, widget_area
+    animatie_fn, event_bool, event_option, ref_event_bool, ref_event_option, set_event, set_event_bool, shader::{draw_view::DrawGView, draw_text::DrawGText}, sync_theme, themes::{scale_layout, scale_padding, scale_walk, size_scale, GSize, Themes}, utils::{get_font_family, BoolToF32, ThemeColor}, widget_area
 };
 
 live_design! {
@@ -135,6 +135,9 @@ live_design! {
 pub struct GInput {
     #[live]
     pub theme: Themes,
+    /// the size preset
+    #[live]
+    pub size: GSize,
     #[live]
     pub shadow_color: Option<Vec4>,
     #[live(0.0)]
@@ -232,16 +235,21 @@ pub struct GInput {
     scroll_bars: ScrollBars,
     #[live(true)]
     pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GInput {
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
         // self.draw_text.wrap = self.wrap.clone();
         self.draw_text.text_style.font = get_font_family(&self.font_family, cx);
-        self.draw_input.begin(cx, walk, self.layout);
+        let scale = size_scale(self.size);
+        self.draw_input
+            .begin(cx, scale_walk(walk, scale), scale_layout(self.layout, scale));
 
         self.draw_selection.append_to_draw_call(cx);
 
@@ -296,7 +304,7 @@ impl Widget for GInput {
         self.draw_input.end(cx);
 
         if cx.has_key_focus(self.draw_input.area()) {
-            let padding = dvec2(self.layout.padding.left, self.layout.padding.top);
+            let padding = self.padding().left_top();
             cx.show_text_ime(
                 self.draw_input.area(),
                 padding + cursor_position - self.cursor_width * 0.5,
@@ -313,9 +321,10 @@ impl Widget for GInput {
     }
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         let rect = self.draw_input.area().rect(cx);
+        let padding = self.padding();
         let padded_rect = Rect {
-            pos: rect.pos + self.layout.padding.left_top(),
-            size: rect.size - self.layout.padding.size(),
+            pos: rect.pos + padding.left_top(),
+            size: rect.size - padding.size(),
         };
 
         let uid = self.widget_uid();
@@ -635,6 +644,12 @@ impl LiveHook for GInput {
         if !self.visible {
             return;
         }
+        self.render(cx);
+    }
+}
+
+impl GInput {
+    pub fn render(&mut self, cx: &mut Cx) {
         // ----------------- background color -------------------------------------------
         let bg_color = self.background_color.get(self.theme, 25);
         // ------------------ hover color -----------------------------------------------
@@ -657,6 +672,10 @@ impl LiveHook for GInput {
         let font_color = self.color.get(self.theme, 800);
         // ---------------------- is empty ------------------------------------------------
         let empty = self.text.len().eq(&0).to_f32();
+        // ------------------ size (see `GSize`) -----------------------------------------
+        let scale = size_scale(self.size);
+        let border_radius = self.border_radius * scale as f32;
+        let font_size = self.font_size * scale;
         // draw input --------------------------------------------------------------
         self.draw_input.apply_over(
             cx,
@@ -665,7 +684,7 @@ impl LiveHook for GInput {
                 background_visible: (self.background_visible.to_f32()),
                 border_color: (border_color),
                 border_width: (self.border_width),
-                border_radius: (self.border_radius),
+                border_radius: (border_radius),
                 focus_color: (focus_color),
                 hover_color: (hover_color),
                 shadow_color: (shadow_color),
@@ -688,7 +707,7 @@ impl LiveHook for GInput {
                     // curve: (self.curve),
                     // line_spacing: (self.layout.line_spacing),
                     // top_drop: (self.top_drop),
-                    font_size: (self.font_size),
+                    font_size: (font_size),
                     // height_factor: (self.height_factor),
                 }
             },
@@ -722,9 +741,10 @@ impl LiveHook for GInput {
         // self.draw_cursor.redraw(cx);
         // self.draw_selection.redraw(cx);
     }
-}
-
-impl GInput {
+    /// the padding scaled by the size
+    fn padding(&self) -> Padding {
+        scale_padding(self.layout.padding, size_scale(self.size))
+    }
     widget_area! {
         area, draw_input,
         area_selection, draw_selection
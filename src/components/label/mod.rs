@@ -5,7 +5,7 @@ pub use event::*;
 pub use register::register;
 
 use crate::{
-    active_event, animatie_fn, default_handle_animation, default_hit_finger_down, default_hit_hover_in, default_hit_hover_out, event::UnifiedEvent, event_option, play_animation, ref_animate_state, ref_area, ref_event_option, ref_play_animation, ref_redraw, ref_render, set_scope_path, set_text_and_visible_fn, shader::draw_text::DrawGText, themes::Themes, utils::{get_font_family, set_cursor, ThemeColor, ToBool}
+    active_event, animatie_fn, default_handle_animation, default_hit_finger_down, default_hit_hover_in, default_hit_hover_out, event::UnifiedEvent, event_option, play_animation, ref_animate_state, ref_area, ref_event_option, ref_play_animation, ref_redraw, ref_render, set_scope_path, set_text_and_visible_fn, shader::draw_text::DrawGText, sync_theme, themes::{size_scale, token_or, GSize, GTheme, Themes}, utils::{get_font_family, set_cursor, ThemeColor, ToBool}
 };
 use makepad_widgets::*;
 use shader::draw_text::TextWrap;
@@ -79,9 +79,10 @@ live_design! {
 /// | live    | stroke_hover_color   | The color of the text stroke when hovered        | `Option<Vec4>`  | `None`      |
 /// | live    | stroke_focus_color   | The color of the text stroke when focused        | `Option<Vec4>`  | `None`      |
 /// | live    | color                | The base color of the text                       | `Option<Vec4>`  | `None`      |
-/// | live    | font_size            | The font size of the label text                  | `f64`           | `9.0`       |
+/// | live    | size                 | Size preset (Small, Medium, Large)               | `GSize`         | `Medium`    |
+/// | live    | font_size            | The font size of the label text, `None` uses the `font.size` token or `10.0` | `Option<f64>` | `None` |
 /// | live    | cursor               | The cursor type when hovering over the label     | `Option<MouseCursor>` | `None`      |
-/// | live    | line_spacing         | The line spacing between the label text          | `f64`           | `1.5`       |
+/// | live    | line_spacing         | The line spacing between the label text, `None` uses the `font.line_spacing` token or `1.5` | `Option<f64>` | `None` |
 /// | live    | height_factor        | Factor controlling the height of the text        | `f64`           | `0.0`       |
 /// | live    | wrap                 | Text wrapping behavior                          | `TextWrap`      | `TextWrap::Word` |
 /// | live    | font_family          | The font family used for the label               | `LiveDependency` | N/A         |
@@ -97,26 +98,34 @@ live_design! {
 /// | live    | grab_key_focus       | Whether the label grabs keyboard focus           | `bool`          | `true`      |
 /// | rust    | scope_path           | The path scope for the label                    | `Option<HeapLiveIdPath>` | N/A  |
 /// > N/A: Default::default()
+/// the built-in `font_size` and `line_spacing` when they are not set and no token is loaded,
+/// `FONT_SIZE` is the `FONT_SIZE` of the theme (the old `#[live]` default of `font_size` was `9.0`)
+const FONT_SIZE: f64 = 10.0;
+const LINE_SPACING: f64 = 1.5;
+
 #[derive(Live, Widget)]
 pub struct GLabel {
     #[live]
     pub theme: Themes,
+    /// the size preset, scales the font_size
+    #[live]
+    pub size: GSize,
     #[live]
     pub stroke_hover_color: Option<Vec4>,
     #[live]
     pub stroke_focus_color: Option<Vec4>,
     #[live]
     pub color: Option<Vec4>,
-    #[live(9.0)]
-    pub font_size: f64,
+    #[live]
+    pub font_size: Option<f64>,
     // #[live(1.0)]
     // pub brightness: f32,
     // #[live(0.5)]
     // pub curve: f32,
     #[live]
     pub cursor: Option<MouseCursor>,
-    #[live(1.5)]
-    pub line_spacing: f64,
+    #[live]
+    pub line_spacing: Option<f64>,
     // #[live(0.0)]
     // pub top_drop: f64,
     #[live(0.0)]
@@ -152,13 +161,21 @@ pub struct GLabel {
     pub grab_key_focus: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
+    /// the background set by the parent (GButton, GView) to check the contrast of the label,
+    /// a label without it is not checked
+    #[rust]
+    pub contrast_background: Option<Vec4>,
 }
 
 impl Widget for GLabel {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
+        self.check_contrast();
         let padding = self.padding;
         let walk = walk.with_add_padding(padding);
         cx.begin_turtle(walk, Layout::default());
@@ -234,10 +251,29 @@ impl GLabel {
     pub fn redraw(&self, cx: &mut Cx) -> () {
         self.draw_text.redraw(cx);
     }
+    /// the font size set on the label, or the `font.size` token, or the built-in default
+    pub fn font_size(&self) -> f64 {
+        token_or(self.font_size, FONT_SIZE, GTheme::tokens().font_size)
+    }
+    /// check the text on `contrast_background`, return false if it fails (see `GTheme::check_contrast`)
+    pub fn check_contrast(&self) -> bool {
+        match self.contrast_background {
+            Some(background) => {
+                let color = self.color.get(self.theme, 50);
+                GTheme::check_contrast("GLabel", color, background, self.font_size())
+            }
+            None => true,
+        }
+    }
     pub fn render(&mut self, cx: &mut Cx) -> () {
         let color = self.color.get(self.theme, 50);
         let stroke_hover_color = self.stroke_hover_color.get(self.theme, 25);
         let stroke_focus_color = self.stroke_focus_color.get(self.theme, 100);
+        // ------------------ tokens (see `ThemeTokens`) ---------------------------------
+        let tokens = GTheme::tokens();
+        let line_spacing = token_or(self.line_spacing, LINE_SPACING, tokens.line_spacing);
+        // ------------------ size (see `GSize`) -----------------------------------------
+        let font_size = self.font_size() * size_scale(self.size);
         self.draw_text.apply_over(
             cx,
             live! {
@@ -247,9 +283,9 @@ impl GLabel {
                 text_style: {
                     // brightness: (self.brightness),
                     // curve: (self.curve),
-                    line_spacing: (self.line_spacing),
+                    line_spacing: (line_spacing),
                     // top_drop: (self.top_drop),
-                    font_size: (self.font_size),
+                    font_size: (font_size),
                     height_factor: (self.height_factor),
                 }
             },
@@ -16,7 +16,7 @@ use crate::{
     active_event, animatie_fn, default_handle_animation, default_hit_finger_down,
     default_hit_hover_in, default_hit_hover_out, event_option, play_animation, ref_area,
     ref_event_option, ref_redraw, ref_render, set_event, set_scope_path, set_text_and_visible_fn,
-    widget_area,
+    sync_theme, widget_area,
 };
 use makepad_widgets::*;
 
@@ -206,6 +206,8 @@ pub struct GLink {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GLink {
@@ -236,6 +238,7 @@ impl Widget for GLink {
         self.handle_widget_event(cx, event, scope, hit, focus_area)
     }
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
@@ -8,6 +8,7 @@ pub use register::register;
 use crate::{
     event_bool, ref_area, ref_event_bool, ref_redraw, ref_render, set_event_bool, set_scope_path,
     shader::draw_loading::{DrawGLoading, GLoadingType},
+    sync_theme,
     themes::Themes,
     utils::{BoolToF32, ThemeColor},
     widget_area,
@@ -49,10 +50,13 @@ pub struct GLoading {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GLoading {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
@@ -133,6 +137,8 @@ impl GLoading {
     pub fn open(&mut self, cx: &mut Cx) -> () {
         self.animation_key = true;
         self.draw_loading.opened = 1.0;
+        // restart the animation cycle, it stops when closed or hidden
+        self.next_frame = cx.new_next_frame();
         self.redraw(cx);
     }
     pub fn close(&mut self, cx: &mut Cx) -> () {
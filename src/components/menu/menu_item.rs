@@ -4,6 +4,7 @@ use crate::{
     components::{label::GLabel, svg::GSvg, view::GView},
     event_option,
     shader::draw_view::DrawGView,
+    sync_theme,
     themes::Themes,
     utils::{set_cursor, BoolToF32, ThemeColor},
     widget_area,
@@ -103,10 +104,13 @@ pub struct GMenuItem {
     pub animator: Animator,
     #[live(true)]
     pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GMenuItem {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.is_visible() {
             return DrawStep::done();
         }
@@ -5,7 +5,7 @@ pub mod sub_menu;
 
 use super::view::GView;
 use crate::{
-    event_option, ref_event_option, set_event, shader::{draw_view::DrawGView, manual::MenuItemMode}, themes::Themes, utils::{BoolToF32, ThemeColor}
+    event_option, ref_event_option, set_event, shader::{draw_view::DrawGView, manual::MenuItemMode}, sync_theme, themes::Themes, utils::{BoolToF32, ThemeColor}
 };
 use event::{GMenuChangedParam, GMenuEvent};
 use makepad_widgets::*;
@@ -74,10 +74,13 @@ pub struct GMenu {
     pub item_modes: Vec<MenuItemMode>,
     #[live(true)]
     pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GMenu {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.is_visible() {
             return DrawStep::done();
         }
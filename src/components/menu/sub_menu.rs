@@ -4,7 +4,9 @@ use crate::{
     components::view::GView,
     event_option, ref_event_option, set_event,
     shader::{draw_view::DrawGView, manual::MenuItemMode},
+    sync_theme,
     themes::Themes,
+    utils::{BoolToF32, ThemeColor},
     widget_area,
 };
 
@@ -78,10 +80,13 @@ pub struct GSubMenu {
     pub walk: Walk,
     #[live(true)]
     pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GSubMenu {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.is_visible() {
             return DrawStep::done();
         }
@@ -163,6 +168,7 @@ impl LiveHook for GSubMenu {
             self.items.after_apply(cx, apply, index, nodes);
             let _ = self.find_selected();
         }
+        self.render(cx);
     }
 }
 
@@ -237,6 +243,35 @@ impl GSubMenu {
         }
         self.selected = MenuItemMode::selected(&self.item_modes);
     }
+    pub fn render(&mut self, cx: &mut Cx) {
+        // ----------------- background color -------------------------------------------
+        let bg_color = self.background_color.get(self.theme, 500);
+        let shadow_color = self.shadow_color.get(self.theme, 700);
+        // ------------------ hover color -----------------------------------------------
+        let hover_color = self.hover_color.get(self.theme, 400);
+        // ------------------ focus color ---------------------------------------------
+        let focus_color = self.focus_color.get(self.theme, 600);
+        // ------------------ border color ----------------------------------------------
+        let border_color = self.border_color.get(self.theme, 600);
+        // ------------------ is background_visible --------------------------------------------
+        let background_visible = self.background_visible.to_f32();
+        self.draw_sub_menu.apply_over(
+            cx,
+            live! {
+                background_color: (bg_color),
+                background_visible: (background_visible),
+                border_color: (border_color),
+                border_width: (self.border_width),
+                border_radius: (self.border_radius),
+                focus_color: (focus_color),
+                hover_color: (hover_color),
+                shadow_color: (shadow_color),
+                shadow_offset: (self.shadow_offset),
+                spread_radius: (self.spread_radius),
+                blur_radius: (self.blur_radius),
+            },
+        );
+    }
     pub fn redraw(&mut self, cx: &mut Cx) {
         if !self.is_visible() {
             return;
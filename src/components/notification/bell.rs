@@ -0,0 +1,92 @@
+use makepad_widgets::*;
+
+use crate::{
+    components::{
+        label::GLabelWidgetExt,
+        view::{event::GViewClickedParam, GView, GViewWidgetExt},
+    },
+    ref_event_option,
+};
+
+use super::{types::badge_text, GNotification, GNotificationGlobal};
+
+live_design! {
+    GNotificationBellBase = {{GNotificationBell}}{}
+}
+
+/// # GNotificationBell
+/// A bell (`icon`, a GIcon) with the unread count of the notification history in a `badge`
+/// (a GView with a `count` GLabel, hidden when all are read), click it to toggle the notification center
+#[derive(Live, Widget)]
+pub struct GNotificationBell {
+    #[deref]
+    pub deref_widget: GView,
+    /// the badge shows `{max_count}+` when there are more unread records
+    #[live(99)]
+    pub max_count: usize,
+    /// toggle the notification center when clicked
+    #[live(true)]
+    pub toggle_center: bool,
+    /// the count shown by the badge
+    #[rust]
+    pub count: usize,
+}
+
+impl LiveHook for GNotificationBell {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) {
+        self.deref_widget.after_apply(cx, apply, index, nodes);
+        if apply.from.is_from_doc() {
+            self.sync(cx);
+        }
+    }
+}
+
+impl Widget for GNotificationBell {
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible {
+            return DrawStep::done();
+        }
+        let count = cx.global::<GNotificationGlobal>().history.borrow().unread_count();
+        if count != self.count {
+            self.count = count;
+            self.sync(cx);
+        }
+        self.deref_widget.draw_walk(cx, scope, walk)
+    }
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible {
+            return;
+        }
+        self.deref_widget.handle_event(cx, event, scope);
+        if let Hit::FingerUp(e) = event.hits(cx, self.area()) {
+            if self.toggle_center && e.is_over {
+                GNotification::toggle_center(cx);
+            }
+        }
+    }
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+impl GNotificationBell {
+    /// show the count in the badge
+    fn sync(&mut self, cx: &mut Cx) {
+        let text = badge_text(self.count, self.max_count);
+        if let Some(mut badge) = self.deref_widget.gview(id!(badge)).borrow_mut() {
+            badge.visible = !text.is_empty();
+        }
+        self.deref_widget.glabel(id!(badge.count)).set_text(&text);
+        self.deref_widget.redraw(cx);
+    }
+}
+
+impl GNotificationBellRef {
+    ref_event_option! {
+        clicked => GViewClickedParam
+    }
+    /// the unread count shown by the badge
+    pub fn count(&self) -> usize {
+        self.borrow().map_or(0, |c_ref| c_ref.count)
+    }
+}
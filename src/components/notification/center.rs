@@ -0,0 +1,291 @@
+use makepad_widgets::*;
+
+use crate::{
+    components::{
+        button::GButtonWidgetExt,
+        drop_down::PopupMenuGlobal,
+        label::{GLabelWidgetExt, GLabelWidgetRefExt},
+        popup::{GPopup, PopupStackGlobal},
+        view::{GViewWidgetExt, GViewWidgetRefExt},
+    },
+    shader::manual::Position,
+};
+
+use super::{
+    types::{now, relative_time},
+    GNotification, GNotificationCenterAction, GNotificationGlobal,
+};
+
+/// seconds between the refreshes of the relative times
+const TIME_INTERVAL: f64 = 30.0;
+
+live_design! {
+    GNotificationCenterHostBase = {{GNotificationCenterHost}}{}
+}
+
+/// # Notification Center Host
+/// Draw the history of `GNotification` into a drawer (`popup`, a `GDrawer` in `PopupMenuGlobal`),
+/// open it with `GNotification::open_center` or a `GNotificationBell`.
+///
+/// - The container should have `mark_all`, `clear` (GButton), `list` (GView) and `empty` (GLabel).
+/// - The records are grouped by source (newest first), each group has a header generated from `group`
+/// (a GLabel) and rows generated from `record` (a GView with `dot` (GView), `title`, `body`, `time` (GLabel)).
+/// - The `dot` of an unread record is shown, clicking a record marks it read and posts a `GNotificationCenterAction`.
+/// - A click on the mask or Escape closes the drawer.
+#[derive(Live, LiveRegister)]
+pub struct GNotificationCenterHost {
+    #[live]
+    pub popup: Option<LivePtr>,
+    #[live]
+    pub group: Option<LivePtr>,
+    #[live]
+    pub record: Option<LivePtr>,
+    /// the side of the window
+    #[live(Position::Right)]
+    pub position: Position,
+    /// the width (or height) of the drawer, as a proportion of the window
+    #[live(0.3)]
+    pub proportion: f32,
+    /// the group header of the records without a source
+    #[live]
+    pub default_source: String,
+    #[rust]
+    pub opened: bool,
+    /// the history version of the list
+    #[rust]
+    pub version: Option<u64>,
+    /// the record rows, their ids and times
+    #[rust]
+    pub rows: Vec<(WidgetRef, u64, f64)>,
+    /// refresh the relative times while the drawer is open
+    #[rust]
+    pub timer: Timer,
+    /// raise and lock the drawer when drawing
+    #[rust]
+    pub opening: bool,
+    #[rust(true)]
+    pub redraw_flag: bool,
+}
+
+impl LiveHook for GNotificationCenterHost {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if self.popup.is_none() || !apply.from.is_from_doc() {
+            return;
+        }
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut global_map = global.map.borrow_mut();
+        global_map.retain(|k, _| cx.live_registry.borrow().generation_valid(*k));
+        let popup = self.popup.unwrap();
+        global_map.get_or_insert(cx, popup, |cx| GPopup::new_from_ptr(cx, Some(popup)));
+        self.version = None;
+    }
+}
+
+impl GNotificationCenterHost {
+    /// follow `GNotification::open_center/close_center`, return true if changed
+    fn sync_open(&mut self, cx: &mut Cx) -> bool {
+        let opened = cx.global::<GNotificationGlobal>().center.get();
+        if opened == self.opened {
+            return false;
+        }
+        self.opened = opened;
+        if opened {
+            // rebuild for the relative times
+            self.version = None;
+            self.opening = true;
+            self.redraw_flag = true;
+            self.timer = cx.start_interval(TIME_INTERVAL);
+        } else {
+            cx.stop_timer(self.timer);
+            self.timer = Timer::empty();
+            self.unlock(cx);
+        }
+        true
+    }
+    /// rebuild the list when the history is changed
+    fn sync_list(&mut self, cx: &mut Cx) {
+        let popup = match self.popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        let global = cx.global::<GNotificationGlobal>().clone();
+        let history = global.history.borrow();
+        if self.version == Some(history.version) {
+            return;
+        }
+        self.version = Some(history.version);
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let container = match map.get_mut(&popup) {
+            Some(popup) => popup.get_mut(),
+            None => return,
+        };
+        if let Some(mut empty) = container.glabel(id!(empty)).borrow_mut() {
+            empty.visible = history.records.is_empty();
+        }
+        self.rows.clear();
+        let now = now();
+        if let Some(mut list) = container.gview(id!(list)).borrow_mut() {
+            list.children.clear();
+            for (source, records) in history.groups() {
+                let group = WidgetRef::new_from_ptr(cx, self.group);
+                let source = if source.is_empty() {
+                    self.default_source.as_str()
+                } else {
+                    source
+                };
+                group.as_glabel().set_text(source);
+                list.children.push((LiveId(list.children.len() as u64), group));
+                for record in records {
+                    let row = WidgetRef::new_from_ptr(cx, self.record);
+                    row.glabel(id!(title)).set_text(&record.title);
+                    row.glabel(id!(body)).set_text(&record.body);
+                    row.glabel(id!(time))
+                        .set_text(&relative_time(now - record.time));
+                    let dot = row.gview(id!(dot));
+                    dot.apply_over(cx, live! {theme: (record.kind.theme())});
+                    if let Some(mut dot) = dot.borrow_mut() {
+                        dot.visible = !record.read;
+                    }
+                    list.children.push((LiveId(list.children.len() as u64), row.clone()));
+                    self.rows.push((row, record.id, record.time));
+                }
+            }
+        }
+        container.redraw(cx);
+        self.redraw_flag = true;
+    }
+    /// update the relative times of the rows
+    fn sync_times(&mut self, cx: &mut Cx) {
+        let now = now();
+        for (row, _, time) in self.rows.iter() {
+            row.glabel(id!(time)).set_text(&relative_time(now - time));
+        }
+        self.redraw_flag = true;
+        if let Some(popup) = self.popup {
+            let global = cx.global::<PopupMenuGlobal>().clone();
+            let mut map = global.map.borrow_mut();
+            if let Some(popup) = map.get_mut(&popup) {
+                popup.redraw_container(cx);
+            }
+        }
+    }
+    /// the id of the drawer in `PopupStack`, the host lives in the app so its address does not change
+    fn uid(&self) -> WidgetUid {
+        WidgetUid(self as *const Self as u64)
+    }
+    /// leave the popup stack, the popup below (the center may be opened from a popup) gets the lock back
+    fn unlock(&mut self, cx: &mut Cx) {
+        let area = self.popup.and_then(|popup| {
+            let global = cx.global::<PopupMenuGlobal>().clone();
+            let map = global.map.borrow();
+            map.get(&popup).map(|popup| popup.area())
+        });
+        PopupStackGlobal::close(cx, self.uid(), area.unwrap_or(Area::Empty));
+    }
+    /// return true if the drawer is changed (the window should redraw)
+    pub fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) -> bool {
+        let mut changed = self.sync_open(cx);
+        if self.timer.is_event(event).is_some() {
+            self.sync_times(cx);
+            changed = true;
+        }
+        let popup = match (self.popup, self.opened) {
+            (Some(popup), true) => popup,
+            _ => return changed,
+        };
+        let uid = self.uid();
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return changed,
+        };
+        let area = popup.area();
+        let actions = cx.capture_actions(|cx| popup.handle_event_with(cx, event, scope, area));
+        let (mut mark_all, mut clear, mut clicked) = (false, false, None);
+        if !actions.is_empty() {
+            let container = popup.get();
+            mark_all = container.gbutton(id!(mark_all)).clicked(&actions).is_some();
+            clear = container.gbutton(id!(clear)).clicked(&actions).is_some();
+            clicked = self
+                .rows
+                .iter()
+                .find(|(row, _, _)| row.as_gview().clicked(&actions).is_some())
+                .map(|(_, id, _)| *id);
+        }
+        // a popup opened from the drawer (above it) closes first
+        let close = match event {
+            Event::KeyDown(e) => {
+                e.key_code == KeyCode::Escape && PopupStackGlobal::is_top(cx, uid)
+            }
+            Event::MouseDown(e) => {
+                !popup.container_contains_pos(cx, e.abs)
+                    && !PopupStackGlobal::above_contains_pos(cx, uid, e.abs)
+            }
+            _ => false,
+        };
+        drop(map);
+        if mark_all {
+            GNotification::mark_all_read(cx);
+        }
+        if clear {
+            GNotification::clear_history(cx);
+        }
+        if let Some(id) = clicked {
+            let global = cx.global::<GNotificationGlobal>().clone();
+            let mut history = global.history.borrow_mut();
+            history.mark_read(id);
+            let record = history.records.iter().find(|record| record.id == id).cloned();
+            drop(history);
+            if let Some(record) = record {
+                cx.action(GNotificationCenterAction { record });
+            }
+            cx.redraw_all();
+        }
+        if close {
+            GNotification::close_center(cx);
+            self.sync_open(cx);
+        }
+        changed || mark_all || clear || clicked.is_some() || close
+    }
+    pub fn draw(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        self.sync_open(cx);
+        if !self.opened {
+            return;
+        }
+        self.sync_list(cx);
+        let popup = match self.popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        let uid = self.uid();
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        let is_new = std::mem::take(&mut self.opening);
+        if is_new {
+            popup.raise();
+        }
+        popup.begin(cx);
+        popup.draw_container_drawer(
+            cx,
+            scope,
+            self.position,
+            self.proportion,
+            &mut self.redraw_flag,
+        );
+        popup.end(cx, scope, Area::Empty, DVec2::default());
+        if is_new {
+            // the drawer is modal, only the popup gets the finger events
+            PopupStackGlobal::open(cx, uid, popup.area());
+        }
+        PopupStackGlobal::set_areas(cx, uid, vec![popup.container_area()]);
+    }
+    pub fn is_open(&self) -> bool {
+        self.opened
+    }
+}
@@ -0,0 +1,55 @@
+use makepad_widgets::Actions;
+
+use super::types::NotificationRecord;
+
+/// Why a toast of `GNotification` is closed
+#[derive(Debug, Clone, PartialEq)]
+pub enum GNotificationResult {
+    /// index of the clicked action button
+    Action(usize),
+    /// the close button is clicked
+    Closed,
+    /// the duration is over, or it is pushed out by newer toasts (`max_count`)
+    Timeout,
+}
+
+/// The action posted when a toast is closed, match it with `GNotificationHandle::result`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GNotificationAction {
+    pub id: u64,
+    pub result: GNotificationResult,
+}
+
+/// # Notification Handle
+/// returned by `GNotification::push`, the result arrives as an action
+/// ```rust
+/// let handle = GNotification::push_with_actions(
+///     cx, GNotificationKind::Info, "Update", "A new version is ready", 0.0, &["Restart"],
+/// );
+/// // in handle_actions
+/// if let Some(GNotificationResult::Action(0)) = handle.result(actions) {
+///     // restart ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GNotificationHandle {
+    pub id: u64,
+}
+
+impl GNotificationHandle {
+    pub fn result(&self, actions: &Actions) -> Option<GNotificationResult> {
+        actions.iter().find_map(|action| {
+            action
+                .downcast_ref::<GNotificationAction>()
+                .filter(|action| action.id == self.id)
+                .map(|action| action.result.clone())
+        })
+    }
+}
+
+/// The action posted when a record in the notification center is clicked (it is marked read),
+/// match it with `GNotification::record_clicked`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GNotificationCenterAction {
+    pub record: NotificationRecord,
+}
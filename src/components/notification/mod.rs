@@ -1,25 +1,387 @@
+pub mod bell;
+pub mod center;
+mod event;
 mod register;
+pub mod types;
 
+pub use event::*;
 pub use register::register;
 
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
 use makepad_widgets::*;
+use types::{
+    corner_shift, is_bottom, now, GNotificationKind, GNotificationRequest, NotificationHistory,
+    NotificationQueue, NotificationRecord,
+};
+
+use crate::shader::manual::Position;
+
+use super::{
+    button::GButtonWidgetRefExt,
+    drop_down::PopupMenuGlobal,
+    icon::GIconWidgetRefExt,
+    label::GLabelWidgetRefExt,
+    popup::GPopup,
+    view::GViewWidgetRefExt,
+};
+
+live_design! {
+    GNotificationHostBase = {{GNotificationHost}}{}
+}
 
-use super::view::GView;
+#[derive(Default, Clone)]
+pub struct GNotificationGlobal {
+    pub queue: Rc<RefCell<NotificationQueue>>,
+    pub history: Rc<RefCell<NotificationHistory>>,
+    /// the notification center should be open
+    pub center: Rc<Cell<bool>>,
+}
+
+/// # Notification
+/// Push a toast from anywhere, the toasts are shown by the `GNotificationHost` of `GWindow`
+/// (stacked in the corner of its `position`), the close reason arrives as a `GNotificationAction`,
+/// see `GNotificationHandle::result`.
+///
+/// Every notification is also kept in the history, which is listed by the notification center
+/// (`GNotificationCenterHost`, a drawer of `GWindow`) grouped by `GNotificationRequest::source`.
+/// `GNotificationBell` shows the unread count and toggles the center.
+pub struct GNotification;
+
+impl GNotification {
+    pub fn open(cx: &mut Cx, mut request: GNotificationRequest) -> GNotificationHandle {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        let id = global.queue.borrow_mut().push(request.clone());
+        request.id = id;
+        let record = NotificationRecord::new(&request, now());
+        global.history.borrow_mut().add(record);
+        cx.redraw_all();
+        GNotificationHandle { id }
+    }
+    /// add a notification to the history without showing a toast
+    pub fn record(cx: &mut Cx, mut request: GNotificationRequest) -> GNotificationHandle {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        request.id = global.queue.borrow_mut().new_id();
+        let record = NotificationRecord::new(&request, now());
+        global.history.borrow_mut().add(record);
+        cx.redraw_all();
+        GNotificationHandle { id: request.id }
+    }
+    /// a toast which closes itself after `duration` seconds (`0.0` keeps it until closed)
+    pub fn push(
+        cx: &mut Cx,
+        kind: GNotificationKind,
+        title: &str,
+        body: &str,
+        duration: f64,
+    ) -> GNotificationHandle {
+        Self::push_with_actions(cx, kind, title, body, duration, &[])
+    }
+    /// a toast with action buttons, clicking one closes it with `GNotificationResult::Action(index)`
+    pub fn push_with_actions(
+        cx: &mut Cx,
+        kind: GNotificationKind,
+        title: &str,
+        body: &str,
+        duration: f64,
+        actions: &[&str],
+    ) -> GNotificationHandle {
+        Self::open(
+            cx,
+            GNotificationRequest {
+                kind,
+                title: title.to_string(),
+                body: body.to_string(),
+                duration,
+                actions: actions.iter().map(|action| action.to_string()).collect(),
+                ..Default::default()
+            },
+        )
+    }
+    /// close a toast, nothing is posted
+    pub fn close(cx: &mut Cx, handle: GNotificationHandle) {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        global.queue.borrow_mut().close(handle.id);
+        cx.redraw_all();
+    }
+    /// the count of unread records, for a badge
+    pub fn unread_count(cx: &mut Cx) -> usize {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        let count = global.history.borrow().unread_count();
+        count
+    }
+    /// the records of the history, oldest first
+    pub fn records(cx: &mut Cx) -> Vec<NotificationRecord> {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        let records = global.history.borrow().records.clone();
+        records
+    }
+    pub fn mark_read(cx: &mut Cx, handle: GNotificationHandle) {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        if global.history.borrow_mut().mark_read(handle.id) {
+            cx.redraw_all();
+        }
+    }
+    pub fn mark_all_read(cx: &mut Cx) {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        global.history.borrow_mut().mark_all_read();
+        cx.redraw_all();
+    }
+    /// remove all records of the history (the shown toasts stay)
+    pub fn clear_history(cx: &mut Cx) {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        global.history.borrow_mut().clear();
+        cx.redraw_all();
+    }
+    pub fn open_center(cx: &mut Cx) {
+        Self::set_center(cx, true);
+    }
+    pub fn close_center(cx: &mut Cx) {
+        Self::set_center(cx, false);
+    }
+    pub fn toggle_center(cx: &mut Cx) {
+        let opened = Self::is_center_open(cx);
+        Self::set_center(cx, !opened);
+    }
+    pub fn is_center_open(cx: &mut Cx) -> bool {
+        cx.global::<GNotificationGlobal>().center.get()
+    }
+    fn set_center(cx: &mut Cx, opened: bool) {
+        cx.global::<GNotificationGlobal>().center.set(opened);
+        cx.redraw_all();
+    }
+    /// the record clicked in the notification center
+    pub fn record_clicked(actions: &Actions) -> Option<NotificationRecord> {
+        actions.iter().find_map(|action| {
+            action
+                .downcast_ref::<GNotificationCenterAction>()
+                .map(|action| action.record.clone())
+        })
+    }
+}
 
-live_design!{
-    GNotificationBase = {{GNotification}} {}
+/// A shown toast
+pub struct Toast {
+    pub request: GNotificationRequest,
+    pub widget: WidgetRef,
+    pub timer: Timer,
+    pub hovered: bool,
 }
 
-#[derive(Live, Widget, LiveHook)]
-pub struct GNotification{
-    #[deref]
+/// # Notification Host
+/// Draw the toasts of `GNotification` into the popup (in `PopupMenuGlobal`) at the corner,
+/// each toast is generated from `toast` (a GView with `title`, `body` (GLabel), `close` (GIcon)
+/// and `actions` children), the action buttons are generated from `action_button`.
+/// The timer of a toast stops while it is hovered and restarts when the pointer leaves.
+#[derive(Live, LiveRegister)]
+pub struct GNotificationHost {
+    #[live]
+    pub popup: Option<LivePtr>,
     #[live]
-    pub view: GView,
+    pub toast: Option<LivePtr>,
+    #[live]
+    pub action_button: Option<LivePtr>,
+    /// the corner (or the center of the top/bottom edge)
+    #[live(Position::TopRight)]
+    pub position: Position,
+    /// the distance to the window edge
+    #[live(16.0)]
+    pub margin: f64,
+    /// the oldest toast is closed when there are more
+    #[live(5)]
+    pub max_count: usize,
+    #[rust]
+    pub toasts: Vec<Toast>,
+    #[rust(true)]
+    pub redraw_flag: bool,
 }
 
-impl Widget for GNotification{
-    fn draw_walk(&mut self, _cx: &mut Cx2d, _scope: &mut Scope, _walk: Walk) -> DrawStep {
-        DrawStep::done()
+impl LiveHook for GNotificationHost {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if self.popup.is_none() || !apply.from.is_from_doc() {
+            return;
+        }
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut global_map = global.map.borrow_mut();
+        global_map.retain(|k, _| cx.live_registry.borrow().generation_valid(*k));
+        let popup = self.popup.unwrap();
+        global_map.get_or_insert(cx, popup, |cx| GPopup::new_from_ptr(cx, Some(popup)));
     }
 }
 
+impl GNotificationHost {
+    /// show the pushed toasts and remove the closed ones, return true if changed
+    fn sync(&mut self, cx: &mut Cx) -> bool {
+        let global = cx.global::<GNotificationGlobal>().clone();
+        let mut queue = global.queue.borrow_mut();
+        let requests = queue.take();
+        let closed = queue.take_closed();
+        drop(queue);
+        if requests.is_empty() && closed.is_empty() {
+            return false;
+        }
+        for id in closed {
+            self.remove(cx, id, None);
+        }
+        for request in requests {
+            let toast = self.new_toast(cx, request);
+            self.toasts.push(toast);
+        }
+        let over = self.toasts.len().saturating_sub(self.max_count.max(1));
+        let removed: Vec<u64> = self.toasts[..over]
+            .iter()
+            .map(|toast| toast.request.id)
+            .collect();
+        for id in removed {
+            self.remove(cx, id, Some(GNotificationResult::Timeout));
+        }
+        self.sync_container(cx);
+        true
+    }
+    fn new_toast(&mut self, cx: &mut Cx, request: GNotificationRequest) -> Toast {
+        let widget = WidgetRef::new_from_ptr(cx, self.toast);
+        widget.apply_over(cx, live! {theme: (request.kind.theme())});
+        let title = widget.glabel(id!(title));
+        title.set_text(&request.title);
+        if let Some(mut title) = title.borrow_mut() {
+            title.visible = !request.title.is_empty();
+        }
+        widget.glabel(id!(body)).set_text(&request.body);
+        if let Some(mut actions) = widget.gview(id!(actions)).borrow_mut() {
+            actions.visible = !request.actions.is_empty();
+            actions.children.clear();
+            for (index, text) in request.actions.iter().enumerate() {
+                let button = WidgetRef::new_from_ptr(cx, self.action_button);
+                if let Some(button) = button.as_gbutton().borrow() {
+                    button.slot.set_text(text);
+                }
+                actions.children.push((LiveId(index as u64), button));
+            }
+        }
+        let timer = if request.duration > 0.0 {
+            cx.start_timeout(request.duration)
+        } else {
+            Timer::empty()
+        };
+        Toast {
+            request,
+            widget,
+            timer,
+            hovered: false,
+        }
+    }
+    /// put the toasts into the container, the newest is nearest to the edge
+    fn sync_container(&mut self, cx: &mut Cx) {
+        let popup = match self.popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let container = match map.get_mut(&popup) {
+            Some(popup) => popup.get_mut(),
+            None => return,
+        };
+        container.children = self
+            .toasts
+            .iter()
+            .map(|toast| (LiveId(toast.request.id), toast.widget.clone()))
+            .collect();
+        if !is_bottom(self.position) {
+            container.children.reverse();
+        }
+        container.redraw(cx);
+        self.redraw_flag = true;
+    }
+    /// remove the toast and post the result (None if it is closed by code)
+    fn remove(&mut self, cx: &mut Cx, id: u64, result: Option<GNotificationResult>) {
+        let index = match self.toasts.iter().position(|toast| toast.request.id == id) {
+            Some(index) => index,
+            None => return,
+        };
+        let toast = self.toasts.remove(index);
+        if !toast.timer.is_empty() {
+            cx.stop_timer(toast.timer);
+        }
+        if let Some(result) = result {
+            cx.action(GNotificationAction { id, result });
+        }
+    }
+    /// return true if the toasts are changed (the window should redraw)
+    pub fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) -> bool {
+        let mut changed = self.sync(cx);
+        let popup = match (self.popup, self.toasts.is_empty()) {
+            (Some(popup), false) => popup,
+            _ => return changed,
+        };
+        let mut results = vec![];
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        if let Some(popup) = map.get_mut(&popup) {
+            let area = popup.area();
+            let actions = cx.capture_actions(|cx| popup.handle_event_with(cx, event, scope, area));
+            if !actions.is_empty() {
+                for toast in self.toasts.iter() {
+                    if toast.widget.gicon(id!(close)).clicked(&actions).is_some() {
+                        results.push((toast.request.id, GNotificationResult::Closed));
+                    }
+                    if let Some(buttons) = toast.widget.gview(id!(actions)).borrow() {
+                        for (index, (_, button)) in buttons.children.iter().enumerate() {
+                            if button.as_gbutton().clicked(&actions).is_some() {
+                                let result = GNotificationResult::Action(index);
+                                results.push((toast.request.id, result));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        drop(map);
+        for toast in self.toasts.iter_mut() {
+            if toast.timer.is_event(event).is_some() {
+                toast.timer = Timer::empty();
+                results.push((toast.request.id, GNotificationResult::Timeout));
+            }
+            // pause on hover
+            if let Event::MouseMove(e) = event {
+                let hovered = toast.widget.area().rect(cx).contains(e.abs);
+                if hovered && !toast.hovered && !toast.timer.is_empty() {
+                    cx.stop_timer(toast.timer);
+                    toast.timer = Timer::empty();
+                } else if !hovered && toast.hovered && toast.request.duration > 0.0 {
+                    toast.timer = cx.start_timeout(toast.request.duration);
+                }
+                toast.hovered = hovered;
+            }
+        }
+        if !results.is_empty() {
+            for (id, result) in results {
+                self.remove(cx, id, Some(result));
+            }
+            self.sync_container(cx);
+            changed = true;
+        }
+        changed
+    }
+    pub fn draw(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        self.sync(cx);
+        let popup = match (self.popup, self.toasts.is_empty()) {
+            (Some(popup), false) => popup,
+            _ => return,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        popup.begin(cx);
+        popup.draw_container(cx, scope, None, 0.0, &mut self.redraw_flag);
+        let size = popup.container_area().rect(cx).size;
+        let shift = corner_shift(self.position, cx.current_pass_size(), size, self.margin);
+        popup.end(cx, scope, Area::Empty, shift);
+    }
+}
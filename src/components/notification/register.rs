@@ -2,4 +2,6 @@ use makepad_widgets::Cx;
 
 pub fn register(cx: &mut Cx) {
     crate::components::notification::live_design(cx);
+    crate::components::notification::center::live_design(cx);
+    crate::components::notification::bell::live_design(cx);
 }
\ No newline at end of file
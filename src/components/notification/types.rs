@@ -0,0 +1,320 @@
+use makepad_widgets::*;
+
+use crate::{shader::manual::Position, themes::Themes};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GNotificationKind {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl GNotificationKind {
+    /// the theme of the toast
+    pub fn theme(&self) -> Themes {
+        match self {
+            GNotificationKind::Info => Themes::Info,
+            GNotificationKind::Success => Themes::Success,
+            GNotificationKind::Warning => Themes::Warning,
+            GNotificationKind::Error => Themes::Error,
+        }
+    }
+}
+
+/// A toast waiting to be shown by `GNotificationHost`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GNotificationRequest {
+    pub id: u64,
+    pub kind: GNotificationKind,
+    /// who sends it, the notification center groups the history by source
+    pub source: String,
+    pub title: String,
+    pub body: String,
+    /// seconds before it closes itself, `0.0` keeps it until closed
+    pub duration: f64,
+    /// texts of the action buttons, clicking one closes the toast
+    pub actions: Vec<String>,
+}
+
+/// The toasts which are pushed but not shown yet, and the shown toasts closed by code
+#[derive(Debug, Default)]
+pub struct NotificationQueue {
+    pub next_id: u64,
+    pub requests: Vec<GNotificationRequest>,
+    pub closed: Vec<u64>,
+}
+
+impl NotificationQueue {
+    pub fn push(&mut self, mut request: GNotificationRequest) -> u64 {
+        request.id = self.new_id();
+        self.requests.push(request);
+        self.next_id
+    }
+    /// an unused id, the ids are shared by the toasts and the records without a toast
+    pub fn new_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+    /// take the toasts to show
+    pub fn take(&mut self) -> Vec<GNotificationRequest> {
+        std::mem::take(&mut self.requests)
+    }
+    /// remove the toast, or mark it closed if it is shown
+    pub fn close(&mut self, id: u64) {
+        let len = self.requests.len();
+        self.requests.retain(|request| request.id != id);
+        if self.requests.len() == len && id <= self.next_id && !self.closed.contains(&id) {
+            self.closed.push(id);
+        }
+    }
+    pub fn take_closed(&mut self) -> Vec<u64> {
+        std::mem::take(&mut self.closed)
+    }
+}
+
+/// the max count of records in `NotificationHistory`, the oldest ones are dropped
+pub const HISTORY_LIMIT: usize = 200;
+
+/// A notification kept in the history of the notification center
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NotificationRecord {
+    /// the id of the toast (`GNotificationHandle::id`)
+    pub id: u64,
+    pub kind: GNotificationKind,
+    pub source: String,
+    pub title: String,
+    pub body: String,
+    /// seconds since the unix epoch
+    pub time: f64,
+    pub read: bool,
+}
+
+impl NotificationRecord {
+    pub fn new(request: &GNotificationRequest, time: f64) -> Self {
+        Self {
+            id: request.id,
+            kind: request.kind,
+            source: request.source.clone(),
+            title: request.title.clone(),
+            body: request.body.clone(),
+            time,
+            read: false,
+        }
+    }
+}
+
+/// # Notification History
+/// The records of the pushed notifications (oldest first),
+/// `version` changes on every change so the notification center knows when to rebuild
+#[derive(Debug, Default)]
+pub struct NotificationHistory {
+    pub records: Vec<NotificationRecord>,
+    pub version: u64,
+}
+
+impl NotificationHistory {
+    pub fn add(&mut self, record: NotificationRecord) {
+        self.records.push(record);
+        let over = self.records.len().saturating_sub(HISTORY_LIMIT);
+        self.records.drain(..over);
+        self.version += 1;
+    }
+    pub fn unread_count(&self) -> usize {
+        self.records.iter().filter(|record| !record.read).count()
+    }
+    /// return true if the record is unread before
+    pub fn mark_read(&mut self, id: u64) -> bool {
+        match self
+            .records
+            .iter_mut()
+            .find(|record| record.id == id && !record.read)
+        {
+            Some(record) => {
+                record.read = true;
+                self.version += 1;
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn mark_all_read(&mut self) {
+        if self.unread_count() > 0 {
+            self.records.iter_mut().for_each(|record| record.read = true);
+            self.version += 1;
+        }
+    }
+    pub fn clear(&mut self) {
+        if !self.records.is_empty() {
+            self.records.clear();
+            self.version += 1;
+        }
+    }
+    /// ## Groups
+    /// the records grouped by source, the group with the newest record comes first
+    /// and the records in a group are newest first
+    pub fn groups(&self) -> Vec<(&str, Vec<&NotificationRecord>)> {
+        let mut groups: Vec<(&str, Vec<&NotificationRecord>)> = vec![];
+        for record in self.records.iter().rev() {
+            match groups
+                .iter_mut()
+                .find(|(source, _)| *source == record.source)
+            {
+                Some((_, records)) => records.push(record),
+                None => groups.push((&record.source, vec![record])),
+            }
+        }
+        groups
+    }
+}
+
+/// seconds since the unix epoch
+pub fn now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |duration| duration.as_secs_f64())
+}
+
+/// the time of a record relative to now: `just now`, `5 min ago`, `2 h ago`, `3 d ago`
+pub fn relative_time(elapsed: f64) -> String {
+    let minutes = (elapsed.max(0.0) / 60.0) as u64;
+    match minutes {
+        0 => "just now".to_string(),
+        1..=59 => format!("{} min ago", minutes),
+        60..=1439 => format!("{} h ago", minutes / 60),
+        _ => format!("{} d ago", minutes / 1440),
+    }
+}
+
+/// the text of a badge, empty when the count is 0, `{max}+` when the count is over max
+pub fn badge_text(count: usize, max: usize) -> String {
+    match count {
+        0 => String::new(),
+        count if count > max => format!("{}+", max),
+        count => count.to_string(),
+    }
+}
+
+/// the toasts grow from the bottom edge (the newest is at the bottom)
+pub fn is_bottom(position: Position) -> bool {
+    matches!(
+        position,
+        Position::Bottom
+            | Position::BottomLeft
+            | Position::BottomRight
+            | Position::LeftBottom
+            | Position::RightBottom
+    )
+}
+
+/// the absolute position of the toast column in the corner (or the center of the edge)
+pub fn corner_shift(position: Position, viewport: DVec2, size: DVec2, margin: f64) -> DVec2 {
+    let x = match position {
+        Position::Top | Position::Bottom => (viewport.x - size.x) / 2.0,
+        Position::TopLeft
+        | Position::BottomLeft
+        | Position::Left
+        | Position::LeftTop
+        | Position::LeftBottom => margin,
+        _ => viewport.x - size.x - margin,
+    };
+    let y = if is_bottom(position) {
+        viewport.y - size.y - margin
+    } else {
+        margin
+    };
+    dvec2(x, y)
+}
+
+#[cfg(test)]
+mod test_notification {
+    use super::*;
+
+    #[test]
+    fn queue() {
+        let mut queue = NotificationQueue::default();
+        let first = queue.push(GNotificationRequest::default());
+        let second = queue.push(GNotificationRequest::default());
+        queue.close(second);
+        let shown = queue.take();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].id, first);
+        assert!(queue.take_closed().is_empty());
+        // the shown one is closed later
+        queue.close(first);
+        assert_eq!(queue.take_closed(), vec![first]);
+        queue.close(99);
+        assert!(queue.take_closed().is_empty());
+    }
+
+    fn record(id: u64, source: &str) -> NotificationRecord {
+        NotificationRecord {
+            id,
+            source: source.to_string(),
+            time: id as f64,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn history() {
+        let mut history = NotificationHistory::default();
+        history.add(record(1, "mail"));
+        history.add(record(2, ""));
+        history.add(record(3, "mail"));
+        assert_eq!(history.unread_count(), 3);
+        let groups = history.groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "mail");
+        let ids: Vec<u64> = groups[0].1.iter().map(|record| record.id).collect();
+        assert_eq!(ids, vec![3, 1]);
+        assert_eq!(groups[1].0, "");
+        // read state
+        let version = history.version;
+        assert!(history.mark_read(1));
+        assert!(!history.mark_read(1));
+        assert_eq!(history.version, version + 1);
+        history.mark_all_read();
+        assert_eq!(history.unread_count(), 0);
+        history.clear();
+        assert!(history.groups().is_empty());
+        // the oldest ones are dropped
+        for id in 0..HISTORY_LIMIT as u64 + 10 {
+            history.add(record(id, ""));
+        }
+        assert_eq!(history.records.len(), HISTORY_LIMIT);
+        assert_eq!(history.records[0].id, 10);
+    }
+
+    #[test]
+    fn texts() {
+        assert_eq!(relative_time(-5.0), "just now");
+        assert_eq!(relative_time(59.0), "just now");
+        assert_eq!(relative_time(300.0), "5 min ago");
+        assert_eq!(relative_time(7200.0), "2 h ago");
+        assert_eq!(relative_time(3.0 * 86400.0), "3 d ago");
+        assert_eq!(badge_text(0, 99), "");
+        assert_eq!(badge_text(7, 99), "7");
+        assert_eq!(badge_text(120, 99), "99+");
+    }
+
+    #[test]
+    fn corner() {
+        let viewport = dvec2(800.0, 600.0);
+        let size = dvec2(300.0, 200.0);
+        assert_eq!(
+            corner_shift(Position::TopRight, viewport, size, 16.0),
+            dvec2(484.0, 16.0)
+        );
+        assert_eq!(
+            corner_shift(Position::BottomLeft, viewport, size, 16.0),
+            dvec2(16.0, 384.0)
+        );
+        assert_eq!(
+            corner_shift(Position::Bottom, viewport, size, 16.0),
+            dvec2(250.0, 384.0)
+        );
+        assert!(!is_bottom(Position::TopLeft));
+    }
+}
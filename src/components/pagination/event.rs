@@ -0,0 +1,16 @@
+use makepad_widgets::{ActionDefaultRef, DefaultNone};
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum GPaginationEvent {
+    /// current page or page size has been changed by user
+    PageChanged(GPaginationChangedParam),
+    None,
+}
+
+#[derive(Clone, Debug)]
+pub struct GPaginationChangedParam {
+    /// current page, start from 1
+    pub current: usize,
+    pub page_size: usize,
+    pub total_pages: usize,
+}
@@ -0,0 +1,259 @@
+mod event;
+mod register;
+pub mod types;
+
+pub use event::*;
+pub use register::register;
+
+use makepad_widgets::*;
+use types::{page_items, total_pages, PageItem};
+
+use crate::{event_option, ref_event_option, utils::LiveIdGenerate, widget_area};
+
+use super::{
+    button::GButtonWidgetRefExt,
+    icon::GIconWidgetExt,
+    input::GInputWidgetExt,
+    label::GLabelWidgetExt,
+    select::{types::SelectOption, GSelectWidgetExt},
+    view::{GView, GViewWidgetExt},
+};
+
+live_design! {
+    GPaginationBase = {{GPagination}}{}
+}
+
+/// # GPagination
+/// A pagination bar: total count label, prev/next icons, page list with ellipsis,
+/// a page size select and a jump to page input.
+/// The page list is generated from `page_item` (a GButton template) and drawn into the `pages` child.
+/// `PageChanged` is emitted when user changes the page or the page size.
+#[derive(Live, Widget)]
+pub struct GPagination {
+    #[deref]
+    pub deref_widget: GView,
+    /// current page, start from 1
+    #[live(1)]
+    pub current: usize,
+    #[live(10)]
+    pub page_size: usize,
+    /// count of all items
+    #[live(0)]
+    pub total: usize,
+    /// options of page size select, if empty use `[10, 20, 50, 100]`
+    #[live]
+    pub page_sizes: Vec<usize>,
+    /// count of pages shown on each side of current page
+    #[live(1)]
+    pub siblings: usize,
+    /// page item template
+    #[live]
+    pub page_item: Option<LivePtr>,
+    #[rust]
+    items: Vec<PageItem>,
+}
+
+impl Widget for GPagination {
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible {
+            return DrawStep::done();
+        }
+        self.render_pages(cx);
+        self.deref_widget.draw_walk(cx, scope, walk)
+    }
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible {
+            return;
+        }
+        let actions = cx.capture_actions(|cx| self.deref_widget.handle_event(cx, event, scope));
+        let mut changed = false;
+
+        if self.gicon(id!(prev)).clicked(&actions).is_some() {
+            changed = self.change_page(cx, self.current.saturating_sub(1));
+        }
+        if self.gicon(id!(next)).clicked(&actions).is_some() {
+            changed = self.change_page(cx, self.current + 1);
+        }
+        // page list ---------------------------------------------------------------------
+        let clicked = self.gview(id!(pages)).borrow().and_then(|pages| {
+            pages
+                .children
+                .iter()
+                .position(|(_, child)| child.as_gbutton().clicked(&actions).is_some())
+        });
+        if let Some(item) = clicked.and_then(|index| self.items.get(index).copied()) {
+            // ellipsis jumps over the hidden pages
+            let jump = self.siblings * 2 + 1;
+            let page = match item {
+                PageItem::Page(page) => page,
+                PageItem::Prev => self.current.saturating_sub(jump),
+                PageItem::Next => self.current + jump,
+            };
+            changed = self.change_page(cx, page);
+        }
+        // page size ---------------------------------------------------------------------
+        let size = self.gselect(id!(size));
+        let size_value = size
+            .changed(&actions)
+            .map(|e| e.value)
+            .or_else(|| size.key_changed(&actions).map(|e| e.value));
+        if let Some(value) = size_value {
+            if let Ok(page_size) = value.parse::<usize>() {
+                if page_size != self.page_size {
+                    self.set_page_size(cx, page_size);
+                    changed = true;
+                }
+            }
+        }
+        // jump to page ------------------------------------------------------------------
+        let jump = self.ginput(id!(jump));
+        if let Some(text) = jump.returned(&actions) {
+            if let Ok(page) = text.trim().parse::<usize>() {
+                changed = self.change_page(cx, page);
+            }
+            jump.set_text("");
+            jump.redraw(cx);
+        }
+
+        if changed && self.event_key {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                GPaginationEvent::PageChanged(GPaginationChangedParam {
+                    current: self.current,
+                    page_size: self.page_size,
+                    total_pages: self.total_pages(),
+                }),
+            );
+        }
+    }
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+impl LiveHook for GPagination {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) {
+        self.deref_widget.after_apply(cx, apply, index, nodes);
+        self.render_sizes();
+    }
+}
+
+impl GPagination {
+    widget_area! {
+        area, deref_widget
+    }
+    event_option! {
+        page_changed: GPaginationEvent::PageChanged => GPaginationChangedParam
+    }
+    pub fn redraw(&mut self, cx: &mut Cx) {
+        self.deref_widget.redraw(cx);
+    }
+    pub fn total_pages(&self) -> usize {
+        total_pages(self.total, self.page_size)
+    }
+    /// set the options of page size select, current page size is always in the options
+    fn render_sizes(&mut self) {
+        let mut sizes = if self.page_sizes.is_empty() {
+            vec![10, 20, 50, 100]
+        } else {
+            self.page_sizes.clone()
+        };
+        if !sizes.contains(&self.page_size) {
+            sizes.push(self.page_size);
+            sizes.sort();
+        }
+        if let Some(mut select) = self.gselect(id!(size)).borrow_mut() {
+            select.selected = sizes
+                .iter()
+                .position(|size| *size == self.page_size)
+                .unwrap_or_default();
+            *select.options_mut() = sizes
+                .iter()
+                .map(|size| SelectOption::new(&format!("{} / page", size), &size.to_string()))
+                .collect();
+        }
+    }
+    /// generate the page list and the total label
+    fn render_pages(&mut self, cx: &mut Cx) {
+        let pages = self.total_pages();
+        self.current = self.current.clamp(1, pages);
+        self.items = page_items(self.current, pages, self.siblings);
+
+        self.glabel(id!(total))
+            .set_text(&format!("Total {}", self.total));
+
+        if let Some(mut pages) = self.gview(id!(pages)).borrow_mut() {
+            pages.children.truncate(self.items.len());
+            for (index, item) in self.items.iter().enumerate() {
+                if pages.children.get(index).is_none() {
+                    let child = WidgetRef::new_from_ptr(cx, self.page_item);
+                    pages.children.push((index.to_live_id(), child));
+                }
+                let (text, active) = match item {
+                    PageItem::Page(page) => (page.to_string(), *page == self.current),
+                    PageItem::Prev | PageItem::Next => ("…".to_string(), false),
+                };
+                if let Some(mut button) = pages.children[index].1.as_gbutton().borrow_mut() {
+                    button.slot.set_text(&text);
+                    button.background_visible = active;
+                    button.render(cx);
+                }
+            }
+        }
+    }
+    /// change current page, return true if the page is changed
+    fn change_page(&mut self, cx: &mut Cx, page: usize) -> bool {
+        let page = page.clamp(1, self.total_pages());
+        if page == self.current {
+            return false;
+        }
+        self.current = page;
+        self.redraw(cx);
+        true
+    }
+    pub fn set_current(&mut self, cx: &mut Cx, current: usize) {
+        self.change_page(cx, current);
+    }
+    /// set total count of items, current page is clamped into the new page count
+    pub fn set_total(&mut self, cx: &mut Cx, total: usize) {
+        self.total = total;
+        self.current = self.current.clamp(1, self.total_pages());
+        self.redraw(cx);
+    }
+    /// set page size, current page is changed to keep the first item of current page visible
+    pub fn set_page_size(&mut self, cx: &mut Cx, page_size: usize) {
+        let first = self.current.saturating_sub(1) * self.page_size;
+        self.page_size = page_size.max(1);
+        self.current = first / self.page_size + 1;
+        self.render_sizes();
+        self.redraw(cx);
+    }
+}
+
+impl GPaginationRef {
+    ref_event_option! {
+        page_changed => GPaginationChangedParam
+    }
+    pub fn current(&self) -> usize {
+        self.borrow().map(|c_ref| c_ref.current).unwrap_or(1)
+    }
+    pub fn page_size(&self) -> usize {
+        self.borrow().map(|c_ref| c_ref.page_size).unwrap_or_default()
+    }
+    pub fn set_current(&self, cx: &mut Cx, current: usize) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_current(cx, current);
+        }
+    }
+    pub fn set_total(&self, cx: &mut Cx, total: usize) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_total(cx, total);
+        }
+    }
+    pub fn set_page_size(&self, cx: &mut Cx, page_size: usize) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_page_size(cx, page_size);
+        }
+    }
+}
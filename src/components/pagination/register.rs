@@ -0,0 +1,5 @@
+use makepad_widgets::Cx;
+
+pub fn register(cx: &mut Cx) {
+    crate::components::pagination::live_design(cx);
+}
@@ -0,0 +1,70 @@
+/// # Page Item
+/// An item in the page list of GPagination
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageItem {
+    Page(usize),
+    /// the ellipsis before current page, click it to jump backward
+    Prev,
+    /// the ellipsis after current page, click it to jump forward
+    Next,
+}
+
+/// count of pages, at least 1 page even if there is no item
+pub fn total_pages(total: usize, page_size: usize) -> usize {
+    if page_size == 0 {
+        return 1;
+    }
+    ((total + page_size - 1) / page_size).max(1)
+}
+
+/// ## Build the page list
+/// The first and last page are always shown, `siblings` pages are shown on each side of current page,
+/// the others are collapsed into ellipsis. The count of items is fixed, so the list does not jump.
+/// - `current`: current page, start from 1
+/// - `pages`: count of pages
+pub fn page_items(current: usize, pages: usize, siblings: usize) -> Vec<PageItem> {
+    // first + last + current + siblings + 2 ellipsis
+    let count = siblings * 2 + 5;
+    if pages <= count {
+        return (1..=pages).map(PageItem::Page).collect();
+    }
+    let current = current.clamp(1, pages);
+    let mut items = vec![PageItem::Page(1)];
+    if current <= siblings + 3 {
+        items.extend((2..count - 1).map(PageItem::Page));
+        items.push(PageItem::Next);
+    } else if current >= pages - siblings - 2 {
+        items.push(PageItem::Prev);
+        items.extend((pages - count + 3..pages).map(PageItem::Page));
+    } else {
+        items.push(PageItem::Prev);
+        items.extend((current - siblings..=current + siblings).map(PageItem::Page));
+        items.push(PageItem::Next);
+    }
+    items.push(PageItem::Page(pages));
+    items
+}
+
+#[cfg(test)]
+mod test_pages {
+    use super::{page_items, total_pages, PageItem::*};
+
+    #[test]
+    fn pages() {
+        assert_eq!(total_pages(0, 10), 1);
+        assert_eq!(total_pages(101, 10), 11);
+        assert_eq!(page_items(1, 3, 1), vec![Page(1), Page(2), Page(3)]);
+        assert_eq!(
+            page_items(2, 10, 1),
+            vec![Page(1), Page(2), Page(3), Page(4), Page(5), Next, Page(10)]
+        );
+        assert_eq!(
+            page_items(5, 10, 1),
+            vec![Page(1), Prev, Page(4), Page(5), Page(6), Next, Page(10)]
+        );
+        assert_eq!(
+            page_items(8, 10, 1),
+            vec![Page(1), Prev, Page(6), Page(7), Page(8), Page(9), Page(10)]
+        );
+    }
+}
@@ -1,18 +1,29 @@
 mod register;
+mod stack;
 
 use makepad_widgets::*;
 pub use register::register;
+pub use stack::*;
 
 use crate::{
     shader::{
         draw_popup::DrawGPopup,
         manual::{CloseMode, PopupMode, Position},
     },
+    sync_theme,
     themes::Themes,
     utils::{BoolToF32, ThemeColor},
 };
 
-use super::view::GView;
+use super::{
+    button::GButtonWidgetRefExt,
+    checkbox::GCheckBoxWidgetRefExt,
+    input::GInputWidgetRefExt,
+    radio::GRadioWidgetRefExt,
+    select::GSelectWidgetRefExt,
+    toggle::GToggleWidgetRefExt,
+    view::{GView, GViewWidgetRefExt},
+};
 
 live_design! {
     GPopupContainerBase = {{GPopupContainer}} {
@@ -76,6 +87,32 @@ impl GPopupContainer {
     pub fn area(&self) -> Area {
         self.area
     }
+    /// ## Focus stops
+    /// the areas of the focusable widgets in the container (in draw order),
+    /// used to trap the Tab focus in a modal popup
+    pub fn focus_stops(&self) -> Vec<Area> {
+        fn collect(children: &[(LiveId, WidgetRef)], stops: &mut Vec<Area>) {
+            for (_, child) in children {
+                if !child.is_visible() {
+                    continue;
+                }
+                let focusable = child.as_ginput().borrow().is_some()
+                    || child.as_gbutton().borrow().is_some()
+                    || child.as_gcheck_box().borrow().is_some()
+                    || child.as_gradio().borrow().is_some()
+                    || child.as_gtoggle().borrow().is_some()
+                    || child.as_gselect().borrow().is_some();
+                if focusable {
+                    stops.push(child.area());
+                } else if let Some(view) = child.as_gview().borrow() {
+                    collect(&view.children, stops);
+                }
+            }
+        }
+        let mut stops = vec![];
+        collect(&self.children, &mut stops);
+        stops
+    }
     pub fn draw_item(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
         let _ = self.deref_widget.draw_walk(cx, scope, self.walk);
     }
@@ -153,6 +190,11 @@ pub struct GPopup {
     draw_list: DrawList2d,
     #[rust]
     pub container_walk: Option<Walk>,
+    /// draw above the other popups in the next `begin`
+    #[rust]
+    pub raised: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl LiveHook for GPopup {
@@ -160,6 +202,12 @@ impl LiveHook for GPopup {
         // if !self.visible {
         //     return;
         // }
+        self.render(cx);
+    }
+}
+
+impl GPopup {
+    pub fn render(&mut self, cx: &mut Cx) {
         // ----------------- background color -------------------------------------------
         let bg_color = self.background_color.get(self.theme, 500);
         let shadow_color = self.shadow_color.get(self.theme, 500);
@@ -191,9 +239,6 @@ impl LiveHook for GPopup {
         );
         // self.draw_popup.redraw(cx);
     }
-}
-
-impl GPopup {
     pub fn area(&self) -> Area {
         self.draw_popup.area()
     }
@@ -221,7 +266,13 @@ impl GPopup {
     /// ## Begin to draw popup
     /// this method is used to begin drawing the popup
     pub fn begin(&mut self, cx: &mut Cx2d) {
-        self.draw_list.begin_overlay_reuse(cx);
+        sync_theme!(self, cx);
+        if self.raised {
+            self.raised = false;
+            self.draw_list.begin_overlay_last(cx);
+        } else {
+            self.draw_list.begin_overlay_reuse(cx);
+        }
         cx.begin_pass_sized_turtle(Layout::flow_down());
         self.draw_popup.begin(cx, self.walk, self.layout);
     }
@@ -231,6 +282,12 @@ impl GPopup {
         cx.end_pass_sized_turtle_with_shift(shift_area, shift);
         self.draw_list.end(cx);
     }
+    /// ## Raise the popup
+    /// the overlay keeps its place when it is drawn again, call it when opening
+    /// so that the popups are stacked in open order
+    pub fn raise(&mut self) {
+        self.raised = true;
+    }
     pub fn redraw_container(&mut self, cx: &mut Cx) {
         self.draw_popup.redraw(cx);
         self.container.redraw(cx);
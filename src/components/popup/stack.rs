@@ -0,0 +1,134 @@
+use std::{cell::RefCell, rc::Rc};
+
+use makepad_widgets::*;
+
+/// An open popup in the stack
+#[derive(Debug, Clone, PartialEq)]
+pub struct PopupEntry {
+    /// the widget which owns the popup
+    pub uid: WidgetUid,
+    /// the sweep area of the owner, the finger events are locked to it while the popup is on top
+    pub owner: Area,
+    /// the areas of the popup (a context menu has one for each level), updated when drawing
+    pub areas: Vec<Area>,
+}
+
+/// # Popup Stack
+/// The open popups in open order, the last one is the topmost.
+/// A popup opened from inside another popup (a GSelect in a Dialog) is above its parent, so:
+/// - a click in a popup above is not "outside" for the popups below (`CloseMode::Out`)
+/// - Escape only closes the topmost popup
+#[derive(Debug, Clone, Default)]
+pub struct PopupStack {
+    pub entries: Vec<PopupEntry>,
+}
+
+impl PopupStack {
+    /// push the popup to the top, an open popup is moved to the top
+    pub fn push(&mut self, uid: WidgetUid, owner: Area) {
+        self.remove(uid);
+        self.entries.push(PopupEntry {
+            uid,
+            owner,
+            areas: vec![],
+        });
+    }
+    pub fn remove(&mut self, uid: WidgetUid) -> Option<PopupEntry> {
+        let index = self.entries.iter().position(|entry| entry.uid == uid)?;
+        Some(self.entries.remove(index))
+    }
+    pub fn set_areas(&mut self, uid: WidgetUid, areas: Vec<Area>) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.uid == uid) {
+            entry.areas = areas;
+        }
+    }
+    pub fn top(&self) -> Option<&PopupEntry> {
+        self.entries.last()
+    }
+    /// true if the popup is the topmost one or not in the stack
+    pub fn is_top(&self, uid: WidgetUid) -> bool {
+        self.top().map_or(true, |entry| entry.uid == uid)
+            || self.entries.iter().all(|entry| entry.uid != uid)
+    }
+    /// the areas of the popups opened after the popup
+    pub fn above(&self, uid: WidgetUid) -> Vec<Area> {
+        self.entries
+            .iter()
+            .skip_while(|entry| entry.uid != uid)
+            .skip(1)
+            .flat_map(|entry| entry.areas.iter().copied())
+            .collect()
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct PopupStackGlobal {
+    pub stack: Rc<RefCell<PopupStack>>,
+}
+
+impl PopupStackGlobal {
+    /// push the popup and lock the finger events to the owner
+    pub fn open(cx: &mut Cx, uid: WidgetUid, owner: Area) {
+        let global = cx.global::<PopupStackGlobal>().clone();
+        global.stack.borrow_mut().push(uid, owner);
+        cx.sweep_lock(owner);
+    }
+    /// remove the popup and give the lock back to the popup below
+    pub fn close(cx: &mut Cx, uid: WidgetUid, owner: Area) {
+        let global = cx.global::<PopupStackGlobal>().clone();
+        global.stack.borrow_mut().remove(uid);
+        cx.sweep_unlock(owner);
+        Self::lock_top(cx);
+    }
+    /// lock the finger events to the owner of the topmost popup
+    pub fn lock_top(cx: &mut Cx) {
+        let global = cx.global::<PopupStackGlobal>().clone();
+        let top = global.stack.borrow().top().map(|top| top.owner);
+        if let Some(owner) = top {
+            cx.sweep_lock(owner);
+        }
+    }
+    pub fn set_areas(cx: &mut Cx, uid: WidgetUid, areas: Vec<Area>) {
+        let global = cx.global::<PopupStackGlobal>().clone();
+        global.stack.borrow_mut().set_areas(uid, areas);
+    }
+    pub fn is_top(cx: &mut Cx, uid: WidgetUid) -> bool {
+        let global = cx.global::<PopupStackGlobal>().clone();
+        let is_top = global.stack.borrow().is_top(uid);
+        is_top
+    }
+    /// check whether the pos is in a popup opened after the popup (its child popups)
+    pub fn above_contains_pos(cx: &mut Cx, uid: WidgetUid, pos: DVec2) -> bool {
+        let global = cx.global::<PopupStackGlobal>().clone();
+        let above = global.stack.borrow().above(uid);
+        above
+            .iter()
+            .any(|area| area.clipped_rect(cx).contains(pos))
+    }
+}
+
+#[cfg(test)]
+mod test_popup_stack {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut stack = PopupStack::default();
+        let (dialog, select, menu) = (WidgetUid(1), WidgetUid(2), WidgetUid(3));
+        stack.push(dialog, Area::Empty);
+        stack.push(select, Area::Empty);
+        stack.set_areas(select, vec![Area::Empty]);
+        assert!(stack.is_top(select));
+        assert!(!stack.is_top(dialog));
+        // not in the stack, nothing above it
+        assert!(stack.is_top(menu));
+        assert_eq!(stack.above(dialog).len(), 1);
+        assert!(stack.above(select).is_empty());
+        // reopen the dialog, it becomes the top
+        stack.push(dialog, Area::Empty);
+        assert!(stack.is_top(dialog));
+        assert!(stack.remove(select).is_some());
+        assert!(stack.remove(select).is_none());
+        assert_eq!(stack.entries.len(), 1);
+    }
+}
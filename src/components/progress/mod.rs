@@ -11,6 +11,7 @@ use crate::{
     active_event, animatie_fn, default_handle_animation, event_option, play_animation, ref_area,
     ref_event_option, ref_redraw, ref_render, set_event, set_scope_path,
     shader::draw_progress::{DrawGProgress, GProgressType},
+    sync_theme,
     themes::Themes,
     utils::{round_to_two_decimals, set_cursor, BoolToF32, ThemeColor},
     widget_area, widget_origin_fn,
@@ -113,10 +114,13 @@ pub struct GProgress {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GProgress {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
@@ -17,6 +17,7 @@ use crate::{
         draw_text::DrawGText,
         draw_view::DrawGView,
     },
+    sync_theme,
     themes::Themes,
     utils::{get_font_family, set_cursor, BoolToF32, ThemeColor},
     widget_area, widget_origin_fn,
@@ -188,10 +189,13 @@ pub struct GRadio {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GRadio {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
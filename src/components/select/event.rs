@@ -1,5 +1,7 @@
 use makepad_widgets::{ActionDefaultRef, DefaultNone, FingerUpEvent};
 
+use super::types::SelectOption;
+
 #[derive(Debug, Clone, DefaultNone)]
 pub enum GSelectItemEvent {
     Clicked(GSelectItemClickedParam),
@@ -33,5 +35,49 @@ pub struct GSelectOptionsChangedParam {
 #[derive(Debug, Clone, DefaultNone)]
 pub enum GSelectEvent {
     Changed(GSelectOptionsChangedParam),
+    /// an option is selected by keyboard (arrow keys and `Enter`), the same as `Changed` without the finger event
+    KeyChanged(GSelectKeyChangedParam),
+    /// selected options changed in `multiple` mode (option toggled, tag closed or cleared)
+    MultiChanged(GSelectMultiChangedParam),
+    /// a new option is created from the search text (`creatable` mode), it has been added and selected
+    Created(GSelectCreatedParam),
+    /// options should be loaded (`remote` mode), answer with `GSelectRef::set_options`
+    Load(GSelectLoadParam),
     None,
 }
+
+#[derive(Clone, Debug)]
+pub struct GSelectKeyChangedParam {
+    pub selected: bool,
+    pub text: String,
+    pub value: String,
+    pub selected_id: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct GSelectMultiChangedParam {
+    /// selected options in selection order
+    pub selected: Vec<SelectOption>,
+    /// indexes of the selected options
+    pub selected_ids: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GSelectCreatedParam {
+    /// the text (and value) of the new option
+    pub text: String,
+    /// index of the new option
+    pub selected_id: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct GSelectLoadParam {
+    /// id of the request, pass it back to `set_options`
+    pub request_id: u64,
+    /// the search text (empty if not searchable)
+    pub query: String,
+    /// offset of the page, 0 is the first page of the query
+    pub cursor: usize,
+    /// expected count of options, a shorter page is the last page (0 means no paging)
+    pub page_size: usize,
+}
@@ -1,12 +1,19 @@
+use std::ops::Range;
+
 use makepad_widgets::*;
 
 use crate::{
-    shader::{draw_view::DrawGView, draw_text::DrawGText},
+    components::icon::GIconWidgetRefExt,
+    shader::{draw_text::DrawGText, draw_view::DrawGView, icon_lib::types::IconType},
+    sync_theme,
     themes::Themes,
     utils::{get_font_family, BoolToF32, ThemeColor},
 };
 
-use super::{GSelectItemClickedParam, GSelectItemEvent};
+use super::{
+    types::{split_highlight, SelectOption},
+    GSelectItemClickedParam, GSelectItemEvent,
+};
 
 live_design! {
     import makepad_draw::shader::std::*;
@@ -113,6 +120,9 @@ pub struct GSelectItem {
     pub stroke_hover_color: Option<Vec4>,
     #[live]
     pub color: Option<Vec4>,
+    /// color of the matched text when searching
+    #[live]
+    pub highlight_color: Option<Vec4>,
     #[live]
     pub background_color: Option<Vec4>,
     #[live(true)]
@@ -141,6 +151,23 @@ pub struct GSelectItem {
     pub draw_item: DrawGView,
     #[live]
     pub draw_text: DrawGText,
+    /// secondary text of the option
+    #[live]
+    pub draw_description: DrawGText,
+    #[live]
+    pub description_color: Option<Vec4>,
+    #[live(8.0)]
+    pub description_size: f64,
+    /// leading icon template (GIcon), the icon type is set by the option
+    #[live]
+    pub icon: Option<LivePtr>,
+    #[rust]
+    icon_ref: Option<WidgetRef>,
+    /// opacity of a disabled option
+    #[live(0.4)]
+    pub disabled_opacity: f32,
+    #[rust]
+    pub disabled: bool,
     #[layout]
     pub layout: Layout,
     #[walk]
@@ -159,10 +186,18 @@ pub struct GSelectItem {
     pub font_family: LiveDependency,
     #[live(true)]
     pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl LiveHook for GSelectItem {
     fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        self.render(cx);
+    }
+}
+
+impl GSelectItem {
+    pub fn render(&mut self, cx: &mut Cx) {
         // ----------------- background color -------------------------------------------
         let bg_color = self.background_color.use_or("#FFFFFF00");
         // ------------------ hover color -----------------------------------------------
@@ -174,6 +209,18 @@ impl LiveHook for GSelectItem {
         let shadow_color = self.shadow_color.get(self.theme, 700);
         let background_visible = self.background_visible.to_f32();
         let color = self.color.use_or("#101828");
+        let description_color = self.description_color.use_or("#667085");
+        self.draw_description.apply_over(
+            cx,
+            live! {
+                color: (description_color),
+                stroke_hover_color: (description_color),
+                stroke_focus_color: (description_color),
+                text_style: {
+                    font_size: (self.description_size),
+                }
+            },
+        );
         let stroke_color = self.stroke_color.get(self.theme, 600);
         // let stroke_hover_color = self.stroke_hover_color.get(self.theme, 600);
         self.draw_item.apply_over(
@@ -209,24 +256,101 @@ impl LiveHook for GSelectItem {
         self.draw_text.redraw(cx);
         self.draw_item.redraw(cx);
     }
-}
-
-impl GSelectItem {
     pub fn area(&self) -> Area {
         self.draw_item.area()
     }
-    pub fn draw_item(&mut self, cx: &mut Cx2d, text: &str, value: &str, theme: Themes) {
+    pub fn draw_item(
+        &mut self,
+        cx: &mut Cx2d,
+        option: &SelectOption,
+        theme: Themes,
+        highlights: &[Range<usize>],
+    ) {
+        sync_theme!(self, cx);
+        let text = option.text.as_str();
         self.theme = theme;
+        self.disabled = option.disabled;
         let _ = self.draw_item.begin(cx, self.walk, self.layout);
         let font = get_font_family(&self.font_family, cx);
-        self.draw_text.text_style.font = font;
-        let _ = self
-            .draw_text
-            .draw_walk(cx, Walk::fit(), Align::default(), text);
-        self.value = value.to_string();
+        self.draw_text.text_style.font = font.clone();
+        self.draw_description.text_style.font = font;
+        if let Some(icon_type) = option.icon.as_ref() {
+            self.draw_icon(cx, icon_type);
+        }
+        // dim the text of disabled option
+        let color = self.draw_text.color;
+        if self.disabled {
+            self.draw_text.color.w *= self.disabled_opacity;
+        }
+        if highlights.is_empty() {
+            let _ = self
+                .draw_text
+                .draw_walk(cx, Walk::fit(), Align::default(), text);
+        } else {
+            // draw the text segment by segment, the matched segments use highlight color,
+            // a disabled option keeps the dimmed color for all segments
+            let color = self.draw_text.color;
+            let hover_color = self.draw_text.stroke_hover_color;
+            let focus_color = self.draw_text.stroke_focus_color;
+            let highlight_color = if self.disabled {
+                color
+            } else {
+                self.highlight_color.use_or("#06AED4")
+            };
+            for (segment, highlight) in split_highlight(text, highlights) {
+                let (color, hover_color, focus_color) = if highlight {
+                    (highlight_color, highlight_color, highlight_color)
+                } else {
+                    (color, hover_color, focus_color)
+                };
+                self.draw_text.color = color;
+                self.draw_text.stroke_hover_color = hover_color;
+                self.draw_text.stroke_focus_color = focus_color;
+                let _ = self
+                    .draw_text
+                    .draw_walk(cx, Walk::fit(), Align::default(), segment);
+            }
+            self.draw_text.stroke_hover_color = hover_color;
+            self.draw_text.stroke_focus_color = focus_color;
+        }
+        self.draw_text.color = color;
+        if let Some(description) = option.description.as_ref() {
+            let _ = self.draw_description.draw_walk(
+                cx,
+                Walk {
+                    margin: Margin {
+                        left: 8.0,
+                        ..Default::default()
+                    },
+                    ..Walk::fit()
+                },
+                Align::default(),
+                description,
+            );
+        }
+        self.value = option.value.to_string();
         self.text = text.to_string();
         let _ = self.draw_item.end(cx);
     }
+    /// draw the leading icon, the icon is rendered again only when the type changes
+    fn draw_icon(&mut self, cx: &mut Cx2d, icon_type: &IconType) {
+        let icon_ptr = self.icon;
+        let icon = self
+            .icon_ref
+            .get_or_insert_with(|| WidgetRef::new_from_ptr(cx, icon_ptr))
+            .clone();
+        if let Some(mut icon) = icon.as_gicon().borrow_mut() {
+            if icon.icon_type != *icon_type {
+                if let Ok(draw_type) = icon_type.to_draw_type() {
+                    icon.icon_type = icon_type.clone();
+                    icon.draw_type.replace(draw_type);
+                    icon.render(cx);
+                }
+            }
+        }
+        let icon_walk = icon.walk(cx);
+        let _ = icon.draw_walk(cx, &mut Scope::empty(), icon_walk);
+    }
     pub fn handle_event_with(
         &mut self,
         cx: &mut Cx,
@@ -237,6 +361,9 @@ impl GSelectItem {
         if self.animator_handle_event(cx, event).must_redraw() {
             self.draw_item.area().redraw(cx);
         }
+        if self.disabled {
+            return;
+        }
         match event.hits_with_options(
             cx,
             self.area(),
@@ -11,16 +11,35 @@ use options::GSelectOptions;
 pub use register::register;
 
 use makepad_widgets::*;
-use types::SelectOption;
+use types::{
+    filter_options, group_options, match_text, merge_page, option_rows, type_ahead,
+    LiveSelectOption, SelectOption, SelectRequest, SelectRow,
+};
 
 use crate::{
-    shader::{draw_view::DrawGView, draw_text::DrawGText},
-    themes::Themes,
+    shader::{
+        draw_icon_pixel::DrawGIconPixel, draw_text::DrawGText, draw_view::DrawGView,
+        manual::ComponentMode,
+    },
+    sync_theme,
+    themes::{scale_layout, scale_walk, size_scale, GSize, Themes},
     utils::{get_font_family, set_cursor, BoolToF32, ThemeColor},
-    widget_area,
+    event_option, ref_event_option, widget_area,
+};
+
+use super::{
+    input::{GInputRef, GInputWidgetRefExt},
+    popup::PopupStackGlobal,
+    tag::{GTagRef, GTagWidgetRefExt},
 };
 
+/// item id of the empty state in options
+const EMPTY_ITEM: usize = usize::MAX - 1;
+/// item id of the create entry in options
+const CREATE_ITEM: usize = usize::MAX - 2;
+
 live_design! {
+    import makepad_draw::shader::std::*;
     GSelectBase = {{GSelect}}{
         height: 36.0,
         width: 180.0,
@@ -39,6 +58,19 @@ live_design! {
                 return self.color;
             }
         }
+        draw_clear: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let c = self.rect_size * 0.5;
+                let r = c.x * 0.4;
+                sdf.move_to(c.x - r, c.y - r);
+                sdf.line_to(c.x + r, c.y + r);
+                sdf.move_to(c.x + r, c.y - r);
+                sdf.line_to(c.x - r, c.y + r);
+                sdf.stroke(self.color, 1.2);
+                return sdf.result;
+            }
+        }
         animator: {
             hover = {
                 default: off,
@@ -85,6 +117,9 @@ live_design! {
 pub struct GSelect {
     #[live]
     pub theme: Themes,
+    /// the size preset of the trigger
+    #[live]
+    pub size: GSize,
     #[live]
     pub color: Option<Vec4>,
     #[live]
@@ -130,8 +165,18 @@ pub struct GSelect {
     pub opened: bool,
     #[live(6.0)]
     pub offset: f32,
+    /// the options, change them with `options_mut` so the rows are rebuilt
+    #[rust]
+    options: Vec<SelectOption>,
+    /// bumped by `options_mut`
     #[rust]
-    pub options: Vec<SelectOption>,
+    options_version: u64,
+    /// `Virtual` only draws the visible options and reuses the item widgets, use it for long lists
+    #[live]
+    pub mode: ComponentMode,
+    /// options declared in live design, replace `options` when not empty
+    #[live]
+    pub option_list: Vec<LiveSelectOption>,
     #[walk]
     pub walk: Walk,
     #[layout]
@@ -140,10 +185,91 @@ pub struct GSelect {
     pub font_family: LiveDependency,
     #[live]
     pub selected: usize,
+    // multiple -------------------
+    /// select several options, the selected options are shown as tags
+    #[live]
+    pub multiple: bool,
+    /// indexes of the selected options in `multiple` mode
+    #[live]
+    pub selected_ids: Vec<usize>,
+    /// tag template of `multiple` mode
+    #[live]
+    pub select_tag: Option<LivePtr>,
+    /// count of tags shown in the trigger, the rest collapse to a `+N more` tag
+    #[live(2)]
+    pub max_tags: usize,
+    /// show a clear all icon when there are selected options (`multiple` mode)
+    #[live(true)]
+    pub clearable: bool,
+    #[live(14.0)]
+    pub clear_size: f64,
+    #[live]
+    pub clear_color: Option<Vec4>,
+    #[live]
+    pub draw_clear: DrawGIconPixel,
+    #[rust]
+    tags: ComponentMap<LiveId, GTagRef>,
+    // search ---------------------
+    /// show a search input in the trigger when opened, the options are filtered by the input text
+    #[live]
+    pub searchable: bool,
+    /// add a create entry when no option has the same text as the search text
+    #[live]
+    pub creatable: bool,
+    /// search input template
+    #[live]
+    pub search_input: Option<LivePtr>,
+    /// text of the empty state when no option matches
+    #[live]
+    pub empty_text: String,
+    /// prefix of the create entry, the entry is `{create_text} "{query}"`
+    #[live]
+    pub create_text: String,
+    #[rust]
+    pub query: String,
+    /// the rows of the matched options and what they are built from (options version and query)
+    #[rust]
+    rows: Option<(u64, String, Vec<SelectRow>)>,
+    /// bumped when the rows are rebuilt, the options menu keeps its layout until then
+    #[rust]
+    rows_version: u64,
+    #[rust]
+    search: Option<GInputRef>,
+    /// set key focus to the search input at next draw
+    #[rust]
+    focus_search: bool,
+    // keyboard -------------------
+    /// the item highlighted by keyboard (option index or the create entry)
+    #[rust]
+    pub highlighted: Option<usize>,
+    /// typed chars of type-ahead, reset after 1s without typing
+    #[rust]
+    type_ahead: String,
+    #[rust]
+    type_ahead_time: f64,
+    #[rust]
+    key_time: f64,
+    // remote ---------------------
+    /// options are loaded by the app, `GSelectEvent::Load` is dispatched when opened,
+    /// the search text changed or scrolled near the bottom
+    #[live]
+    pub remote: bool,
+    /// count of options of each page, 0 loads all options in one request
+    #[live(20)]
+    pub page_size: usize,
+    /// load the next page when the content below the visible area is shorter than this
+    #[live(24.0)]
+    pub load_threshold: f64,
+    #[rust]
+    request: SelectRequest,
+    #[rust]
+    next_frame: NextFrame,
     #[animator]
     animator: Animator,
     #[live(true)]
     pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 #[derive(Default, Clone)]
@@ -153,12 +279,20 @@ pub struct SelectOptionsGlobal {
 
 impl Widget for GSelect {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
-        let _ = self.draw_select.begin(cx, walk, self.layout);
+        let scale = size_scale(self.size);
+        let _ = self
+            .draw_select
+            .begin(cx, scale_walk(walk, scale), scale_layout(self.layout, scale));
 
-        if (self.options.len() >= self.selected) && self.options.len() != 0 {
+        if self.searchable && self.opened {
+            self.draw_search(cx, scope);
+        } else if self.multiple {
+            self.draw_tags(cx, scope);
+        } else if (self.options.len() >= self.selected) && self.options.len() != 0 {
             let font = get_font_family(&self.font_family, cx);
             self.draw_text.text_style.font = font;
             let text = self.options[self.selected].text.to_string();
@@ -166,6 +300,7 @@ impl Widget for GSelect {
                 .draw_walk(cx, Walk::fit(), Align { x: 0.0, y: 0.5 }, &text);
         }
         self.draw_select.end(cx);
+        self.draw_clear_icon(cx);
 
         cx.add_nav_stop(self.area(), NavRole::DropDown, Margin::default());
 
@@ -177,12 +312,49 @@ impl Widget for GSelect {
             options_menu.begin(cx, self.theme);
             // set item live ptr and draw
             options_menu.item = self.select_item.clone();
+            options_menu.multiple = self.multiple;
+            options_menu.mode = self.mode;
 
-            for (index, option) in self.options.iter().enumerate() {
-                options_menu.draw_option(cx, LiveId(index as u64), &option.text, &option.value);
+            let loading = self.remote && (self.request.pending || self.request.loading);
+            self.update_rows();
+            let rows_key = (self.widget_uid(), self.rows_version);
+            let rows = self.rows.as_ref().map_or(&[][..], |(_, _, rows)| rows.as_slice());
+            let is_selected = |index: usize| {
+                if self.multiple {
+                    self.selected_ids.contains(&index)
+                } else {
+                    index == self.selected
+                }
+            };
+            options_menu.draw_rows(cx, rows_key, rows, &self.options, &is_selected);
+            if self.can_create() {
+                let text = format!("{} \"{}\"", self.create_text, self.query.trim());
+                options_menu.draw_option(
+                    cx,
+                    LiveId(CREATE_ITEM as u64),
+                    &SelectOption::new(&text, ""),
+                    false,
+                    &[],
+                );
+            } else if loading {
+                options_menu.draw_loading(cx);
+            } else if rows.is_empty() {
+                options_menu.draw_option(
+                    cx,
+                    LiveId(EMPTY_ITEM as u64),
+                    &SelectOption::new(&self.empty_text, "").with_disabled(true),
+                    false,
+                    &[],
+                );
             }
-            
+
             let _ = options_menu.end_container(cx);
+            if self.remote
+                && options_menu.rest_height < self.load_threshold
+                && self.request.more()
+            {
+                self.next_frame = cx.new_next_frame();
+            }
             let area = self.area().rect(cx);
             let container_size = options_menu.area().rect(cx).size;
 
@@ -191,37 +363,62 @@ impl Widget for GSelect {
                 y: area.size.y + self.offset as f64,
             };
             options_menu.end(cx, scope, self.area(), shift);
+            PopupStackGlobal::set_areas(cx, self.widget_uid(), vec![options_menu.area()]);
         }
         DrawStep::done()
     }
 
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.animator_handle_event(cx, event);
-        let uid = self.widget_uid();
+        if self.next_frame.is_event(event).is_some() {
+            self.start_load(cx, &scope.path);
+        }
         if self.opened && self.select_options.is_some() {
             let global = cx.global::<SelectOptionsGlobal>().clone();
             let mut map = global.map.borrow_mut();
             let menu = map.get_mut(&self.select_options.unwrap()).unwrap();
-            let mut close = false;
-
-            menu.handle_event_with(cx, event, self.area(), &mut |cx, action| match action {
-                GSelectOptionsEvent::Changed(e) => {
-                    self.selected = e.selected_id;
-                    cx.widget_action(uid, &scope.path, GSelectEvent::Changed(e));
-                    self.draw_select.redraw(cx);
-                    close = true;
+            let mut changed = vec![];
+            // a click in a popup opened from the options is not outside
+            let in_above = match event {
+                Event::MouseDown(e) => {
+                    PopupStackGlobal::above_contains_pos(cx, self.widget_uid(), e.abs)
                 }
+                _ => false,
+            };
+
+            menu.handle_event_with(cx, event, self.area(), &mut |_, action| match action {
+                GSelectOptionsEvent::Changed(e) => changed.push(e),
                 _ => (),
             });
-            if close {
-                self.close(cx);
-            }
             if let Event::MouseDown(e) = event {
-                if !menu.menu_contains_pos(cx, e.abs) {
+                // the search input is in the trigger, click it should keep the options open
+                let in_search = self.searchable && self.area().rect(cx).contains(e.abs);
+                if !menu.menu_contains_pos(cx, e.abs) && !in_search && !in_above {
                     self.close(cx);
                     // self.animator_play(cx, id!(hover.off));
                 }
             }
+            drop(map);
+            for e in changed {
+                self.option_changed(cx, &scope.path, GSelectEvent::Changed(e));
+            }
+            if self.searchable {
+                // the search input has key focus, navigation keys are taken before it
+                if let Event::KeyDown(e) = event {
+                    if matches!(
+                        e.key_code,
+                        KeyCode::ArrowUp | KeyCode::ArrowDown | KeyCode::Escape
+                    ) {
+                        self.handle_key(cx, e, &scope.path);
+                    }
+                }
+                self.handle_search(cx, event, scope);
+            }
+        }
+
+        // a tag is closed, the trigger should not open
+        if self.multiple && self.handle_tags(cx, event, scope) {
+            return;
         }
 
         match event.hits_with_sweep_area(cx, self.area(), self.area()) {
@@ -229,6 +426,28 @@ impl Widget for GSelect {
                 set_cursor(cx, self.cursor.as_ref());
                 self.animator_play(cx, id!(hover.on));
             }
+            Hit::FingerDown(_) => {
+                cx.set_key_focus(self.area());
+            }
+            Hit::KeyDown(e) => {
+                if self.opened {
+                    self.handle_key(cx, &e, &scope.path);
+                } else if matches!(
+                    e.key_code,
+                    KeyCode::Space | KeyCode::ReturnKey | KeyCode::ArrowDown
+                ) {
+                    self.open(cx);
+                }
+            }
+            Hit::TextInput(e) => {
+                if self.opened && !self.searchable {
+                    self.handle_type_ahead(cx, &e.input);
+                }
+            }
+            Hit::FingerUp(f) if f.is_over && self.area_clear().rect(cx).contains(f.abs) => {
+                self.clear(cx);
+                self.active_multi_changed(cx, &scope.path);
+            }
             Hit::FingerUp(f) => {
                 if f.is_over && f.device.has_hovers() {
                     set_cursor(cx, self.cursor.as_ref());
@@ -245,7 +464,7 @@ impl Widget for GSelect {
 }
 
 impl LiveHook for GSelect {
-    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
         if !self.visible {
             return;
         }
@@ -254,6 +473,15 @@ impl LiveHook for GSelect {
         map.retain(|k, _| cx.live_registry.borrow().generation_valid(*k));
         let menu = self.select_options.unwrap();
         map.get_or_insert(cx, menu, |cx| GSelectOptions::new_from_ptr(cx, Some(menu)));
+        if apply.from.is_from_doc() && !self.option_list.is_empty() {
+            *self.options_mut() = self.option_list.iter().map(SelectOption::from).collect();
+        }
+        self.render(cx);
+    }
+}
+
+impl GSelect {
+    pub fn render(&mut self, cx: &mut Cx) {
         // ----------------- background color -------------------------------------------
         let bg_color = self.background_color.use_or("#ffffff");
         // ------------------ hover color -----------------------------------------------
@@ -265,6 +493,17 @@ impl LiveHook for GSelect {
         let shadow_color = self.shadow_color.get(self.theme, 700);
         let background_visible = self.background_visible.to_f32();
         let color = self.color.use_or("#ADBAC7");
+        let clear_color = self.clear_color.use_or("#98A2B3");
+        // ------------------ size (see `GSize`) -----------------------------------------
+        let scale = size_scale(self.size);
+        let border_radius = self.border_radius * scale as f32;
+        let font_size = self.font_size * scale;
+        self.draw_clear.apply_over(
+            cx,
+            live! {
+                color: (clear_color),
+            },
+        );
         self.draw_select.apply_over(
             cx,
             live! {
@@ -272,7 +511,7 @@ impl LiveHook for GSelect {
                 background_visible: (background_visible),
                 border_color: (border_color),
                 border_width: (self.border_width),
-                border_radius: (self.border_radius),
+                border_radius: (border_radius),
                 focus_color: (focus_color),
                 hover_color: (hover_color),
                 shadow_color: (shadow_color),
@@ -286,35 +525,537 @@ impl LiveHook for GSelect {
             live! {
                 color: (color),
                 text_style: {
-                    font_size: (self.font_size),
+                    font_size: (font_size),
                 }
             },
         );
         self.draw_text.redraw(cx);
         self.draw_select.redraw(cx);
     }
-}
-
-impl GSelect {
     widget_area! {
         area, draw_select
     }
+    event_option! {
+        changed: GSelectEvent::Changed => GSelectOptionsChangedParam,
+        key_changed: GSelectEvent::KeyChanged => GSelectKeyChangedParam,
+        multi_changed: GSelectEvent::MultiChanged => GSelectMultiChangedParam,
+        created: GSelectEvent::Created => GSelectCreatedParam,
+        load: GSelectEvent::Load => GSelectLoadParam
+    }
+    /// options matched by the search text (all options if not searchable), in display order.
+    /// In `remote` mode the options are filtered by the app, the text only decides the highlights
+    pub fn matched_options(&self) -> Vec<(usize, Vec<std::ops::Range<usize>>)> {
+        let matched = if self.remote {
+            self.options
+                .iter()
+                .enumerate()
+                .map(|(index, option)| {
+                    let ranges = match_text(&option.text, &self.query).unwrap_or_default();
+                    (index, ranges)
+                })
+                .collect()
+        } else if self.searchable {
+            filter_options(&self.options, &self.query)
+        } else {
+            (0..self.options.len()).map(|index| (index, vec![])).collect()
+        };
+        group_options(&self.options, matched)
+    }
+    /// rebuild the rows if the options or the query changed
+    fn update_rows(&mut self) {
+        let fresh = matches!(
+            &self.rows,
+            Some((version, query, _)) if *version == self.options_version && *query == self.query
+        );
+        if !fresh {
+            let rows = option_rows(&self.options, self.matched_options());
+            self.rows = Some((self.options_version, self.query.clone(), rows));
+            self.rows_version += 1;
+        }
+    }
+    /// the create entry is shown when the search text is not empty and no option has the same text
+    fn can_create(&self) -> bool {
+        let query = self.query.trim();
+        self.creatable
+            && self.searchable
+            && !query.is_empty()
+            && !self
+                .options
+                .iter()
+                .any(|option| option.text.to_lowercase() == query.to_lowercase())
+    }
+    /// draw the search input in the trigger
+    fn draw_search(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        let search_ptr = self.search_input;
+        let search = self
+            .search
+            .get_or_insert_with(|| WidgetRef::new_from_ptr(cx, search_ptr).as_ginput())
+            .clone();
+        let search_walk = search.walk(cx);
+        let _ = search.draw_walk(cx, scope, search_walk);
+        if self.focus_search {
+            self.focus_search = false;
+            search.set_text("");
+            cx.set_key_focus(search.area());
+        }
+    }
+    /// handle the search input, return selects the first matched option (or creates a new one)
+    fn handle_search(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let search = match self.search.clone() {
+            Some(search) => search,
+            None => return,
+        };
+        let actions = cx.capture_actions(|cx| search.handle_event(cx, event, scope));
+        if let Some(text) = search.changed(&actions) {
+            self.query = text;
+            self.highlighted = None;
+            if self.remote {
+                self.reload(cx);
+            }
+            self.redraw_options(cx);
+        }
+        if search.returned(&actions).is_some() {
+            let first = self.matched_options().first().map(|(index, _)| *index);
+            if let Some(item) = self.highlighted.or(first) {
+                self.select_item(cx, &scope.path, item);
+            } else if self.can_create() {
+                self.create_option(cx, &scope.path);
+            }
+        }
+    }
+    /// the items which can be highlighted by keyboard, in display order
+    fn nav_items(&self) -> Vec<usize> {
+        let mut items: Vec<usize> = self
+            .matched_options()
+            .into_iter()
+            .map(|(index, _)| index)
+            .filter(|index| !self.options[*index].disabled)
+            .collect();
+        if self.can_create() {
+            items.push(CREATE_ITEM);
+        }
+        items
+    }
+    /// ## Handle key
+    /// ArrowUp/ArrowDown/Home/End move the highlight, Enter selects the highlighted item, Escape closes
+    fn handle_key(&mut self, cx: &mut Cx, e: &KeyEvent, path: &HeapLiveIdPath) {
+        self.key_time = e.time;
+        let items = self.nav_items();
+        let current = self
+            .highlighted
+            .and_then(|item| items.iter().position(|i| *i == item));
+        let last = items.len().saturating_sub(1);
+        let target = match e.key_code {
+            KeyCode::ArrowDown => current.map_or(0, |position| (position + 1).min(last)),
+            KeyCode::ArrowUp => current.map_or(last, |position| position.saturating_sub(1)),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            KeyCode::ReturnKey => {
+                if let Some(item) = self.highlighted {
+                    self.select_item(cx, path, item);
+                }
+                return;
+            }
+            // only the topmost popup is closed
+            KeyCode::Escape if PopupStackGlobal::is_top(cx, self.widget_uid()) => {
+                self.close(cx);
+                cx.set_key_focus(self.area());
+                return;
+            }
+            _ => return,
+        };
+        if let Some(item) = items.get(target) {
+            self.highlight(cx, Some(*item));
+        }
+    }
+    /// jump to the option which starts with the typed chars
+    fn handle_type_ahead(&mut self, cx: &mut Cx, input: &str) {
+        if self.key_time - self.type_ahead_time > 1.0 {
+            self.type_ahead.clear();
+        }
+        self.type_ahead_time = self.key_time;
+        self.type_ahead.push_str(input);
+        let items = self.nav_items();
+        if let Some(item) = type_ahead(&self.options, &items, &self.type_ahead, self.highlighted) {
+            self.highlight(cx, Some(item));
+        }
+    }
+    /// highlight an item and scroll it into view
+    fn highlight(&mut self, cx: &mut Cx, item: Option<usize>) {
+        self.highlighted = item;
+        if let Some(ptr) = self.select_options {
+            let global = cx.global::<SelectOptionsGlobal>().clone();
+            let mut map = global.map.borrow_mut();
+            if let Some(menu) = map.get_mut(&ptr) {
+                let item_id = item.map(|item| LiveId(item as u64));
+                menu.highlight(cx, item_id);
+                if let Some(item_id) = item_id {
+                    menu.scroll_into_view(cx, item_id);
+                }
+                menu.redraw(cx);
+            }
+        }
+    }
+    /// select an item by keyboard, the same as clicking it
+    fn select_item(&mut self, cx: &mut Cx, path: &HeapLiveIdPath, item: usize) {
+        let option = match self.options.get(item) {
+            Some(option) if option.disabled => return,
+            Some(option) => option.clone(),
+            None => {
+                if item == CREATE_ITEM {
+                    self.create_option(cx, path);
+                }
+                return;
+            }
+        };
+        let selected = !self.multiple || !self.selected_ids.contains(&item);
+        self.option_changed(
+            cx,
+            path,
+            GSelectEvent::KeyChanged(GSelectKeyChangedParam {
+                selected,
+                text: option.text,
+                value: option.value,
+                selected_id: item,
+            }),
+        );
+        if !self.opened {
+            cx.set_key_focus(self.area());
+        }
+    }
+    /// ## Option changed
+    /// an option is clicked (`Changed`) or selected by keyboard (`KeyChanged`), the empty state is ignored
+    fn option_changed(&mut self, cx: &mut Cx, path: &HeapLiveIdPath, e: GSelectEvent) {
+        let (selected_id, selected) = match &e {
+            GSelectEvent::Changed(e) => (e.selected_id, e.selected),
+            GSelectEvent::KeyChanged(e) => (e.selected_id, e.selected),
+            _ => return,
+        };
+        match selected_id {
+            EMPTY_ITEM => {}
+            CREATE_ITEM => self.create_option(cx, path),
+            id if self.options.get(id).map_or(true, |option| option.disabled) => {}
+            id if self.multiple => {
+                // keep the popup open, each option is toggled
+                self.selected_ids.retain(|selected| *selected != id);
+                if selected {
+                    self.selected_ids.push(id);
+                }
+                self.redraw(cx);
+                self.active_multi_changed(cx, path);
+            }
+            id => {
+                self.selected = id;
+                if self.event_key {
+                    cx.widget_action(self.widget_uid(), path, e);
+                }
+                self.draw_select.redraw(cx);
+                self.close(cx);
+            }
+        }
+    }
+    /// create a new option from the search text and select it
+    fn create_option(&mut self, cx: &mut Cx, path: &HeapLiveIdPath) {
+        let text = self.query.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        self.options_mut().push(SelectOption::new(&text, &text));
+        let id = self.options.len() - 1;
+        if self.event_key {
+            cx.widget_action(
+                self.widget_uid(),
+                path,
+                GSelectEvent::Created(GSelectCreatedParam {
+                    text,
+                    selected_id: id,
+                }),
+            );
+        }
+        if self.multiple {
+            self.selected_ids.push(id);
+            self.query.clear();
+            if let Some(search) = self.search.as_ref() {
+                search.set_text("");
+            }
+            self.redraw(cx);
+            self.redraw_options(cx);
+            self.active_multi_changed(cx, path);
+        } else {
+            self.selected = id;
+            self.close(cx);
+        }
+    }
+    /// load options from the first page at next frame (`remote` mode)
+    fn reload(&mut self, cx: &mut Cx) {
+        self.request.reset();
+        self.next_frame = cx.new_next_frame();
+    }
+    /// dispatch the pending load request
+    fn start_load(&mut self, cx: &mut Cx, path: &HeapLiveIdPath) {
+        if !self.remote || !self.opened {
+            return;
+        }
+        if let Some((request_id, cursor)) = self.request.start() {
+            if self.event_key {
+                cx.widget_action(
+                    self.widget_uid(),
+                    path,
+                    GSelectEvent::Load(GSelectLoadParam {
+                        request_id,
+                        query: self.query.trim().to_string(),
+                        cursor,
+                        page_size: self.page_size,
+                    }),
+                );
+            }
+            self.redraw_options(cx);
+        }
+    }
+    /// ## Set options
+    /// answer a `GSelectEvent::Load` request, the response of an outdated request is ignored.
+    /// The first page replaces the options (the selected options are kept), other pages are appended.
+    pub fn set_options(&mut self, cx: &mut Cx, request_id: u64, options: Vec<SelectOption>) {
+        let first = match self
+            .request
+            .accept(request_id, options.len(), self.page_size)
+        {
+            Some(first) => first,
+            None => return,
+        };
+        self.options_version += 1;
+        if self.multiple {
+            self.selected_ids = merge_page(&mut self.options, options, first, &self.selected_ids);
+        } else {
+            let selected: Vec<usize> = (self.selected < self.options.len())
+                .then_some(self.selected)
+                .into_iter()
+                .collect();
+            let selected = merge_page(&mut self.options, options, first, &selected);
+            self.selected = selected.first().copied().unwrap_or(0);
+        }
+        self.redraw(cx);
+        self.redraw_options(cx);
+    }
+    /// waiting for the options of `remote` mode
+    pub fn is_loading(&self) -> bool {
+        self.request.pending || self.request.loading
+    }
+    fn redraw_options(&mut self, cx: &mut Cx) {
+        if let Some(ptr) = self.select_options {
+            let global = cx.global::<SelectOptionsGlobal>().clone();
+            let mut map = global.map.borrow_mut();
+            if let Some(menu) = map.get_mut(&ptr) {
+                menu.redraw(cx);
+            }
+        }
+    }
+    /// the clear icon area, empty if it is not drawn
+    pub fn area_clear(&self) -> Area {
+        if self.multiple && self.clearable && !self.selected_ids.is_empty() {
+            self.draw_clear.area()
+        } else {
+            Area::Empty
+        }
+    }
+    /// ## Draw tags
+    /// draw the selected options as closeable tags, tags after `max_tags` collapse to a `+N more` tag
+    fn draw_tags(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        let options = &self.options;
+        self.selected_ids.retain(|id| *id < options.len());
+        let count = self.selected_ids.len();
+        let shown = count.min(self.max_tags);
+        let more = count - shown;
+        let right = if self.clearable { self.clear_size + 4.0 } else { 0.0 };
+        cx.begin_turtle(
+            Walk::fill(),
+            Layout {
+                flow: Flow::Right,
+                spacing: 4.0,
+                align: Align { x: 0.0, y: 0.5 },
+                padding: Padding {
+                    right,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let texts = self.selected_ids[..shown]
+            .iter()
+            .map(|id| (self.options[*id].text.to_string(), true))
+            .chain((more > 0).then(|| (format!("+{} more", more), false)));
+        for (slot, (text, closeable)) in texts.enumerate() {
+            let tag_ptr = self.select_tag;
+            let tag = self
+                .tags
+                .get_or_insert(cx, LiveId(slot as u64), |cx| {
+                    WidgetRef::new_from_ptr(cx, tag_ptr).as_gtag()
+                })
+                .clone();
+            if let Some(mut tag) = tag.borrow_mut() {
+                tag.closeable = closeable;
+                tag.text.as_mut_empty().push_str(&text);
+            }
+            let tag_walk = tag.walk(cx);
+            let _ = tag.draw_walk(cx, scope, tag_walk);
+        }
+        cx.end_turtle();
+    }
+    /// draw the clear icon at the right side of the trigger
+    fn draw_clear_icon(&mut self, cx: &mut Cx2d) {
+        if !self.multiple || !self.clearable || self.selected_ids.is_empty() {
+            return;
+        }
+        let rect = self.area().rect(cx);
+        let size = self.clear_size;
+        self.draw_clear.draw_abs(
+            cx,
+            Rect {
+                pos: dvec2(
+                    rect.pos.x + rect.size.x - size - 8.0,
+                    rect.pos.y + (rect.size.y - size) * 0.5,
+                ),
+                size: dvec2(size, size),
+            },
+        );
+    }
+    /// handle the closed event of tags, return true if a tag is closed
+    fn handle_tags(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) -> bool {
+        let count = self.selected_ids.len().min(self.max_tags);
+        let actions = cx.capture_actions(|cx| {
+            for (_, tag) in self.tags.iter() {
+                tag.handle_event(cx, event, scope);
+            }
+        });
+        let closed = (0..count).find(|slot| {
+            self.tags
+                .get(&LiveId(*slot as u64))
+                .map_or(false, |tag| tag.closed(&actions).is_some())
+        });
+        match closed {
+            Some(slot) => {
+                self.selected_ids.remove(slot);
+                self.redraw(cx);
+                self.active_multi_changed(cx, &scope.path);
+                true
+            }
+            None => false,
+        }
+    }
+    fn active_multi_changed(&mut self, cx: &mut Cx, path: &HeapLiveIdPath) {
+        if self.event_key {
+            cx.widget_action(
+                self.widget_uid(),
+                path,
+                GSelectEvent::MultiChanged(GSelectMultiChangedParam {
+                    selected: self.selected_options(),
+                    selected_ids: self.selected_ids.clone(),
+                }),
+            );
+        }
+    }
+    pub fn options(&self) -> &[SelectOption] {
+        &self.options
+    }
+    /// the options to change, the rows are rebuilt at the next draw
+    pub fn options_mut(&mut self) -> &mut Vec<SelectOption> {
+        self.options_version += 1;
+        &mut self.options
+    }
+    /// selected options of `multiple` mode in selection order
+    pub fn selected_options(&self) -> Vec<SelectOption> {
+        self.selected_ids
+            .iter()
+            .filter_map(|id| self.options.get(*id).cloned())
+            .collect()
+    }
+    pub fn set_selected_ids(&mut self, cx: &mut Cx, selected_ids: Vec<usize>) {
+        self.selected_ids = selected_ids;
+        self.redraw(cx);
+    }
+    /// clear all selected options of `multiple` mode
+    pub fn clear(&mut self, cx: &mut Cx) {
+        self.selected_ids.clear();
+        self.redraw(cx);
+    }
+    pub fn redraw(&mut self, cx: &mut Cx) {
+        self.draw_select.redraw(cx);
+    }
     pub fn open(&mut self, cx: &mut Cx) {
+        if self.opened {
+            return;
+        }
         self.opened = true;
+        self.type_ahead.clear();
+        // start from the selected option
+        self.highlighted = if self.multiple {
+            self.selected_ids.first().copied()
+        } else {
+            Some(self.selected).filter(|selected| *selected < self.options.len())
+        };
+        if self.searchable {
+            self.query.clear();
+            self.focus_search = true;
+        }
+        if self.remote {
+            self.reload(cx);
+        }
         self.draw_select.apply_over(cx, live! {focus: 1.0});
         self.draw_select.redraw(cx);
+        if let Some(options) = self.select_options {
+            let global = cx.global::<SelectOptionsGlobal>().clone();
+            let mut map = global.map.borrow_mut();
+            if let Some(menu) = map.get_mut(&options) {
+                menu.raised = true;
+            }
+        }
         // let global = cx.global::<PopupMenuGlobal>().clone();
         // let mut map = global.map.borrow_mut();
         // let lb = map.get_mut(&self.popup_menu.unwrap()).unwrap();
         // let node_id = LiveId(self.selected_item as u64).into();
         // lb.init_select_item(node_id);
-        cx.sweep_lock(self.draw_select.area());
+        PopupStackGlobal::open(cx, self.widget_uid(), self.draw_select.area());
     }
 
     pub fn close(&mut self, cx: &mut Cx) {
         self.opened = false;
+        self.query.clear();
         self.draw_select.apply_over(cx, live! {focus: 0.0});
         self.draw_select.redraw(cx);
-        cx.sweep_unlock(self.draw_select.area());
+        PopupStackGlobal::close(cx, self.widget_uid(), self.draw_select.area());
+    }
+}
+
+impl GSelectRef {
+    ref_event_option! {
+        changed => GSelectOptionsChangedParam,
+        key_changed => GSelectKeyChangedParam,
+        multi_changed => GSelectMultiChangedParam,
+        created => GSelectCreatedParam,
+        load => GSelectLoadParam
+    }
+    /// answer a `GSelectEvent::Load` request (`remote` mode)
+    pub fn set_options(&self, cx: &mut Cx, request_id: u64, options: Vec<SelectOption>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_options(cx, request_id, options);
+        }
+    }
+    pub fn is_loading(&self) -> bool {
+        self.borrow().map_or(false, |c_ref| c_ref.is_loading())
+    }
+    pub fn selected_options(&self) -> Vec<SelectOption> {
+        self.borrow()
+            .map(|c_ref| c_ref.selected_options())
+            .unwrap_or_default()
+    }
+    pub fn set_selected_ids(&self, cx: &mut Cx, selected_ids: Vec<usize>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_selected_ids(cx, selected_ids);
+        }
+    }
+    pub fn clear(&self, cx: &mut Cx) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.clear(cx);
+        }
     }
 }
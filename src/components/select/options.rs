@@ -1,12 +1,19 @@
+use std::{collections::HashMap, ops::Range};
+
 use makepad_widgets::*;
 
 use crate::{
-    shader::draw_view::DrawGView,
+    shader::{draw_view::DrawGView, manual::ComponentMode},
+    sync_theme,
     themes::Themes,
     utils::{BoolToF32, ThemeColor},
 };
 
-use super::{item::GSelectItem, GSelectItemEvent, GSelectOptionsChangedParam, GSelectOptionsEvent};
+use super::{
+    item::GSelectItem,
+    types::{RowHeights, RowLayout, SelectOption, SelectRow},
+    GSelectItemEvent, GSelectOptionsChangedParam, GSelectOptionsEvent,
+};
 
 live_design! {
     GSelectOptionsBase = {{GSelectOptions}}{
@@ -14,6 +21,14 @@ live_design! {
     }
 }
 
+/// the maps of the row widgets, each map counts its own slots in virtual mode
+#[derive(Debug, Clone, Copy)]
+enum SlotKind {
+    Option,
+    Group,
+    Divider,
+}
+
 #[derive(Live, LiveRegister)]
 pub struct GSelectOptions {
     #[live]
@@ -54,11 +69,57 @@ pub struct GSelectOptions {
     pub draw_list: DrawList2d,
     #[live]
     pub item: Option<LivePtr>,
+    /// items are toggled independently (set by GSelect)
+    #[rust]
+    pub multiple: bool,
+    /// group header template, it is a GSelectItem which can not be selected
+    #[live]
+    pub group_item: Option<LivePtr>,
+    /// divider template (GDivider) between groups
+    #[live]
+    pub divider: Option<LivePtr>,
+    #[rust]
+    pub groups: ComponentMap<LiveId, GSelectItem>,
+    #[rust]
+    pub dividers: ComponentMap<LiveId, WidgetRef>,
+    /// loading template (GLoading), shown while remote options are loading
+    #[live]
+    pub loading: Option<LivePtr>,
+    #[rust]
+    loading_ref: Option<WidgetRef>,
+    /// height of the content below the visible area, updated when drawing
+    #[rust]
+    pub rest_height: f64,
+    // virtual --------------------
+    /// `Virtual` only draws the visible rows and reuses the item widgets (set by GSelect),
+    /// the rows have fixed heights: `item_height`, `group_height` and `divider_height`
+    #[live]
+    pub mode: ComponentMode,
+    #[live(36.0)]
+    pub item_height: f64,
+    #[live(28.0)]
+    pub group_height: f64,
+    #[live(9.0)]
+    pub divider_height: f64,
+    /// next slot to draw in virtual mode, by `SlotKind`
+    #[rust]
+    slot: [u64; 3],
+    /// virtual mode: slot of the drawn option -> item id
+    #[rust]
+    slots: HashMap<LiveId, LiveId>,
+    /// virtual mode: the layout of the rows, rebuilt when the key of the rows changes (see `draw_rows`)
+    #[rust]
+    layout: Option<((WidgetUid, u64), RowLayout)>,
     // scroll ---------------------
     #[live]
     pub scroll_bars: Option<LivePtr>,
     #[rust]
     pub scroll_bars_obj: Option<Box<ScrollBars>>,
+    /// draw above the other popups in the next `begin` (see `GPopup::raise`)
+    #[rust]
+    pub raised: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl LiveHook for GSelectOptions {
@@ -69,6 +130,12 @@ impl LiveHook for GSelectOptions {
                     Some(Box::new(ScrollBars::new_from_ptr(cx, self.scroll_bars)));
             }
         }
+        self.render(cx);
+    }
+}
+
+impl GSelectOptions {
+    pub fn render(&mut self, cx: &mut Cx) {
         // ----------------- background color -------------------------------------------
         let bg_color = self.background_color.use_or("#ffffff");
         // ------------------ hover color -----------------------------------------------
@@ -97,9 +164,6 @@ impl LiveHook for GSelectOptions {
         );
         self.draw_options.redraw(cx);
     }
-}
-
-impl GSelectOptions {
     pub fn area(&self) -> Area {
         self.draw_options.area()
     }
@@ -110,7 +174,13 @@ impl GSelectOptions {
     /// ## Begin to draw popup
     /// this method is used to begin drawing the popup
     pub fn begin(&mut self, cx: &mut Cx2d, theme: Themes) {
-        self.draw_list.begin_overlay_reuse(cx);
+        sync_theme!(self, cx);
+        if self.raised {
+            self.raised = false;
+            self.draw_list.begin_overlay_last(cx);
+        } else {
+            self.draw_list.begin_overlay_reuse(cx);
+        }
         cx.begin_pass_sized_turtle(Layout::flow_down());
         let scroll = if let Some(scroll_bars) = &mut self.scroll_bars_obj {
             scroll_bars.begin_nav_area(cx);
@@ -121,11 +191,16 @@ impl GSelectOptions {
         self.draw_options
             .begin(cx, self.walk, self.layout.with_scroll(scroll));
         self.theme = theme;
+        self.slot = [0; 3];
+        self.slots.clear();
     }
     pub fn end_container(&mut self, cx: &mut Cx2d) {
         if let Some(scroll_bars) = &mut self.scroll_bars_obj {
             scroll_bars.draw_scroll_bars(cx);
         }
+        let turtle = cx.turtle();
+        let rect = turtle.rect();
+        self.rest_height = turtle.pos().y - (rect.pos.y + rect.size.y);
         // before end do apply
         self.draw_options.end(cx);
         let area = self.area();
@@ -145,11 +220,213 @@ impl GSelectOptions {
         self.draw_list.redraw(cx);
         // self.draw_options.redraw(cx);
     }
-    pub fn draw_option(&mut self, cx: &mut Cx2d, item_id: LiveId, text: &str, value: &str) {
+    fn row_heights(&self) -> RowHeights {
+        RowHeights {
+            item: self.item_height,
+            group: self.group_height,
+            divider: self.divider_height,
+        }
+    }
+    /// the key of the row widget, it is the item id in real mode and the slot in virtual mode
+    fn next_key(&mut self, kind: SlotKind, item_id: LiveId) -> LiveId {
+        match self.mode {
+            ComponentMode::Real => item_id,
+            ComponentMode::Virtual => {
+                let slot = &mut self.slot[kind as usize];
+                let key = LiveId(*slot);
+                *slot += 1;
+                if let SlotKind::Option = kind {
+                    self.slots.insert(key, item_id);
+                }
+                key
+            }
+        }
+    }
+    /// the item widget is drawn in the last frame, a slot which is not drawn keeps a stale item in virtual mode
+    fn is_drawn(&self, key: &LiveId) -> bool {
+        self.mode == ComponentMode::Real || self.slots.contains_key(key)
+    }
+    /// the item id of the item widget
+    fn item_id(&self, key: LiveId) -> LiveId {
+        match self.mode {
+            ComponentMode::Real => key,
+            ComponentMode::Virtual => self.slots.get(&key).copied().unwrap_or(key),
+        }
+    }
+    /// highlight an item (keyboard navigation) with the hover state, `None` clears the highlight
+    pub fn highlight(&mut self, cx: &mut Cx, item_id: Option<LiveId>) {
+        let highlighted = self
+            .children
+            .keys()
+            .copied()
+            .find(|key| self.is_drawn(key) && Some(self.item_id(*key)) == item_id);
+        for (key, item) in self.children.iter_mut() {
+            if Some(*key) == highlighted {
+                item.animator_play(cx, id!(hover.on));
+            } else {
+                item.animator_play(cx, id!(hover.off));
+            }
+        }
+    }
+    /// ## Scroll into view
+    /// scroll the options so the item is fully visible,
+    /// in real mode the item should have been drawn
+    pub fn scroll_into_view(&mut self, cx: &mut Cx, item_id: LiveId) {
+        let view = self.area().rect(cx);
+        let (top, height) = match self.mode {
+            ComponentMode::Real => match self.children.get(&item_id) {
+                Some(item) => {
+                    let item = item.area().rect(cx);
+                    (item.pos.y - view.pos.y, item.size.y)
+                }
+                None => return,
+            },
+            ComponentMode::Virtual => {
+                match self
+                    .layout
+                    .as_ref()
+                    .and_then(|(_, layout)| layout.option_top(item_id.0 as usize))
+                {
+                    Some(top) => (
+                        top + self.layout.padding.top - self.scroll_pos().y,
+                        self.item_height,
+                    ),
+                    None => return,
+                }
+            }
+        };
+        if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            let scroll = scroll_bars.get_scroll_pos();
+            let y = if top < 0.0 {
+                scroll.y + top
+            } else if top + height > view.size.y {
+                scroll.y + top + height - view.size.y
+            } else {
+                return;
+            };
+            scroll_bars.set_scroll_pos(cx, dvec2(scroll.x, y.max(0.0)));
+        }
+    }
+    pub fn scroll_pos(&self) -> DVec2 {
+        self.scroll_bars_obj
+            .as_ref()
+            .map(|scroll_bars| scroll_bars.get_scroll_pos())
+            .unwrap_or(self.layout.scroll)
+    }
+    /// ## Draw rows
+    /// draw all rows in real mode, only the rows in viewport in virtual mode.
+    /// The `key` (owner and version of the rows) changes when the rows change, the layout is kept until then
+    pub fn draw_rows(
+        &mut self,
+        cx: &mut Cx2d,
+        key: (WidgetUid, u64),
+        rows: &[SelectRow],
+        options: &[SelectOption],
+        is_selected: &dyn Fn(usize) -> bool,
+    ) {
+        let (range, rest) = match self.mode {
+            ComponentMode::Real => (0..rows.len(), None),
+            ComponentMode::Virtual => {
+                let heights = self.row_heights();
+                if !matches!(&self.layout, Some((k, layout)) if *k == key && layout.heights == heights)
+                {
+                    self.layout = Some((key, RowLayout::new(rows, heights)));
+                }
+                let viewport = cx.turtle().rect();
+                let scroll = self.scroll_pos().y;
+                let (range, top, rest) = match self.layout.as_ref() {
+                    Some((_, layout)) => layout.visible(scroll, viewport.size.y),
+                    None => (0..0, 0.0, 0.0),
+                };
+                cx.walk_turtle(Walk {
+                    width: Size::Fixed(0.0),
+                    height: Size::Fixed(top),
+                    ..Default::default()
+                });
+                (range, Some(rest))
+            }
+        };
+        for row in &rows[range] {
+            match row {
+                SelectRow::Divider(id) => self.draw_divider(cx, LiveId(*id as u64)),
+                SelectRow::Group(id, title) => self.draw_group(cx, LiveId(*id as u64), title),
+                SelectRow::Option(index, highlights) => self.draw_option(
+                    cx,
+                    LiveId(*index as u64),
+                    &options[*index],
+                    is_selected(*index),
+                    highlights,
+                ),
+            }
+        }
+        if let Some(rest) = rest {
+            cx.walk_turtle(Walk {
+                width: Size::Fixed(0.0),
+                height: Size::Fixed(rest),
+                ..Default::default()
+            });
+        }
+    }
+    pub fn draw_option(
+        &mut self,
+        cx: &mut Cx2d,
+        item_id: LiveId,
+        option: &SelectOption,
+        selected: bool,
+        highlights: &[Range<usize>],
+    ) {
+        let key = self.next_key(SlotKind::Option, item_id);
         let target = self
             .children
-            .get_or_insert(cx, item_id, |cx| GSelectItem::new_from_ptr(cx, self.item));
-        target.draw_item(cx, text, value, self.theme);
+            .get_or_insert(cx, key, |cx| GSelectItem::new_from_ptr(cx, self.item));
+        if self.mode == ComponentMode::Virtual {
+            target.walk.height = Size::Fixed(self.item_height);
+        }
+        // keep the item state in sync with the select
+        if target.selected != selected {
+            target.selected = selected;
+            if selected {
+                target.animator_cut(cx, id!(select.on));
+            } else {
+                target.animator_cut(cx, id!(select.off));
+            }
+        }
+        target.draw_item(cx, option, self.theme, highlights);
+    }
+    /// draw a group header
+    pub fn draw_group(&mut self, cx: &mut Cx2d, group_id: LiveId, title: &str) {
+        let key = self.next_key(SlotKind::Group, group_id);
+        let target = self
+            .groups
+            .get_or_insert(cx, key, |cx| GSelectItem::new_from_ptr(cx, self.group_item));
+        if self.mode == ComponentMode::Virtual {
+            target.walk.height = Size::Fixed(self.group_height);
+        }
+        target.draw_item(cx, &SelectOption::new(title, ""), self.theme, &[]);
+    }
+    /// draw a divider between groups
+    pub fn draw_divider(&mut self, cx: &mut Cx2d, divider_id: LiveId) {
+        let key = self.next_key(SlotKind::Divider, divider_id);
+        let divider_ptr = self.divider;
+        let target = self
+            .dividers
+            .get_or_insert(cx, key, |cx| WidgetRef::new_from_ptr(cx, divider_ptr))
+            .clone();
+        let mut divider_walk = target.walk(cx);
+        if self.mode == ComponentMode::Virtual {
+            divider_walk.height = Size::Fixed(self.divider_height);
+        }
+        let _ = target.draw_walk(cx, &mut Scope::empty(), divider_walk);
+    }
+    /// draw the loading spinner after the options
+    pub fn draw_loading(&mut self, cx: &mut Cx2d) {
+        let loading_ptr = self.loading;
+        let loading = self
+            .loading_ref
+            .get_or_insert_with(|| WidgetRef::new_from_ptr(cx, loading_ptr))
+            .clone();
+        let loading_walk = loading.walk(cx);
+        let _ = loading.draw_walk(cx, &mut Scope::empty(), loading_walk);
     }
     pub fn handle_event_with(
         &mut self,
@@ -158,8 +435,14 @@ impl GSelectOptions {
         sweep_area: Area,
         dispatch_action: &mut dyn FnMut(&mut Cx, GSelectOptionsEvent),
     ) {
+        if let Some(loading) = self.loading_ref.as_ref() {
+            loading.handle_event(cx, event, &mut Scope::empty());
+        }
         let mut actions = Vec::new();
         for (item_id, node) in self.children.iter_mut() {
+            if self.mode == ComponentMode::Virtual && !self.slots.contains_key(item_id) {
+                continue;
+            }
             node.handle_event_with(cx, event, sweep_area, &mut |_, e| {
                 actions.push((*item_id, e))
             });
@@ -169,7 +452,15 @@ impl GSelectOptions {
             match action {
                 GSelectItemEvent::Clicked(param) => {
                     // if is item clicked, do options event change
-                    if param.selected {
+                    if self.multiple {
+                        if let Some(item) = self.children.get_mut(&node_id) {
+                            if param.selected {
+                                item.animator_play(cx, id!(select.on));
+                            } else {
+                                item.animator_play(cx, id!(select.off));
+                            }
+                        }
+                    } else if param.selected {
                         for (_index, (id, item)) in self.children.iter_mut().enumerate() {
                             if id.0 != node_id.0 {
                                 item.selected = false;
@@ -186,7 +477,7 @@ impl GSelectOptions {
                             selected: param.selected,
                             text: param.text,
                             value: param.value,
-                            selected_id: node_id.0 as usize,
+                            selected_id: self.item_id(node_id).0 as usize,
                             e: param.e,
                         }),
                     );
@@ -1,7 +1,29 @@
-#[derive(Debug, Clone, PartialEq)]
+use std::{collections::HashMap, ops::Range};
+
+use makepad_widgets::*;
+
+use crate::shader::icon_lib::types::IconType;
+
+/// # Select Option
+/// Build an option with the builder methods:
+/// ```rust
+/// let option = SelectOption::new("Apple", "apple")
+///     .with_group("Fruits")
+///     .with_icon(IconType::Home)
+///     .with_description("red or green");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct SelectOption {
     pub text: String,
     pub value: String,
+    /// title of the group, options of the same group are shown together under a group header
+    pub group: Option<String>,
+    /// a disabled option can not be selected
+    pub disabled: bool,
+    /// leading icon
+    pub icon: Option<IconType>,
+    /// secondary text shown after the text
+    pub description: Option<String>,
 }
 
 impl SelectOption {
@@ -9,8 +31,25 @@ impl SelectOption {
         Self {
             text: text.to_string(),
             value: value.to_string(),
+            ..Default::default()
         }
     }
+    pub fn with_group(mut self, group: &str) -> Self {
+        self.group.replace(group.to_string());
+        self
+    }
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+    pub fn with_icon(mut self, icon: IconType) -> Self {
+        self.icon.replace(icon);
+        self
+    }
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description.replace(description.to_string());
+        self
+    }
 }
 
 impl From<(&str, &str)> for SelectOption {
@@ -18,3 +57,546 @@ impl From<(&str, &str)> for SelectOption {
         Self::new(value.0, value.1)
     }
 }
+
+/// # Live Select Option
+/// The option which can be declared in live design (`GSelect.option_list`):
+/// ```
+/// option_list: [
+///     {text: "Apple", value: "apple", group: "Fruits", icon: Home, show_icon: true},
+///     {text: "Carrot", value: "carrot", group: "Vegetables", disabled: true},
+/// ]
+/// ```
+/// - `group`: empty means no group
+/// - `icon`: only drawn when `show_icon` is true
+#[derive(Live, LiveHook, Clone, Debug)]
+#[live_ignore]
+pub struct LiveSelectOption {
+    #[live]
+    pub text: String,
+    #[live]
+    pub value: String,
+    #[live]
+    pub group: String,
+    #[live]
+    pub disabled: bool,
+    #[live]
+    pub icon: IconType,
+    #[live]
+    pub show_icon: bool,
+    #[live]
+    pub description: String,
+}
+
+impl From<&LiveSelectOption> for SelectOption {
+    fn from(value: &LiveSelectOption) -> Self {
+        let not_empty = |s: &String| (!s.is_empty()).then(|| s.to_string());
+        Self {
+            text: value.text.to_string(),
+            value: value.value.to_string(),
+            group: not_empty(&value.group),
+            disabled: value.disabled,
+            icon: value.show_icon.then(|| value.icon.clone()),
+            description: not_empty(&value.description),
+        }
+    }
+}
+
+/// ## Group options
+/// keep the options of the same group together, groups are in the order of their first option,
+/// options without group come first
+pub fn group_options<T>(options: &[SelectOption], matched: Vec<(usize, T)>) -> Vec<(usize, T)> {
+    let mut groups: Vec<Option<&str>> = vec![];
+    for (index, _) in matched.iter() {
+        let group = options[*index].group.as_deref();
+        if group.is_some() && !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+    let mut matched = matched;
+    // stable sort, the order in a group is kept
+    matched.sort_by_key(|(index, _)| {
+        let group = options[*index].group.as_deref();
+        groups
+            .iter()
+            .position(|g| *g == group)
+            .map_or(0, |position| position + 1)
+    });
+    matched
+}
+
+/// # Select Row
+/// A row of the options list in display order
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectRow {
+    /// divider before the group (index of the group)
+    Divider(usize),
+    /// group header (index of the group, title)
+    Group(usize, String),
+    /// index of the option and the highlighted ranges of its text
+    Option(usize, Vec<Range<usize>>),
+}
+
+/// ## Option rows
+/// Turn the grouped matched options (see [group_options]) into rows,
+/// each group after the first one starts with a divider, a named group has a header.
+pub fn option_rows(
+    options: &[SelectOption],
+    matched: Vec<(usize, Vec<Range<usize>>)>,
+) -> Vec<SelectRow> {
+    let mut rows = Vec::with_capacity(matched.len());
+    let mut group: Option<&str> = None;
+    let mut group_count = 0;
+    for (position, (index, highlights)) in matched.into_iter().enumerate() {
+        let option_group = options[index].group.as_deref();
+        if position == 0 || option_group != group {
+            group = option_group;
+            if position > 0 {
+                rows.push(SelectRow::Divider(group_count));
+            }
+            if let Some(title) = group {
+                rows.push(SelectRow::Group(group_count, title.to_string()));
+            }
+            group_count += 1;
+        }
+        rows.push(SelectRow::Option(index, highlights));
+    }
+    rows
+}
+
+/// # Row Heights
+/// Fixed heights of the rows in virtual mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RowHeights {
+    pub item: f64,
+    pub group: f64,
+    pub divider: f64,
+}
+
+impl RowHeights {
+    pub fn of(&self, row: &SelectRow) -> f64 {
+        match row {
+            SelectRow::Divider(_) => self.divider,
+            SelectRow::Group(..) => self.group,
+            SelectRow::Option(..) => self.item,
+        }
+    }
+}
+
+/// # Row Layout
+/// The top of each row in virtual mode, it is built once when the rows change
+/// so a frame only looks up the visible rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowLayout {
+    pub heights: RowHeights,
+    /// the top of each row and the total height at the end
+    tops: Vec<f64>,
+    /// option index -> top of the option row
+    options: HashMap<usize, f64>,
+}
+
+impl RowLayout {
+    pub fn new(rows: &[SelectRow], heights: RowHeights) -> Self {
+        let mut tops = Vec::with_capacity(rows.len() + 1);
+        let mut options = HashMap::new();
+        let mut top = 0.0;
+        for row in rows {
+            if let SelectRow::Option(index, _) = row {
+                options.insert(*index, top);
+            }
+            tops.push(top);
+            top += heights.of(row);
+        }
+        tops.push(top);
+        Self {
+            heights,
+            tops,
+            options,
+        }
+    }
+    /// the top of an option row
+    pub fn option_top(&self, index: usize) -> Option<f64> {
+        self.options.get(&index).copied()
+    }
+    /// ## Visible rows
+    /// Return the range of rows in the viewport, the height of the rows above and below it
+    pub fn visible(&self, scroll: f64, viewport: f64) -> (Range<usize>, f64, f64) {
+        let len = self.tops.len() - 1;
+        let start = self.tops[1..].partition_point(|bottom| *bottom <= scroll);
+        let end = start + self.tops[start..len].partition_point(|top| *top < scroll + viewport);
+        (
+            start..end,
+            self.tops[start],
+            self.tops[len] - self.tops[end],
+        )
+    }
+}
+
+/// ## Match text
+/// Case insensitive substring match first, then fuzzy match (the query chars appear in order).
+/// Returns the matched byte ranges of `text`, `None` if not matched.
+/// An empty query matches everything without ranges.
+pub fn match_text(text: &str, query: &str) -> Option<Vec<Range<usize>>> {
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let query: Vec<char> = query.trim().chars().map(lower).collect();
+    if query.is_empty() {
+        return Some(vec![]);
+    }
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let end = |index: usize| chars.get(index).map_or(text.len(), |(byte, _)| *byte);
+    // substring
+    if chars.len() >= query.len() {
+        for start in 0..=chars.len() - query.len() {
+            let found = query
+                .iter()
+                .enumerate()
+                .all(|(offset, q)| lower(chars[start + offset].1) == *q);
+            if found {
+                return Some(vec![chars[start].0..end(start + query.len())]);
+            }
+        }
+    }
+    // fuzzy, adjacent matched chars are merged into one range
+    let mut ranges: Vec<Range<usize>> = vec![];
+    let mut query_chars = query.iter().peekable();
+    for (index, (byte, c)) in chars.iter().enumerate() {
+        match query_chars.peek() {
+            Some(q) if lower(*c) == **q => {
+                query_chars.next();
+                match ranges.last_mut() {
+                    Some(last) if last.end == *byte => last.end = end(index + 1),
+                    _ => ranges.push(*byte..end(index + 1)),
+                }
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+    query_chars.peek().is_none().then_some(ranges)
+}
+
+/// filter the options by the query, returns the index of matched options and the matched ranges
+pub fn filter_options(options: &[SelectOption], query: &str) -> Vec<(usize, Vec<Range<usize>>)> {
+    options
+        .iter()
+        .enumerate()
+        .filter_map(|(index, option)| match_text(&option.text, query).map(|ranges| (index, ranges)))
+        .collect()
+}
+
+/// split the text into segments, the bool is true if the segment is highlighted
+pub fn split_highlight<'a>(text: &'a str, ranges: &[Range<usize>]) -> Vec<(&'a str, bool)> {
+    let mut segments = vec![];
+    let mut start = 0;
+    for range in ranges {
+        if range.start > start {
+            segments.push((&text[start..range.start], false));
+        }
+        segments.push((&text[range.clone()], true));
+        start = range.end;
+    }
+    if start < text.len() {
+        segments.push((&text[start..], false));
+    }
+    segments
+}
+
+/// ## Type ahead
+/// Find the option (in `order`) whose text starts with `prefix`, case insensitive.
+/// If the prefix is one char (or the same char repeated), cycle through the options starting with it
+/// from the one after `current`
+pub fn type_ahead(
+    options: &[SelectOption],
+    order: &[usize],
+    prefix: &str,
+    current: Option<usize>,
+) -> Option<usize> {
+    let prefix = prefix.to_lowercase();
+    let first = prefix.chars().next()?;
+    let starts_with = |id: &usize, prefix: &str| {
+        options.get(*id).map_or(false, |option| {
+            option.text.to_lowercase().starts_with(prefix)
+        })
+    };
+    if !prefix.chars().all(|c| c == first) {
+        return order.iter().find(|id| starts_with(id, &prefix)).copied();
+    }
+    let first = first.to_string();
+    let start = current
+        .and_then(|current| order.iter().position(|id| *id == current))
+        .map_or(0, |position| position + 1);
+    order[start..]
+        .iter()
+        .chain(order[..start].iter())
+        .find(|id| starts_with(id, &first))
+        .copied()
+}
+
+/// # Select Request
+/// The state of remote options loading (`GSelect.remote`).
+/// Each request has an id, the response of an outdated request is dropped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectRequest {
+    /// id of the latest request
+    pub id: u64,
+    /// a request should be sent (at next frame)
+    pub pending: bool,
+    /// waiting for the response of `id`
+    pub loading: bool,
+    /// the last page is full, there may be more options
+    pub has_more: bool,
+    /// offset of the next page, the count of loaded options
+    pub cursor: usize,
+}
+
+impl SelectRequest {
+    /// restart from the first page (opened or query changed), the running request is outdated
+    pub fn reset(&mut self) {
+        self.id += 1;
+        self.pending = true;
+        self.loading = false;
+        self.has_more = true;
+        self.cursor = 0;
+    }
+    /// ask for the next page, return false if there is no more page or a request is running
+    pub fn more(&mut self) -> bool {
+        if self.has_more && !self.pending && !self.loading {
+            self.pending = true;
+            true
+        } else {
+            false
+        }
+    }
+    /// start the pending request, return the request id and the cursor
+    pub fn start(&mut self) -> Option<(u64, usize)> {
+        if !self.pending {
+            return None;
+        }
+        self.pending = false;
+        self.loading = true;
+        self.id += 1;
+        Some((self.id, self.cursor))
+    }
+    /// ## Accept a response
+    /// return `Some(true)` if it is the first page, `None` if the request is outdated.
+    /// A page which is shorter than `page_size` is the last page (`page_size` 0 means no paging)
+    pub fn accept(&mut self, id: u64, count: usize, page_size: usize) -> Option<bool> {
+        if id != self.id || !self.loading {
+            return None;
+        }
+        self.loading = false;
+        let first = self.cursor == 0;
+        self.cursor += count;
+        self.has_more = page_size > 0 && count >= page_size;
+        Some(first)
+    }
+}
+
+/// ## Merge a page
+/// The first page replaces the options, the selected options which are not in it are kept at the front,
+/// so the selection is not lost when searching. Other pages are appended (options with the same value are skipped).
+/// Returns the new indexes of `selected`
+pub fn merge_page(
+    options: &mut Vec<SelectOption>,
+    page: Vec<SelectOption>,
+    first: bool,
+    selected: &[usize],
+) -> Vec<usize> {
+    let selected_values: Vec<String> = selected
+        .iter()
+        .filter_map(|index| options.get(*index).map(|option| option.value.to_string()))
+        .collect();
+    if first {
+        let kept: Vec<SelectOption> = selected
+            .iter()
+            .filter_map(|index| options.get(*index))
+            .filter(|option| !page.iter().any(|p| p.value == option.value))
+            .cloned()
+            .collect();
+        *options = kept;
+    }
+    for option in page {
+        if !options.iter().any(|o| o.value == option.value) {
+            options.push(option);
+        }
+    }
+    selected_values
+        .iter()
+        .filter_map(|value| options.iter().position(|option| option.value == *value))
+        .collect()
+}
+
+#[cfg(test)]
+mod test_rows {
+    use super::*;
+
+    #[test]
+    fn rows() {
+        let options = vec![
+            SelectOption::new("A", "a"),
+            SelectOption::new("B", "b").with_group("G1"),
+            SelectOption::new("C", "c").with_group("G1"),
+            SelectOption::new("D", "d").with_group("G2"),
+        ];
+        let matched = (0..options.len()).map(|index| (index, vec![])).collect();
+        let rows = option_rows(&options, group_options(&options, matched));
+        assert_eq!(
+            rows,
+            vec![
+                SelectRow::Option(0, vec![]),
+                SelectRow::Divider(1),
+                SelectRow::Group(1, "G1".to_string()),
+                SelectRow::Option(1, vec![]),
+                SelectRow::Option(2, vec![]),
+                SelectRow::Divider(2),
+                SelectRow::Group(2, "G2".to_string()),
+                SelectRow::Option(3, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn visible() {
+        let heights = RowHeights {
+            item: 10.0,
+            group: 5.0,
+            divider: 1.0,
+        };
+        let rows: Vec<SelectRow> = (0..50_000)
+            .map(|index| SelectRow::Option(index, vec![]))
+            .collect();
+        let layout = RowLayout::new(&rows, heights);
+        let (range, top, rest) = layout.visible(105.0, 30.0);
+        assert_eq!(range, 10..14);
+        assert_eq!(top, 100.0);
+        assert_eq!(rest, (50_000 - 14) as f64 * 10.0);
+        assert_eq!(layout.option_top(12), Some(120.0));
+        let (range, _, rest) = RowLayout::new(&rows[..3], heights).visible(0.0, 100.0);
+        assert_eq!(range, 0..3);
+        assert_eq!(rest, 0.0);
+        // scrolled past the end, nothing is visible
+        let (range, top, rest) = RowLayout::new(&rows[..3], heights).visible(50.0, 10.0);
+        assert_eq!(range, 3..3);
+        assert_eq!((top, rest), (30.0, 0.0));
+        assert_eq!(
+            RowLayout::new(&[], heights).visible(0.0, 10.0),
+            (0..0, 0.0, 0.0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_remote {
+    use super::*;
+
+    #[test]
+    fn request() {
+        let mut request = SelectRequest::default();
+        request.reset();
+        let (id, cursor) = request.start().unwrap();
+        assert_eq!(cursor, 0);
+        assert!(!request.more());
+        assert_eq!(request.accept(id, 20, 20), Some(true));
+        assert!(request.more());
+        let (outdated, cursor) = request.start().unwrap();
+        assert_eq!(cursor, 20);
+        // query changed before the response
+        request.reset();
+        assert_eq!(request.accept(outdated, 20, 20), None);
+        let (id, _) = request.start().unwrap();
+        assert_eq!(request.accept(id, 3, 20), Some(true));
+        assert!(!request.has_more);
+        assert!(!request.more());
+    }
+
+    #[test]
+    fn merge() {
+        let mut options: Vec<SelectOption> = vec![("A", "a").into(), ("B", "b").into()];
+        let page = vec![("C", "c").into(), ("A", "a").into()];
+        let selected = merge_page(&mut options, page, true, &[1, 0]);
+        let values: Vec<&str> = options.iter().map(|o| o.value.as_str()).collect();
+        assert_eq!(values, vec!["b", "c", "a"]);
+        assert_eq!(selected, vec![0, 2]);
+        let selected = merge_page(
+            &mut options,
+            vec![("B", "b").into(), ("D", "d").into()],
+            false,
+            &selected,
+        );
+        assert_eq!(options.len(), 4);
+        assert_eq!(selected, vec![0, 2]);
+    }
+}
+
+#[cfg(test)]
+mod test_search {
+    use super::*;
+
+    #[test]
+    fn substring() {
+        assert_eq!(match_text("United States", "STATE"), Some(vec![7..12]));
+        assert_eq!(match_text("United States", ""), Some(vec![]));
+        assert_eq!(match_text("日本", "本"), Some(vec![3..6]));
+    }
+
+    #[test]
+    fn fuzzy() {
+        assert_eq!(
+            match_text("United Kingdom", "ukd"),
+            Some(vec![0..1, 7..8, 11..12])
+        );
+        assert_eq!(match_text("Germany", "gre"), None);
+        assert_eq!(
+            match_text("America/New_York", "amny"),
+            Some(vec![0..2, 8..9, 12..13])
+        );
+    }
+
+    #[test]
+    fn filter() {
+        let options: Vec<SelectOption> = vec![
+            ("China", "cn").into(),
+            ("Chile", "cl").into(),
+            ("Japan", "jp").into(),
+        ];
+        let matched: Vec<usize> = filter_options(&options, "chi")
+            .iter()
+            .map(|(index, _)| *index)
+            .collect();
+        assert_eq!(matched, vec![0, 1]);
+        assert_eq!(
+            split_highlight("Chile", &[0..3]),
+            vec![("Chi", true), ("le", false)]
+        );
+    }
+
+    #[test]
+    fn group() {
+        let options = vec![
+            SelectOption::new("Apple", "apple").with_group("Fruits"),
+            SelectOption::new("Beef", "beef").with_group("Meat"),
+            SelectOption::new("Any", "any"),
+            SelectOption::new("Pear", "pear").with_group("Fruits"),
+        ];
+        let matched = (0..options.len()).map(|index| (index, ())).collect();
+        let order: Vec<usize> = group_options(&options, matched)
+            .iter()
+            .map(|(index, _)| *index)
+            .collect();
+        assert_eq!(order, vec![2, 0, 3, 1]);
+    }
+
+    #[test]
+    fn jump() {
+        let options: Vec<SelectOption> = vec![
+            ("Canada", "ca").into(),
+            ("China", "cn").into(),
+            ("Denmark", "dk").into(),
+        ];
+        let order = vec![0, 1, 2];
+        assert_eq!(type_ahead(&options, &order, "ch", None), Some(1));
+        assert_eq!(type_ahead(&options, &order, "c", None), Some(0));
+        assert_eq!(type_ahead(&options, &order, "c", Some(0)), Some(1));
+        assert_eq!(type_ahead(&options, &order, "cc", Some(1)), Some(0));
+        assert_eq!(type_ahead(&options, &order, "x", None), None);
+    }
+}
@@ -2,6 +2,7 @@ use makepad_widgets::*;
 
 use crate::{
     shader::{draw_view::DrawGView, draw_icon_pixel::DrawGIconPixel, draw_tab::DrawTabBtn},
+    sync_theme,
     themes::{get_color, hex_to_vec4, Themes},
     utils::{get_font_family, set_cursor, AbsExt, DefaultTextStyle},
 };
@@ -110,6 +111,8 @@ pub struct GTabButton {
     pub msg_count_walk: Walk,
     #[layout]
     pub layout: Layout,
+    #[rust]
+    pub theme_version: u64,
 }
 
 #[derive(Clone, Debug, DefaultNone)]
@@ -125,6 +128,7 @@ pub enum GTabButtonEvent {
 
 impl Widget for GTabButton {
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         self.draw_tab_btn.begin(cx, walk, self.layout);
         let font = get_font_family(&self.font_family, cx);
 
@@ -1,9 +1,6 @@
 use makepad_widgets::*;
 
-use crate::{
-    shader::draw_view::DrawGView,
-    themes::{hex_to_vec4, Themes},
-};
+use crate::{shader::draw_view::DrawGView, sync_theme, themes::Themes, utils::ThemeColor};
 
 use super::button::{GTabButtonEvent, GTabButtonRef, GTabButtonWidgetRefExt};
 
@@ -70,6 +67,8 @@ pub struct GTabHeader {
     pub view_area: Area,
     #[live(true)]
     pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 #[derive(Clone, Debug, DefaultNone)]
@@ -81,6 +80,7 @@ pub enum GTabHeaderEvent {
 
 impl Widget for GTabHeader {
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         self.draw_header.begin(cx, walk, self.layout);
         self.scroll_bars.begin(
             cx,
@@ -144,14 +144,7 @@ impl Widget for GTabHeader {
 
 impl LiveHook for GTabHeader {
     fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
-        let bg_color = hex_to_vec4("#EAECF0");
-        self.draw_header.apply_over(
-            cx,
-            live! {
-                background_color: (bg_color),
-                background_visible: 1.0
-            },
-        )
+        self.render(cx);
     }
 }
 
@@ -210,6 +203,16 @@ impl GTabHeader {
             tab_btn_ref.as_origin_mut().unwrap().render(cx);
         }
     }
+    pub fn render(&mut self, cx: &mut Cx) {
+        let bg_color = self.background_color.use_or("#EAECF0");
+        self.draw_header.apply_over(
+            cx,
+            live! {
+                background_color: (bg_color),
+                background_visible: 1.0
+            },
+        )
+    }
     pub fn set_items(&mut self, items: Vec<String>) {
         self.items = items;
     }
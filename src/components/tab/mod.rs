@@ -7,7 +7,12 @@ use header::GTabHeader;
 use makepad_widgets::*;
 use pane::GTabPane;
 
-use crate::{shader::draw_view::DrawGView, themes::Themes};
+use crate::{
+    shader::draw_view::DrawGView,
+    sync_theme,
+    themes::Themes,
+    utils::{BoolToF32, ThemeColor},
+};
 
 live_design! {
     GTabBase = {{GTab}}{}
@@ -36,10 +41,13 @@ pub struct GTab {
     pub layout: Layout,
     #[live(true)]
     pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GTab {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         self.draw_tab.begin(cx, walk, self.layout);
 
         self.items = self.body.header_items();
@@ -77,19 +85,28 @@ impl Widget for GTab {
 impl LiveHook for GTab {
     fn after_apply(
         &mut self,
-        _cx: &mut Cx,
+        cx: &mut Cx,
         _apply: &mut Apply,
         _index: usize,
         _nodes: &[LiveNode],
     ) {
-        // let bg_color = get_color(self.theme, self.background_color, 500);
-        // self.draw_tab.apply_over(cx, live!{
-        //     background_color: (bg_color),
-        // })
+        self.render(cx);
     }
 }
 
 impl GTab {
+    /// the background is only drawn when `background_color` is set
+    pub fn render(&mut self, cx: &mut Cx) {
+        let bg_color = self.background_color.get(self.theme, 500);
+        let background_visible = self.background_color.is_some().to_f32();
+        self.draw_tab.apply_over(
+            cx,
+            live! {
+                background_color: (bg_color),
+                background_visible: (background_visible),
+            },
+        );
+    }
     pub fn area(&self) -> Area {
         self.draw_tab.area
     }
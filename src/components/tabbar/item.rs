@@ -5,6 +5,7 @@ use crate::{
     default_handle_animation, event_option, play_animation, ref_area, ref_area_ext,
     ref_event_option, ref_redraw, ref_render, set_event, set_scope_path,
     shader::draw_view::DrawGView,
+    sync_theme,
     themes::Themes,
     utils::{set_cursor, BoolToF32, ThemeColor},
     widget_area,
@@ -127,10 +128,13 @@ pub struct GTabbarItem {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GTabbarItem {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.is_visible() {
             return DrawStep::done();
         }
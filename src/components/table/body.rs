@@ -1,9 +1,14 @@
+use std::collections::HashSet;
+
 use makepad_widgets::*;
 
 use crate::{
     components::{
+        table::{
+            row::{GTableRowRef, GTableRowWidgetRefExt},
+            types::{FrozenBounds, TableFrozen},
+        },
         view::DrawState,
-        table::row::{GTableRowRef, GTableRowWidgetRefExt},
     },
     shader::draw_view::DrawGView,
 };
@@ -40,6 +45,23 @@ pub struct GTableBody {
     pub draw_state: DrawStateWrap<DrawState>,
     #[live(true)]
     pub event_key: bool,
+    // scroll ---------------------
+    #[live]
+    pub scroll_bars: Option<LivePtr>,
+    #[rust]
+    pub scroll_bars_obj: Option<Box<ScrollBars>>,
+    /// frozen state, synced from GTable
+    #[rust]
+    pub frozen: TableFrozen,
+    /// the width of the body viewport (without scroll)
+    #[rust]
+    pub viewport_width: f64,
+    /// the width of the row content, which decide the horizontal scroll range
+    #[rust]
+    pub content_width: f64,
+    /// the rows hidden by the table filters (see `filter`)
+    #[rust]
+    pub filtered: HashSet<LiveId>,
 }
 
 impl Widget for GTableBody {
@@ -53,8 +75,22 @@ impl Widget for GTableBody {
             }
             self.defer_walks.clear();
 
+            // get scroll position
+            // the horizontal scroll is not applied to the turtle, rows handle it by themselves
+            // so that frozen columns can stay where they are
+            let scroll = if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+                scroll_bars.begin_nav_area(cx);
+                scroll_bars.get_scroll_pos()
+            } else {
+                self.layout.scroll
+            };
+            self.frozen.scroll_x = scroll.x;
             // begin draw the view
-            let _ = self.draw_table_body.begin(cx, walk, self.layout);
+            let _ = self
+                .draw_table_body
+                .begin(cx, walk, self.layout.with_scroll(dvec2(0.0, scroll.y)));
+            self.viewport_width = cx.turtle().rect().size.x;
+            self.content_width = 0.0;
         }
         // loop handle the inner children
         while let Some(DrawState::Drawing(step, resumed)) = self.draw_state.get() {
@@ -63,9 +99,15 @@ impl Widget for GTableBody {
                 let id = self.draw_order[step];
                 // get the child widget by id
                 if let Some(child) = self.children.get_mut(&id) {
-                    // is the child visible?
+                    // is the child visible and not filtered?
                     // true -> draw the child walk
-                    if child.is_visible() {
+                    if child.is_visible() && !self.filtered.contains(&id) {
+                        if !resumed {
+                            child.borrow_mut().map(|mut row| {
+                                row.frozen = self.frozen;
+                                self.content_width = self.content_width.max(row.content_width(cx));
+                            });
+                        }
                         let walk = child.walk(cx);
                         // if resumed
                         if !resumed {
@@ -91,8 +133,21 @@ impl Widget for GTableBody {
                 }
                 self.draw_state.set(DrawState::DeferWalk(step + 1));
             } else {
+                // make the turtle know the real content width, then the scroll bars can work
+                cx.walk_turtle(Walk {
+                    width: Size::Fixed(self.content_width),
+                    height: Size::Fixed(0.0),
+                    ..Default::default()
+                });
+                if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+                    scroll_bars.draw_scroll_bars(cx);
+                }
                 // draw background
                 self.draw_table_body.end(cx);
+                if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+                    scroll_bars.set_area(self.draw_table_body.area());
+                    scroll_bars.end_nav_area(cx);
+                }
             }
             self.draw_state.end();
         }
@@ -103,11 +158,21 @@ impl Widget for GTableBody {
     //     self.deref_widget.handle_event(cx, event, scope)
     // }
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        for (_index, (_id, row)) in self.children.iter().enumerate() {
-            if row.is_visible() {
+        if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            let mut actions = Vec::new();
+            scroll_bars.handle_main_event(cx, event, scope, &mut actions);
+            if actions.len().gt(&0) {
+                cx.redraw_area_and_children(self.draw_table_body.area());
+            }
+        }
+        for (_index, (id, row)) in self.children.iter().enumerate() {
+            if row.is_visible() && !self.filtered.contains(id) {
                 row.handle_event(cx, event, scope);
             }
         }
+        if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            scroll_bars.handle_scroll_event(cx, event, scope, &mut Vec::new());
+        }
     }
 
     fn is_visible(&self) -> bool {
@@ -116,6 +181,14 @@ impl Widget for GTableBody {
 }
 
 impl LiveHook for GTableBody {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if self.scroll_bars.is_some() {
+            if self.scroll_bars_obj.is_none() {
+                self.scroll_bars_obj =
+                    Some(Box::new(ScrollBars::new_from_ptr(cx, self.scroll_bars)));
+            }
+        }
+    }
     fn apply_value_instance(
         &mut self,
         cx: &mut Cx,
@@ -150,6 +223,52 @@ impl LiveHook for GTableBody {
 }
 
 impl GTableBody {
+    /// get current scroll position of the body
+    pub fn scroll_pos(&self) -> DVec2 {
+        self.scroll_bars_obj
+            .as_ref()
+            .map(|scroll_bars| scroll_bars.get_scroll_pos())
+            .unwrap_or(self.layout.scroll)
+    }
+    /// the max horizontal scroll
+    pub fn max_scroll_x(&self) -> f64 {
+        (self.content_width - self.viewport_width).max(0.0)
+    }
+    /// get the frozen bounds of the first visible row
+    pub fn frozen_bounds(&self) -> Option<FrozenBounds> {
+        self.draw_order.iter().find_map(|id| {
+            self.children
+                .get(id)
+                .and_then(|row| row.borrow().and_then(|row| row.frozen_bounds))
+        })
+    }
+    /// get the text of all rows in draw order
+    pub fn texts(&self) -> Vec<Vec<String>> {
+        self.draw_order
+            .iter()
+            .filter_map(|id| self.children.get(id))
+            .filter_map(|row| row.borrow().map(|row| row.texts()))
+            .collect()
+    }
+    /// ## Filter
+    /// hide the rows whose texts do not match
+    pub fn filter<F>(&mut self, cx: &mut Cx, matches: F)
+    where
+        F: Fn(&[String]) -> bool,
+    {
+        let mut filtered = HashSet::new();
+        for id in self.draw_order.iter() {
+            if let Some(row) = self.children.get(id).and_then(|row| row.borrow()) {
+                if !matches(&row.texts()) {
+                    filtered.insert(*id);
+                }
+            }
+        }
+        if filtered != self.filtered {
+            self.filtered = filtered;
+            self.redraw(cx);
+        }
+    }
     pub fn redraw(&mut self, cx: &mut Cx) {
         self.draw_table_body.redraw(cx);
         for (_, child) in self.children.iter() {
@@ -1,8 +1,14 @@
 use makepad_widgets::*;
 
-use crate::{components::view::GView, widget_area};
+use crate::{
+    components::view::GView,
+    shader::{draw_icon_pixel::DrawGIconPixel, manual::FilterMode},
+    utils::ThemeColor,
+    widget_area,
+};
 
 live_design! {
+    import makepad_draw::shader::std::*;
     GTableCellBase = {{GTableCell}}{
         background_visible: true,
         border_radius: 0.0,
@@ -11,7 +17,26 @@ live_design! {
         hover_color: #F9FAFB,
         focus_color: #F9FAFB,
         border_color: #EAECF0,
-        shadow_color: #FFFFFF00
+        shadow_color: #FFFFFF00,
+        draw_filter: {
+            instance active: 0.0
+            uniform active_color: vec4
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let w = self.rect_size.x;
+                let h = self.rect_size.y;
+                // funnel
+                sdf.move_to(w * 0.1, h * 0.2);
+                sdf.line_to(w * 0.9, h * 0.2);
+                sdf.line_to(w * 0.6, h * 0.55);
+                sdf.line_to(w * 0.6, h * 0.85);
+                sdf.line_to(w * 0.4, h * 0.75);
+                sdf.line_to(w * 0.4, h * 0.55);
+                sdf.close_path();
+                sdf.fill(mix(self.color, self.active_color, self.active));
+                return sdf.result;
+            }
+        }
     }
 }
 
@@ -19,13 +44,57 @@ live_design! {
 pub struct GTableCell {
     #[deref]
     pub deref_widget: GView,
+    /// show a filter icon at the right side (header cell only)
+    #[live]
+    pub filter: FilterMode,
+    #[live(12.0)]
+    pub filter_size: f64,
+    #[live]
+    pub filter_color: Option<Vec4>,
+    /// the color of the filter icon when the filter is active
+    #[live]
+    pub filter_active_color: Option<Vec4>,
+    #[live]
+    pub draw_filter: DrawGIconPixel,
+    #[rust]
+    pub filter_active: bool,
 }
 
-impl LiveHook for GTableCell {}
+impl LiveHook for GTableCell {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) {
+        self.deref_widget.after_apply(cx, apply, index, nodes);
+        let filter_color = self.filter_color.use_or("#98A2B3");
+        let filter_active_color = self.filter_active_color.use_or("#06AED4");
+        self.draw_filter.apply_over(
+            cx,
+            live! {
+                color: (filter_color),
+                active_color: (filter_active_color),
+            },
+        );
+    }
+}
 
 impl Widget for GTableCell {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        self.deref_widget.draw_walk(cx, scope, walk)
+        self.deref_widget.draw_walk(cx, scope, walk)?;
+        if self.visible && self.filter != FilterMode::None {
+            let rect = self.area().rect(cx);
+            let size = self.filter_size;
+            let active = if self.filter_active { 1.0 } else { 0.0 };
+            self.draw_filter.apply_over(cx, live! {active: (active)});
+            self.draw_filter.draw_abs(
+                cx,
+                Rect {
+                    pos: dvec2(
+                        rect.pos.x + rect.size.x - size - 6.0,
+                        rect.pos.y + (rect.size.y - size) * 0.5,
+                    ),
+                    size: dvec2(size, size),
+                },
+            );
+        }
+        DrawStep::done()
     }
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.deref_widget.handle_event(cx, event, scope)
@@ -34,9 +103,15 @@ impl Widget for GTableCell {
 
 impl GTableCell {
     widget_area! {
-        area, draw_view
+        area, draw_view,
+        area_filter, draw_filter
     }
     pub fn redraw(&mut self, cx: &mut Cx) {
         self.deref_widget.redraw(cx);
     }
+    /// highlight the filter icon
+    pub fn set_filter_active(&mut self, cx: &mut Cx, active: bool) {
+        self.filter_active = active;
+        self.redraw(cx);
+    }
 }
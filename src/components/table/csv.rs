@@ -0,0 +1,208 @@
+//! # CSV / TSV
+//! Read and write the table data as delimiter separated text (RFC 4180 quoting).
+
+const BOM: char = '\u{feff}';
+
+/// # CSV Options
+/// - `delimiter`: `,` for csv, `\t` for tsv
+/// - `bom`: write UTF-8 BOM at the beginning (Excel needs it to detect UTF-8)
+/// - `header`: export: write the header row or not, import: the first row is header or not,
+/// `None` means write header when exporting and detect header when importing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub bom: bool,
+    pub header: Option<bool>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+impl CsvOptions {
+    pub fn csv() -> Self {
+        Self {
+            delimiter: ',',
+            bom: false,
+            header: None,
+        }
+    }
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Self::csv()
+        }
+    }
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header.replace(header);
+        self
+    }
+    /// file extension of the format
+    pub fn extension(&self) -> &'static str {
+        if self.delimiter == '\t' {
+            "tsv"
+        } else {
+            "csv"
+        }
+    }
+}
+
+/// # CSV Table
+/// The result of reading csv text
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvTable {
+    pub header: Option<Vec<String>>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// quote the field if it contains delimiter, quote, line break or leading/trailing space
+pub fn quote_field(field: &str, delimiter: char) -> String {
+    let need_quote = field.contains(|c| c == delimiter || c == '"' || c == '\n' || c == '\r')
+        || field.starts_with(' ')
+        || field.ends_with(' ');
+    if need_quote {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// write rows to text, rows are separated by `\r\n`
+pub fn write_csv(header: Option<&[String]>, rows: &[Vec<String>], options: &CsvOptions) -> String {
+    let mut text = String::new();
+    if options.bom {
+        text.push(BOM);
+    }
+    let delimiter = options.delimiter.to_string();
+    let header = header.filter(|_| options.header.unwrap_or(true));
+    for row in header.into_iter().chain(rows.iter().map(|row| row.as_slice())) {
+        let line: Vec<String> = row
+            .iter()
+            .map(|field| quote_field(field, options.delimiter))
+            .collect();
+        text.push_str(&line.join(&delimiter));
+        text.push_str("\r\n");
+    }
+    text
+}
+
+/// parse text to rows, quoted fields can contain delimiter, quote (`""`) and line break
+pub fn parse_csv(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let text = text.strip_prefix(BOM).unwrap_or(text);
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    // a quoted field was opened in current row, so an empty `""` at the end is kept
+    let mut opened = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => {
+                quoted = true;
+                opened = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+                opened = false;
+            }
+            c if c == delimiter => row.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() || opened {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// ## Detect header
+/// Each column votes: if the cells under the first row are all numbers (or have the same length)
+/// and the first row cell is not, the first row looks like a header.
+pub fn has_header(rows: &[Vec<String>]) -> bool {
+    let (first, rest) = match rows.split_first() {
+        Some((first, rest)) if !rest.is_empty() => (first, rest),
+        _ => return false,
+    };
+    let is_number = |cell: &str| cell.trim().parse::<f64>().is_ok();
+    let mut votes = 0_i32;
+    for (index, title) in first.iter().enumerate() {
+        let cells: Vec<&str> = rest
+            .iter()
+            .filter_map(|row| row.get(index).map(|cell| cell.as_str()))
+            .collect();
+        if cells.is_empty() {
+            continue;
+        }
+        if cells.iter().all(|cell| is_number(cell)) {
+            votes += if is_number(title) { -1 } else { 1 };
+        } else {
+            let len = cells[0].chars().count();
+            if cells.iter().all(|cell| cell.chars().count() == len) {
+                votes += if title.chars().count() == len { -1 } else { 1 };
+            }
+        }
+    }
+    votes > 0
+}
+
+/// read csv text to table, the header is detected if `options.header` is `None`
+pub fn read_csv(text: &str, options: &CsvOptions) -> CsvTable {
+    let mut rows = parse_csv(text, options.delimiter);
+    let header = options.header.unwrap_or_else(|| has_header(&rows));
+    CsvTable {
+        header: (header && !rows.is_empty()).then(|| rows.remove(0)),
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod test_csv {
+    use super::*;
+
+    #[test]
+    fn write() {
+        let header = vec!["name".to_string(), "note".to_string()];
+        let rows = vec![vec!["a,b".to_string(), "say \"hi\"\nbye".to_string()]];
+        let text = write_csv(Some(header.as_slice()), &rows, &CsvOptions::csv());
+        assert_eq!(text, "name,note\r\n\"a,b\",\"say \"\"hi\"\"\nbye\"\r\n");
+        let text = write_csv(None, &rows, &CsvOptions::tsv().with_bom(true));
+        assert_eq!(text, "\u{feff}a,b\t\"say \"\"hi\"\"\nbye\"\r\n");
+    }
+
+    #[test]
+    fn read() {
+        let text = "\u{feff}name,size\r\n\"lib,rs\",1024\n\"a\"\"b\nc\",12\n";
+        let table = read_csv(text, &CsvOptions::csv());
+        assert_eq!(table.header, Some(vec!["name".to_string(), "size".to_string()]));
+        assert_eq!(table.rows[0], vec!["lib,rs", "1024"]);
+        assert_eq!(table.rows[1], vec!["a\"b\nc", "12"]);
+        let table = read_csv("1,2\n3,4", &CsvOptions::csv());
+        assert_eq!(table.header, None);
+        assert_eq!(table.rows.len(), 2);
+        // an empty quoted field at the end is kept
+        assert_eq!(parse_csv("a,\"\"", ','), vec![vec!["a", ""]]);
+        assert_eq!(parse_csv("a\n\"\"", ','), vec![vec!["a"], vec![""]]);
+    }
+}
@@ -0,0 +1,37 @@
+use std::collections::BTreeMap;
+
+use makepad_widgets::{ActionDefaultRef, DefaultNone};
+
+use crate::components::pagination::GPaginationChangedParam;
+
+use super::types::TableFilter;
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum GTableEvent {
+    /// a tree node has been expanded
+    Expanded(GTableTreeParam),
+    /// a tree node has been collapsed
+    Collapsed(GTableTreeParam),
+    /// a lazy tree node is expanded and no loader can provide the children,
+    /// call `GTableRef::set_children` when the children are ready
+    LoadChildren(GTableTreeParam),
+    /// the page of the table pagination has been changed by user
+    PageChanged(GPaginationChangedParam),
+    /// the column filters have been changed by user (apply or clear in the filter popup)
+    FiltersChanged(GTableFiltersParam),
+    None,
+}
+
+#[derive(Clone, Debug)]
+pub struct GTableTreeParam {
+    /// the node id
+    pub id: String,
+    /// index path of the node, see `TableFlatRow::path`
+    pub path: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GTableFiltersParam {
+    /// all active filters, key is the data column index
+    pub filters: BTreeMap<usize, TableFilter>,
+}
@@ -2,10 +2,13 @@ use makepad_widgets::*;
 
 use crate::{
     components::{
+        table::{
+            row::{GTableRowRef, GTableRowWidgetRefExt},
+            types::{FrozenBounds, TableFrozen},
+        },
         view::DrawState,
-        table::row::{GTableRowRef, GTableRowWidgetRefExt},
     },
-    shader::draw_view::DrawGView,
+    shader::{draw_view::DrawGView, manual::FilterMode},
     utils::{BoolToF32, ThemeColor}, widget_area,
 };
 
@@ -58,6 +61,9 @@ pub struct GTableHeader {
     pub draw_state: DrawStateWrap<DrawState>,
     #[live(true)]
     pub event_key: bool,
+    /// frozen state, synced from GTable (the scroll_x follows the body)
+    #[rust]
+    pub frozen: TableFrozen,
 }
 
 impl Widget for GTableHeader {
@@ -85,6 +91,9 @@ impl Widget for GTableHeader {
                     // is the child visible?
                     // true -> draw the child walk
                     if child.is_visible() {
+                        if !resumed {
+                            child.borrow_mut().map(|mut row| row.frozen = self.frozen);
+                        }
                         let walk = child.walk(cx);
                         // if resumed
                         if !resumed {
@@ -189,4 +198,90 @@ impl GTableHeader {
     widget_area! {
         area, draw_table_header
     }
+    /// get the frozen bounds of the first visible row
+    pub fn frozen_bounds(&self) -> Option<FrozenBounds> {
+        self.draw_order.iter().find_map(|id| {
+            self.children
+                .get(id)
+                .and_then(|row| row.borrow().and_then(|row| row.frozen_bounds))
+        })
+    }
+    /// the first row is the title row
+    fn title_row(&self) -> Option<&GTableRowRef> {
+        self.draw_order.first().and_then(|id| self.children.get(id))
+    }
+    /// get the titles from the first row
+    pub fn titles(&self) -> Vec<String> {
+        self.title_row()
+            .and_then(|row| row.borrow().map(|row| row.texts()))
+            .unwrap_or_default()
+    }
+    /// get the filter mode of each cell in the first row
+    pub fn filter_modes(&self) -> Vec<FilterMode> {
+        self.title_row()
+            .and_then(|row| {
+                row.borrow().map(|row| {
+                    (0..row.draw_order.len())
+                        .map(|index| {
+                            row.cell(index)
+                                .and_then(|cell| cell.borrow().map(|cell| cell.filter))
+                                .unwrap_or_default()
+                        })
+                        .collect()
+                })
+            })
+            .unwrap_or_default()
+    }
+    /// set the titles and filter modes of the first row, cells after `count` are hidden
+    pub fn set_titles(&mut self, cx: &mut Cx, titles: &[String], filters: &[FilterMode], count: usize) {
+        if let Some(mut row) = self.title_row().and_then(|row| row.borrow_mut()) {
+            row.set_cells(titles);
+            row.show_cells(count);
+            for (index, filter) in filters.iter().enumerate() {
+                if let Some(mut cell) = row.cell(index).and_then(|cell| cell.borrow_mut()) {
+                    cell.filter = *filter;
+                }
+            }
+        }
+        self.redraw(cx);
+    }
+    /// highlight the filter icons of the first row, `actives` is in draw order
+    pub fn set_filters_active(&mut self, cx: &mut Cx, actives: &[bool]) {
+        if let Some(row) = self.title_row().and_then(|row| row.borrow()) {
+            for (index, active) in actives.iter().enumerate() {
+                if let Some(mut cell) = row.cell(index).and_then(|cell| cell.borrow_mut()) {
+                    cell.set_filter_active(cx, *active);
+                }
+            }
+        }
+    }
+    /// ## Filter hit
+    /// check if the filter icon of a cell in the first row is clicked,
+    /// return the index (draw order) and the rect of the cell
+    pub fn filter_hit(&self, cx: &mut Cx, event: &Event) -> Option<(usize, Rect)> {
+        let row = self.title_row()?.borrow()?;
+        for index in 0..row.draw_order.len() {
+            let cell = match row.cell(index).and_then(|cell| cell.borrow()) {
+                Some(cell) => cell,
+                None => continue,
+            };
+            if !cell.visible || cell.filter == FilterMode::None {
+                continue;
+            }
+            if let Hit::FingerUp(e) = event.hits(cx, cell.area_filter()) {
+                if e.is_over {
+                    return Some((index, cell.area().rect(cx)));
+                }
+            }
+        }
+        None
+    }
+    pub fn redraw(&mut self, cx: &mut Cx) {
+        self.draw_table_header.redraw(cx);
+        for (_, child) in self.children.iter() {
+            child.borrow_mut().map(|mut child| {
+                child.redraw(cx);
+            });
+        }
+    }
 }
\ No newline at end of file
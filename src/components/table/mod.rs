@@ -1,29 +1,66 @@
 pub mod body;
 pub mod cell;
 pub mod column;
+pub mod csv;
+pub mod event;
 pub mod header;
 mod register;
 pub mod row;
+pub mod types;
 pub mod virt;
 
+use std::{collections::BTreeMap, path::Path};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
 use body::GTableBody;
+use csv::{read_csv, write_csv, CsvOptions};
+use event::*;
 use header::GTableHeader;
 use makepad_widgets::*;
 pub use register::register;
+use types::{
+    FrozenBounds, TableFilter, TableFrozen, TableNode, TablePage, TableSort, TableTree, TableView,
+};
 use virt::GVTableBody;
 
 use crate::{
-    shader::{draw_view::DrawGView, manual::ComponentMode},
+    components::{
+        button::GButtonWidgetExt,
+        checkbox::GCheckBoxWidgetRefExt,
+        drop_down::PopupMenuGlobal,
+        input::GInputWidgetExt,
+        pagination::{GPagination, GPaginationChangedParam},
+        popup::{GPopup, GPopupContainer},
+        view::GViewWidgetExt,
+    },
+    event_option, ref_event_option,
+    shader::{
+        draw_view::DrawGView,
+        manual::{ComponentMode, FilterMode, Position},
+    },
+    sync_theme,
     themes::Themes,
     utils::{BoolToF32, ThemeColor},
     widget_area,
 };
 
 live_design! {
+    import makepad_draw::shader::std::*;
     GTableBase = {{GTable}}{
         flow: Down,
         padding: 0.0,
         margin: 0.0,
+        draw_frozen_shadow: {
+            // 0.0: shadow on the right side of left frozen columns
+            // 1.0: shadow on the left side of right frozen columns
+            instance direction: 0.0
+            fn pixel(self) -> vec4 {
+                let x = mix(self.pos.x, 1.0 - self.pos.x, self.direction);
+                let alpha = self.shadow_color.a * (1.0 - x) * (1.0 - x);
+                return Pal::premultiply(vec4(self.shadow_color.rgb, alpha));
+            }
+        }
     }
 }
 
@@ -76,39 +113,142 @@ pub struct GTable {
     pub body_virtual: GVTableBody,
     #[live]
     pub mode: ComponentMode,
+    // pagination -----------------
+    #[live]
+    #[redraw]
+    #[find]
+    pub pagination: GPagination,
+    /// slice the rows by `pagination` and show it under the body.
+    /// Only the virtual body (`mode: Virtual`) is sliced, the rows of a real body are all drawn
+    #[live(false)]
+    pub pageable: bool,
+    /// the titles of the header in data order, used when the view changes column order
+    #[rust]
+    pub titles: Vec<String>,
+    // filter ---------------------
+    /// the popup to edit the filter of a column, it has `text_panel`, `values_panel`,
+    /// `range_panel` and `actions` (`clear`, `apply`) in its container
+    #[live]
+    pub filter_popup: Option<LivePtr>,
+    /// the checkbox template of each value in `values_panel`
+    #[live]
+    pub filter_value: Option<LivePtr>,
+    /// apply the filters to the data model (virtual mode) or hide the unmatched rows (real mode),
+    /// set false to filter the data yourself when `FiltersChanged` is emitted
+    #[live(true)]
+    pub local_filter: bool,
+    /// active filters, key is the data column index
+    #[rust]
+    pub filters: BTreeMap<usize, TableFilter>,
+    /// the filter modes of the header in data order
+    #[rust]
+    pub filter_modes: Vec<FilterMode>,
+    /// the data column and the header cell rect of the opened filter popup
+    #[rust]
+    filter_opened: Option<(usize, Rect)>,
+    #[rust(true)]
+    filter_redraw: bool,
+    // frozen ---------------------
+    /// the first N columns which are frozen when the body scrolls horizontally
+    #[live(0)]
+    pub frozen_left: usize,
+    /// the last N columns which are frozen when the body scrolls horizontally
+    #[live(0)]
+    pub frozen_right: usize,
+    #[live(8.0)]
+    pub frozen_shadow_width: f64,
+    #[live]
+    pub frozen_shadow_color: Option<Vec4>,
+    #[live]
+    pub draw_frozen_shadow: DrawGView,
+    /// last scroll position of the body, used to sync the header
+    #[rust]
+    pub scroll_pos: DVec2,
+    #[live(true)]
+    pub event_key: bool,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GTable {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
         self.draw_table.begin(cx, walk, self.layout);
+        // header is drawn outside of the body scroll, so it always stays pinned at the top
+        // only the horizontal scroll is synced from the body
+        let frozen = TableFrozen::new(self.frozen_left, self.frozen_right);
+        self.header.frozen = frozen.with_scroll(self.scroll_pos.x);
         let header_walk = self.header.walk(cx);
         let _ = self.header.draw_walk(cx, scope, header_walk);
         match self.mode {
             ComponentMode::Real => {
+                self.body.frozen = frozen;
                 let body_walk = self.body.walk(cx);
                 let _ = self.body.draw_walk(cx, scope, body_walk);
             }
             ComponentMode::Virtual => {
+                self.body_virtual.frozen = frozen;
                 let body_walk = self.body_virtual.walk(cx);
                 let _ = self.body_virtual.draw_walk(cx, scope, body_walk);
             }
         }
-
+        self.draw_frozen_shadows(cx);
+        if self.pageable {
+            let pagination_walk = self.pagination.walk(cx);
+            let _ = self.pagination.draw_walk(cx, scope, pagination_walk);
+        }
         self.draw_table.end(cx);
+        self.draw_filter_popup(cx, scope);
         DrawStep::done()
     }
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        match self.mode{
+        if self.filter_opened.is_some() {
+            self.handle_filter_popup(cx, event, scope);
+        }
+        if let Some((index, rect)) = self.header.filter_hit(cx, event) {
+            self.cache_header();
+            let column = self.data_column(index);
+            self.open_filter(cx, column, rect);
+        }
+        let scroll_pos = match self.mode {
             ComponentMode::Real => {
                 self.body.handle_event(cx, event, scope);
-            },
+                self.body.scroll_pos()
+            }
             ComponentMode::Virtual => {
                 self.body_virtual.handle_event(cx, event, scope);
-            },
-        }   
+                let uid = self.widget_uid();
+                let event_key = self.event_key;
+                self.body_virtual
+                    .handle_tree_event(cx, event, &mut |cx, action| {
+                        if event_key {
+                            cx.widget_action(uid, &scope.path, action);
+                        }
+                    });
+                self.body_virtual.scroll_pos()
+            }
+        };
+        if self.pageable {
+            let actions =
+                cx.capture_actions(|cx| self.pagination.handle_event(cx, event, scope));
+            if let Some(e) = self.pagination.page_changed(&actions) {
+                self.sync_page(cx);
+                if self.event_key {
+                    cx.widget_action(self.widget_uid(), &scope.path, GTableEvent::PageChanged(e));
+                }
+            }
+        }
+        // sync the header when body scrolls horizontally
+        if scroll_pos != self.scroll_pos {
+            if scroll_pos.x != self.scroll_pos.x {
+                self.header.redraw(cx);
+                self.draw_table.redraw(cx);
+            }
+            self.scroll_pos = scroll_pos;
+        }
     }
 
     fn is_visible(&self) -> bool {
@@ -117,10 +257,23 @@ impl Widget for GTable {
 }
 
 impl LiveHook for GTable {
-    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
         if !self.visible {
             return;
         }
+        if let (Some(popup), true) = (self.filter_popup, apply.from.is_from_doc()) {
+            let global = cx.global::<PopupMenuGlobal>().clone();
+            let mut global_map = global.map.borrow_mut();
+            global_map.retain(|k, _| cx.live_registry.borrow().generation_valid(*k));
+            global_map.get_or_insert(cx, popup, |cx| GPopup::new_from_ptr(cx, Some(popup)));
+        }
+        self.render(cx);
+        self.sync_page(cx);
+    }
+}
+
+impl GTable {
+    pub fn render(&mut self, cx: &mut Cx) {
         // ----------------- background color -------------------------------------------
         let bg_color = self.background_color.get(self.theme, 500);
         let shadow_color = self.shadow_color.get(self.theme, 700);
@@ -132,6 +285,13 @@ impl LiveHook for GTable {
         let border_color = self.border_color.get(self.theme, 600);
         // ------------------ is background_visible --------------------------------------------
         let background_visible = self.background_visible.to_f32();
+        let frozen_shadow_color = self.frozen_shadow_color.use_or("#10182826");
+        self.draw_frozen_shadow.apply_over(
+            cx,
+            live! {
+                shadow_color: (frozen_shadow_color),
+            },
+        );
         self.draw_table.apply_over(
             cx,
             live! {
@@ -150,12 +310,637 @@ impl LiveHook for GTable {
         );
         self.draw_table.redraw(cx);
     }
-}
-
-impl GTable {
     widget_area! {
         area, draw_table,
         area_header, header,
         area_body, body
     }
+    /// ## Draw frozen shadows
+    /// draw a shadow edge on the frozen columns when the scroll region is scrolled under them
+    fn draw_frozen_shadows(&mut self, cx: &mut Cx2d) {
+        if self.frozen_left == 0 && self.frozen_right == 0 {
+            return;
+        }
+        let (body_bounds, body_area, max_scroll_x) = match self.mode {
+            ComponentMode::Real => (
+                self.body.frozen_bounds(),
+                self.body.area(),
+                self.body.max_scroll_x(),
+            ),
+            ComponentMode::Virtual => (
+                self.body_virtual.frozen_bounds(),
+                self.body_virtual.area(),
+                self.body_virtual.max_scroll_x(),
+            ),
+        };
+        let bounds = match self.header.frozen_bounds().or(body_bounds) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let FrozenBounds { left, right } = bounds;
+        let rect = cx.turtle().rect();
+        let header = self.header.area().rect(cx);
+        let body = body_area.rect(cx);
+        let top = if self.header.visible { header.pos.y } else { body.pos.y };
+        let height = body.pos.y + body.size.y - top;
+        let size = dvec2(self.frozen_shadow_width, height.max(0.0));
+        let scroll_x = self.scroll_pos.x;
+
+        if self.frozen_left > 0 && scroll_x > 0.0 {
+            self.draw_frozen_shadow
+                .apply_over(cx, live! {direction: 0.0});
+            self.draw_frozen_shadow.draw_abs(
+                cx,
+                Rect {
+                    pos: dvec2(left, top),
+                    size,
+                },
+            );
+        }
+        if self.frozen_right > 0
+            && scroll_x < max_scroll_x
+            && right < rect.pos.x + rect.size.x
+        {
+            self.draw_frozen_shadow
+                .apply_over(cx, live! {direction: 1.0});
+            self.draw_frozen_shadow.draw_abs(
+                cx,
+                Rect {
+                    pos: dvec2(right - self.frozen_shadow_width, top),
+                    size,
+                },
+            );
+        }
+    }
+    /// ## Set frozen columns
+    /// - left: the first N columns
+    /// - right: the last N columns
+    pub fn set_frozen(&mut self, cx: &mut Cx, left: usize, right: usize) {
+        self.frozen_left = left;
+        self.frozen_right = right;
+        self.header.redraw(cx);
+        self.body.redraw(cx);
+        self.draw_table.redraw(cx);
+    }
+}
+
+impl GTable {
+    event_option! {
+        expanded: GTableEvent::Expanded => GTableTreeParam,
+        collapsed: GTableEvent::Collapsed => GTableTreeParam,
+        load_children: GTableEvent::LoadChildren => GTableTreeParam,
+        page_changed: GTableEvent::PageChanged => GPaginationChangedParam,
+        filters_changed: GTableEvent::FiltersChanged => GTableFiltersParam
+    }
+    /// ## Set tree data
+    /// set the hierarchical rows of the table, the table should be in `Virtual` mode
+    pub fn set_tree(&mut self, cx: &mut Cx, roots: Vec<TableNode>) {
+        self.body_virtual.set_tree(cx, roots);
+        self.sync_page(cx);
+    }
+    /// ## Sync page
+    /// update the total of pagination by the count of (filtered) root nodes and slice the body by current page
+    fn sync_page(&mut self, cx: &mut Cx) {
+        if !self.pageable {
+            self.body_virtual.set_page(cx, None);
+            return;
+        }
+        let total = self
+            .body_virtual
+            .view
+            .order(&self.body_virtual.tree.roots)
+            .len();
+        if self.pagination.total != total {
+            self.pagination.set_total(cx, total);
+        }
+        let page = TablePage::new(self.pagination.current, self.pagination.page_size);
+        self.body_virtual.set_page(cx, Some(page));
+    }
+    /// set current page (start from 1), the table should be `pageable`
+    pub fn set_page(&mut self, cx: &mut Cx, page: usize) {
+        self.pagination.set_current(cx, page);
+        self.sync_page(cx);
+    }
+    pub fn set_page_size(&mut self, cx: &mut Cx, page_size: usize) {
+        self.pagination.set_page_size(cx, page_size);
+        self.sync_page(cx);
+    }
+    /// set the children of a node, usually called after `LoadChildren` event
+    pub fn set_children(&mut self, cx: &mut Cx, path: &[usize], children: Vec<TableNode>) {
+        self.body_virtual.set_children(cx, path, children);
+    }
+    /// ## Set lazy loader
+    /// the loader is called when a lazy node is expanded at the first time
+    /// ```rust
+    /// table.set_loader(|node| {
+    ///     Some(read_dir(&node.id))
+    /// });
+    /// ```
+    pub fn set_loader<F>(&mut self, loader: F)
+    where
+        F: FnMut(&TableNode) -> Option<Vec<TableNode>> + 'static,
+    {
+        self.body_virtual.loader.replace(Box::new(loader));
+    }
+    pub fn expand_all(&mut self, cx: &mut Cx) {
+        self.body_virtual.expand_all(cx);
+    }
+    pub fn collapse_all(&mut self, cx: &mut Cx) {
+        self.body_virtual.collapse_all(cx);
+    }
+}
+
+impl GTable {
+    /// ## Set view
+    /// set the column order, visibility and sort, the header titles follow the column order.
+    /// A `Real` body draws the rows as declared (only filtered), the export follows the view in both modes.
+    /// The filters of the view are replaced by the table filters (see `set_filters`), which apply to both modes
+    pub fn set_view(&mut self, cx: &mut Cx, mut view: TableView) {
+        self.cache_header();
+        view.filters = if self.local_filter {
+            self.filters.clone()
+        } else {
+            BTreeMap::new()
+        };
+        let actives: Vec<bool> = (0..self.filter_modes.len())
+            .map(|column| self.filters.contains_key(&column))
+            .collect();
+        if let ComponentMode::Virtual = self.mode {
+            self.header.set_titles(
+                cx,
+                &view.project(&self.titles),
+                &view.project(&self.filter_modes),
+                view.column_count(),
+            );
+            self.header.set_filters_active(cx, &view.project(&actives));
+        } else {
+            self.header.set_filters_active(cx, &actives);
+            self.body.filter(cx, |row| view.matches(row));
+        }
+        self.body_virtual.set_view(cx, view);
+        self.sync_page(cx);
+    }
+    /// set the displayed columns (data column indexes in display order), empty shows all
+    pub fn set_columns(&mut self, cx: &mut Cx, columns: Vec<usize>) {
+        let view = TableView {
+            columns,
+            ..self.body_virtual.view.clone()
+        };
+        self.set_view(cx, view);
+    }
+    pub fn set_sort(&mut self, cx: &mut Cx, sort: Option<TableSort>) {
+        let view = TableView {
+            sort,
+            ..self.body_virtual.view.clone()
+        };
+        self.set_view(cx, view);
+    }
+    /// ## Set filters
+    /// key is the data column index, inactive filters are dropped
+    pub fn set_filters(&mut self, cx: &mut Cx, filters: BTreeMap<usize, TableFilter>) {
+        self.filters = filters
+            .into_iter()
+            .filter(|(_, filter)| filter.is_active())
+            .collect();
+        let view = self.body_virtual.view.clone();
+        self.set_view(cx, view);
+    }
+    /// set or clear (`None`) the filter of a data column
+    pub fn set_filter(&mut self, cx: &mut Cx, column: usize, filter: Option<TableFilter>) {
+        let mut filters = self.filters.clone();
+        match filter {
+            Some(filter) => filters.insert(column, filter),
+            None => filters.remove(&column),
+        };
+        self.set_filters(cx, filters);
+    }
+    /// the distinct values of a data column, used by the `Values` filter
+    pub fn column_values(&self, column: usize) -> Vec<String> {
+        match self.mode {
+            ComponentMode::Real => self.body_tree().column_values(column),
+            ComponentMode::Virtual => self.body_virtual.tree.column_values(column),
+        }
+    }
+    /// ## Get the rows of current view
+    /// the rows are filtered, sorted and projected by the view, pagination and collapse are ignored.
+    /// In `Real` mode the drawn rows are taken as the roots, so the export follows the view as well
+    pub fn view_rows(&self) -> Vec<Vec<String>> {
+        let view = &self.body_virtual.view;
+        match self.mode {
+            ComponentMode::Real => self.body_tree().view_rows(view),
+            ComponentMode::Virtual => self.body_virtual.tree.view_rows(view),
+        }
+    }
+    /// the drawn rows of a `Real` body as the roots of a data model
+    fn body_tree(&self) -> TableTree {
+        let roots = self
+            .body
+            .texts()
+            .into_iter()
+            .map(|cells| TableNode {
+                cells,
+                ..Default::default()
+            })
+            .collect();
+        TableTree::new(roots)
+    }
+    /// ## Export to text
+    /// export current view as csv or tsv (see `view_rows`)
+    /// ```rust
+    /// let text = table.export_text(&CsvOptions::tsv());
+    /// ```
+    pub fn export_text(&self, options: &CsvOptions) -> String {
+        let titles = if self.titles.is_empty() {
+            self.header.titles()
+        } else {
+            self.titles.clone()
+        };
+        let titles = self.body_virtual.view.project(&titles);
+        write_csv(Some(titles.as_slice()), &self.view_rows(), options)
+    }
+    pub fn export_to<P: AsRef<Path>>(&self, path: P, options: &CsvOptions) -> std::io::Result<()> {
+        std::fs::write(path, self.export_text(options))
+    }
+    /// ## Save as
+    /// open the system save dialog and export current view to the selected file,
+    /// return `None` if user cancels the dialog
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_as(&self, options: &CsvOptions) -> Option<std::io::Result<PathBuf>> {
+        let extension = options.extension();
+        let path = rfd::FileDialog::new()
+            .add_filter(extension, &[extension])
+            .set_file_name(format!("table.{}", extension))
+            .save_file()?;
+        Some(self.export_to(&path, options).map(|_| path))
+    }
+    /// ## Import from text
+    /// load csv or tsv text into the data model (each row is a root node, id is the row index).
+    /// If a header is found (or detected), it is set to the table header and returned
+    pub fn import_text(&mut self, cx: &mut Cx, text: &str, options: &CsvOptions) -> Option<Vec<String>> {
+        let table = read_csv(text, options);
+        let roots = table
+            .rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, cells)| TableNode {
+                id: index.to_string(),
+                cells,
+                ..Default::default()
+            })
+            .collect();
+        self.set_tree(cx, roots);
+        if let Some(header) = table.header.as_ref() {
+            self.titles = header.clone();
+            let view = self.body_virtual.view.clone();
+            self.set_view(cx, view);
+        }
+        table.header
+    }
+    pub fn import_from<P: AsRef<Path>>(
+        &mut self,
+        cx: &mut Cx,
+        path: P,
+        options: &CsvOptions,
+    ) -> std::io::Result<Option<Vec<String>>> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(self.import_text(cx, &text, options))
+    }
+}
+
+impl GTable {
+    /// keep the titles and filter modes of the header in data order before the view changes them
+    fn cache_header(&mut self) {
+        if self.titles.is_empty() {
+            self.titles = self.header.titles();
+        }
+        if self.filter_modes.is_empty() {
+            self.filter_modes = self.header.filter_modes();
+        }
+    }
+    /// the data column of a header cell (display order), the header only follows the view in `Virtual` mode
+    fn data_column(&self, index: usize) -> usize {
+        match self.mode {
+            ComponentMode::Real => index,
+            ComponentMode::Virtual => self
+                .body_virtual
+                .view
+                .columns
+                .get(index)
+                .copied()
+                .unwrap_or(index),
+        }
+    }
+    /// ## Open filter popup
+    /// fill the panel of the column filter mode with the current filter and show the popup under the header cell
+    fn open_filter(&mut self, cx: &mut Cx, column: usize, rect: Rect) {
+        let popup = match self.filter_popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        let mode = self.filter_modes.get(column).copied().unwrap_or_default();
+        let values = if let FilterMode::Values = mode {
+            self.column_values(column)
+        } else {
+            vec![]
+        };
+        let current = self.filters.get(&column).cloned();
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let container = match map.get_mut(&popup) {
+            Some(popup) => popup.get_mut(),
+            None => return,
+        };
+        for (id, panel_mode) in [
+            (id!(text_panel), FilterMode::Text),
+            (id!(values_panel), FilterMode::Values),
+            (id!(range_panel), FilterMode::Range),
+        ] {
+            if let Some(mut panel) = container.gview(id).borrow_mut() {
+                panel.visible = panel_mode == mode;
+            }
+        }
+        match mode {
+            FilterMode::Text => {
+                let text = match &current {
+                    Some(TableFilter::Text(text)) => text.clone(),
+                    _ => String::new(),
+                };
+                container.ginput(id!(text_panel.text)).set_text(&text);
+            }
+            FilterMode::Values => {
+                if let Some(mut panel) = container.gview(id!(values_panel)).borrow_mut() {
+                    panel.children.truncate(values.len());
+                    for (index, value) in values.into_iter().enumerate() {
+                        if panel.children.get(index).is_none() {
+                            let child = WidgetRef::new_from_ptr(cx, self.filter_value);
+                            panel.children.push((LiveId(index as u64), child));
+                        }
+                        // no filter means all values are selected
+                        let selected = match &current {
+                            Some(TableFilter::Values(values)) => values.contains(&value),
+                            _ => true,
+                        };
+                        if let Some(mut checkbox) = panel.children[index].1.as_gcheckbox().borrow_mut() {
+                            checkbox.text.as_mut_empty().push_str(&value);
+                            checkbox.value.replace(value);
+                            checkbox.toggle(cx, selected);
+                        }
+                    }
+                }
+            }
+            FilterMode::Range => {
+                let (min, max) = match &current {
+                    Some(TableFilter::Range { min, max }) => (*min, *max),
+                    _ => (None, None),
+                };
+                let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+                container.ginput(id!(range_panel.min)).set_text(&number(min));
+                container.ginput(id!(range_panel.max)).set_text(&number(max));
+            }
+            FilterMode::None => return,
+        }
+        container.redraw(cx);
+        self.filter_opened.replace((column, rect));
+        self.filter_redraw = true;
+        self.draw_table.redraw(cx);
+    }
+    fn close_filter(&mut self, cx: &mut Cx) {
+        if self.filter_opened.take().is_some() {
+            self.filter_redraw = true;
+            self.draw_table.redraw(cx);
+        }
+    }
+    /// read the filter from the opened panel, `None` if the filter is inactive
+    fn read_filter(container: &GPopupContainer, mode: FilterMode) -> Option<TableFilter> {
+        let filter = match mode {
+            FilterMode::Text => TableFilter::Text(container.ginput(id!(text_panel.text)).text()),
+            FilterMode::Values => {
+                let panel = container.gview(id!(values_panel));
+                let panel = panel.borrow()?;
+                let values: Vec<String> = panel
+                    .children
+                    .iter()
+                    .map(|(_, child)| child.as_gcheckbox())
+                    .filter(|checkbox| checkbox.is_selected())
+                    .filter_map(|checkbox| checkbox.value())
+                    .collect();
+                // all values selected is the same as no filter
+                if values.len() == panel.children.len() {
+                    return None;
+                }
+                TableFilter::Values(values)
+            }
+            FilterMode::Range => {
+                let number = |id: &[LiveId]| container.ginput(id).text().trim().parse::<f64>().ok();
+                TableFilter::Range {
+                    min: number(id!(range_panel.min)),
+                    max: number(id!(range_panel.max)),
+                }
+            }
+            FilterMode::None => return None,
+        };
+        Some(filter).filter(|filter| filter.is_active())
+    }
+    fn handle_filter_popup(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let (column, popup) = match (self.filter_opened, self.filter_popup) {
+            (Some((column, _)), Some(popup)) => (column, popup),
+            _ => return,
+        };
+        let mode = self.filter_modes.get(column).copied().unwrap_or_default();
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        let area = self.area();
+        let actions = cx.capture_actions(|cx| popup.handle_event_with(cx, event, scope, area));
+        let container = popup.get();
+        // Some(None) means clear the filter
+        let mut changed = None;
+        if container.gbutton(id!(actions.clear)).clicked(&actions).is_some() {
+            changed.replace(None);
+        }
+        if container.gbutton(id!(actions.apply)).clicked(&actions).is_some()
+            || container.ginput(id!(text_panel.text)).returned(&actions).is_some()
+        {
+            changed.replace(Self::read_filter(container, mode));
+        }
+        let outside = match event {
+            Event::MouseDown(e) => !popup.container_contains_pos(cx, e.abs),
+            _ => false,
+        };
+        drop(map);
+        if let Some(filter) = changed {
+            self.set_filter(cx, column, filter);
+            if self.event_key {
+                cx.widget_action(
+                    self.widget_uid(),
+                    &scope.path,
+                    GTableEvent::FiltersChanged(GTableFiltersParam {
+                        filters: self.filters.clone(),
+                    }),
+                );
+            }
+        }
+        if changed.is_some() || outside {
+            self.close_filter(cx);
+        }
+    }
+    /// draw the filter popup under the header cell, aligned to the right edge of the cell
+    fn draw_filter_popup(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        let (rect, popup) = match (self.filter_opened, self.filter_popup) {
+            (Some((_, rect)), Some(popup)) => (rect, popup),
+            _ => return,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        popup.begin(cx);
+        popup.draw_container(
+            cx,
+            scope,
+            Some(Position::BottomRight),
+            0.0,
+            &mut self.filter_redraw,
+        );
+        let container = popup.container_area().rect(cx);
+        let header = self.header.area().rect(cx);
+        let shift = dvec2(
+            rect.pos.x + rect.size.x - container.size.x - header.pos.x,
+            rect.pos.y + rect.size.y - header.pos.y + 4.0,
+        );
+        popup.end(cx, scope, self.header.area(), shift);
+    }
+}
+
+impl GTableRef {
+    ref_event_option! {
+        expanded => GTableTreeParam,
+        collapsed => GTableTreeParam,
+        load_children => GTableTreeParam,
+        page_changed => GPaginationChangedParam,
+        filters_changed => GTableFiltersParam
+    }
+    pub fn set_tree(&self, cx: &mut Cx, roots: Vec<TableNode>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_tree(cx, roots);
+        }
+    }
+    pub fn set_children(&self, cx: &mut Cx, path: &[usize], children: Vec<TableNode>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_children(cx, path, children);
+        }
+    }
+    pub fn set_loader<F>(&self, loader: F)
+    where
+        F: FnMut(&TableNode) -> Option<Vec<TableNode>> + 'static,
+    {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_loader(loader);
+        }
+    }
+    pub fn set_page(&self, cx: &mut Cx, page: usize) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_page(cx, page);
+        }
+    }
+    pub fn set_page_size(&self, cx: &mut Cx, page_size: usize) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_page_size(cx, page_size);
+        }
+    }
+    /// current page of the table pagination
+    pub fn page(&self) -> usize {
+        self.borrow().map(|c_ref| c_ref.pagination.current).unwrap_or(1)
+    }
+    pub fn expand_all(&self, cx: &mut Cx) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.expand_all(cx);
+        }
+    }
+    pub fn collapse_all(&self, cx: &mut Cx) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.collapse_all(cx);
+        }
+    }
+    pub fn set_frozen(&self, cx: &mut Cx, left: usize, right: usize) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_frozen(cx, left, right);
+        }
+    }
+    /// get the current scroll position of the table body
+    pub fn scroll_pos(&self) -> DVec2 {
+        if let Some(c_ref) = self.borrow() {
+            return match c_ref.mode {
+                ComponentMode::Real => c_ref.body.scroll_pos(),
+                ComponentMode::Virtual => c_ref.body_virtual.scroll_pos(),
+            };
+        }
+        DVec2::default()
+    }
+    pub fn set_view(&self, cx: &mut Cx, view: TableView) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_view(cx, view);
+        }
+    }
+    pub fn set_columns(&self, cx: &mut Cx, columns: Vec<usize>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_columns(cx, columns);
+        }
+    }
+    pub fn set_sort(&self, cx: &mut Cx, sort: Option<TableSort>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_sort(cx, sort);
+        }
+    }
+    pub fn set_filters(&self, cx: &mut Cx, filters: BTreeMap<usize, TableFilter>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_filters(cx, filters);
+        }
+    }
+    pub fn set_filter(&self, cx: &mut Cx, column: usize, filter: Option<TableFilter>) {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            c_ref.set_filter(cx, column, filter);
+        }
+    }
+    /// active filters, key is the data column index
+    pub fn filters(&self) -> BTreeMap<usize, TableFilter> {
+        self.borrow()
+            .map(|c_ref| c_ref.filters.clone())
+            .unwrap_or_default()
+    }
+    pub fn export_text(&self, options: &CsvOptions) -> String {
+        self.borrow()
+            .map(|c_ref| c_ref.export_text(options))
+            .unwrap_or_default()
+    }
+    pub fn export_to<P: AsRef<Path>>(&self, path: P, options: &CsvOptions) -> std::io::Result<()> {
+        if let Some(c_ref) = self.borrow() {
+            return c_ref.export_to(path, options);
+        }
+        Ok(())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_as(&self, options: &CsvOptions) -> Option<std::io::Result<PathBuf>> {
+        self.borrow().and_then(|c_ref| c_ref.save_as(options))
+    }
+    pub fn import_text(&self, cx: &mut Cx, text: &str, options: &CsvOptions) -> Option<Vec<String>> {
+        self.borrow_mut()
+            .and_then(|mut c_ref| c_ref.import_text(cx, text, options))
+    }
+    pub fn import_from<P: AsRef<Path>>(
+        &self,
+        cx: &mut Cx,
+        path: P,
+        options: &CsvOptions,
+    ) -> std::io::Result<Option<Vec<String>>> {
+        if let Some(mut c_ref) = self.borrow_mut() {
+            return c_ref.import_from(cx, path, options);
+        }
+        Ok(None)
+    }
 }
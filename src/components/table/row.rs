@@ -1,8 +1,15 @@
 use makepad_widgets::*;
 
-use crate::{components::view::DrawState, shader::draw_view::DrawGView};
+use crate::{
+    components::view::DrawState,
+    shader::draw_view::DrawGView,
+    themes::{scale_walk, size_scale, GSize},
+};
 
-use super::cell::{GTableCellRef, GTableCellWidgetRefExt};
+use super::{
+    cell::{GTableCellRef, GTableCellWidgetRefExt},
+    types::{walk_fixed_width, FrozenBounds, TableFrozen},
+};
 
 live_design! {
     GTableRowBase = {{GTableRow}}{
@@ -26,10 +33,25 @@ pub struct GTableRow {
     pub draw_state: DrawStateWrap<DrawState>,
     #[live(true)]
     pub visible: bool,
+    /// the size preset, scales the fixed height of the row
+    #[live]
+    pub size: GSize,
+    /// the height is already scaled by the parent (GVTableBody), the row does not scale it again
+    #[rust]
+    pub scaled: bool,
     #[rust]
     pub defer_walks: Vec<(LiveId, DeferWalk)>,
     #[live(true)]
     pub event_key: bool,
+    /// frozen state, set by GTableHeader or GTableBody before drawing
+    #[rust]
+    pub frozen: TableFrozen,
+    /// frozen bounds after drawing
+    #[rust]
+    pub frozen_bounds: Option<FrozenBounds>,
+    /// is the scroll region (middle cells) opened
+    #[rust]
+    scroll_region: bool,
 }
 
 impl Widget for GTableRow {
@@ -44,12 +66,22 @@ impl Widget for GTableRow {
             self.defer_walks.clear();
 
             // begin draw the view
+            let walk = if self.scaled {
+                walk
+            } else {
+                scale_walk(walk, size_scale(self.size))
+            };
             let _ = self.draw_table_row.begin(cx, walk, self.layout);
+            self.scroll_region = false;
+            self.frozen_bounds = None;
         }
 
         // loop handle the inner children
         while let Some(DrawState::Drawing(step, resumed)) = self.draw_state.get() {
             if step < self.draw_order.len() {
+                if !resumed {
+                    self.draw_frozen_region(cx, step);
+                }
                 // get id from draw_order list
                 let id = self.draw_order[step];
                 // get the child widget by id
@@ -68,6 +100,9 @@ impl Widget for GTableRow {
                 // set the next step
                 self.draw_state.set(DrawState::Drawing(step + 1, false));
             } else {
+                if self.scroll_region {
+                    self.end_scroll_region(cx);
+                }
                 self.draw_state.set(DrawState::DeferWalk(0));
             }
         }
@@ -137,6 +172,125 @@ impl LiveHook for GTableRow {
 }
 
 impl GTableRow {
+    /// ## Draw frozen region
+    /// The cells are split into three regions: `[left frozen] [scroll region] [right frozen]`,
+    /// only the scroll region moves with `scroll_x`. Without frozen columns all cells are in the scroll region.
+    /// If the row width is not known yet (`Fit`), the scroll region fits its cells and has no bounds.
+    /// This method is called before drawing the cell at `step`
+    fn draw_frozen_region(&mut self, cx: &mut Cx2d, step: usize) {
+        let len = self.draw_order.len();
+        let left = self.frozen.left.min(len);
+        let right_start = len.saturating_sub(self.frozen.right).max(left);
+
+        if step == left {
+            let left_width = self.cells_width(cx, 0..left);
+            let right_width = self.cells_width(cx, right_start..len);
+            let rect = cx.turtle().rect();
+            let width = if rect.size.x.is_nan() {
+                Size::Fit
+            } else {
+                let inner_width =
+                    rect.size.x - self.layout.padding.left - self.layout.padding.right;
+                let scroll_width = (inner_width - left_width - right_width).max(0.0);
+                let start_x = rect.pos.x + self.layout.padding.left + left_width;
+                self.frozen_bounds.replace(FrozenBounds {
+                    left: start_x,
+                    right: start_x + scroll_width,
+                });
+                Size::Fixed(scroll_width)
+            };
+            cx.begin_turtle(
+                Walk {
+                    width,
+                    height: Size::Fill,
+                    ..Default::default()
+                },
+                Layout {
+                    flow: Flow::Right,
+                    align: self.layout.align,
+                    spacing: self.layout.spacing,
+                    clip_x: true,
+                    clip_y: false,
+                    scroll: dvec2(self.frozen.scroll_x, 0.0),
+                    ..Default::default()
+                },
+            );
+            self.scroll_region = true;
+        }
+        if step == right_start && self.scroll_region {
+            self.end_scroll_region(cx);
+        }
+    }
+    fn end_scroll_region(&mut self, cx: &mut Cx2d) {
+        cx.end_turtle();
+        self.scroll_region = false;
+    }
+    /// sum the fixed width of cells in range
+    fn cells_width(&mut self, cx: &mut Cx, range: std::ops::Range<usize>) -> f64 {
+        let mut width = 0.0;
+        for id in &self.draw_order[range] {
+            if let Some(cell) = self.children.get_mut(id) {
+                if cell.is_visible() {
+                    width += walk_fixed_width(&cell.walk(cx));
+                }
+            }
+        }
+        width
+    }
+    /// ## Get the content width of the row
+    /// The sum of all visible cells which has fixed width, it is used by GTableBody to know the scroll width
+    pub fn content_width(&mut self, cx: &mut Cx) -> f64 {
+        let len = self.draw_order.len();
+        self.cells_width(cx, 0..len) + self.layout.padding.left + self.layout.padding.right
+    }
+    /// ## Set text of cells
+    /// Each cell should have a child named `text` (such as a GLabel), the text is set in draw order
+    pub fn set_cells(&mut self, cells: &[String]) {
+        for (id, text) in self.draw_order.iter().zip(cells.iter()) {
+            if let Some(cell) = self.children.get(id) {
+                cell.widget(id!(text)).set_text(text);
+            }
+        }
+    }
+    /// get text of cells (the `text` child of each cell) in draw order
+    pub fn texts(&self) -> Vec<String> {
+        self.draw_order
+            .iter()
+            .filter_map(|id| self.children.get(id))
+            .map(|cell| cell.widget(id!(text)).text())
+            .collect()
+    }
+    /// show the first `count` cells and hide the others, used when columns are hidden by `TableView`
+    pub fn show_cells(&mut self, count: usize) {
+        for (index, id) in self.draw_order.iter().enumerate() {
+            if let Some(mut cell) = self.children.get(id).and_then(|cell| cell.borrow_mut()) {
+                cell.visible = index < count;
+            }
+        }
+    }
+    /// set the left padding of the first cell, used by tree table to indent child rows
+    pub fn set_indent(&mut self, cx: &mut Cx, indent: f64) {
+        if let Some(cell) = self.draw_order.first().and_then(|id| self.children.get(id)) {
+            cell.apply_over(
+                cx,
+                live! {
+                    padding: {left: (indent)}
+                },
+            );
+        }
+    }
+    /// get the cell by draw order index
+    pub fn cell(&self, index: usize) -> Option<&GTableCellRef> {
+        self.draw_order.get(index).and_then(|id| self.children.get(id))
+    }
+    /// get the area of the cell by draw order index
+    pub fn cell_area(&self, index: usize) -> Area {
+        self.draw_order
+            .get(index)
+            .and_then(|id| self.children.get(id))
+            .map(|cell| cell.area())
+            .unwrap_or(Area::Empty)
+    }
     pub fn redraw(&mut self, cx:&mut Cx){
         self.draw_table_row.redraw(cx);
         for (_,cell) in self.children.iter() {
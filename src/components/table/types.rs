@@ -0,0 +1,526 @@
+use std::{cmp::Ordering, collections::BTreeMap, ops::Range};
+
+use makepad_widgets::*;
+
+/// # Frozen columns state
+/// The table pass this state to each row (header and body) before drawing,
+/// so that the row knows which cells should not move when the body scrolls horizontally.
+/// - `left`: the first N cells which are frozen at the left side
+/// - `right`: the last N cells which are frozen at the right side
+/// - `scroll_x`: current horizontal scroll of the table body
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TableFrozen {
+    pub left: usize,
+    pub right: usize,
+    pub scroll_x: f64,
+}
+
+impl TableFrozen {
+    pub fn new(left: usize, right: usize) -> Self {
+        Self {
+            left,
+            right,
+            scroll_x: 0.0,
+        }
+    }
+    pub fn with_scroll(mut self, scroll_x: f64) -> Self {
+        self.scroll_x = scroll_x;
+        self
+    }
+}
+
+/// # Frozen bounds
+/// The absolute x position of the frozen regions after a row has been drawn,
+/// GTable use it to draw the shadow edge of frozen columns
+/// - `left`: the right edge of left frozen region
+/// - `right`: the left edge of right frozen region
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrozenBounds {
+    pub left: f64,
+    pub right: f64,
+}
+
+/// get the fixed width of a walk (include margin), if the width is not fixed, return 0.0
+pub fn walk_fixed_width(walk: &Walk) -> f64 {
+    if let Size::Fixed(w) = walk.width {
+        w + walk.margin.left + walk.margin.right
+    } else {
+        0.0
+    }
+}
+
+/// # Table Node
+/// A node of the tree table data model, a node without children is a normal row
+/// - `id`: the unique key of the node, it will be back in events
+/// - `cells`: the text of each cell
+/// - `children`: the child rows
+/// - `expanded`: the children are shown or not
+/// - `lazy`: the node has children but they are not loaded yet (see `GTable::set_loader`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableNode {
+    pub id: String,
+    pub cells: Vec<String>,
+    pub children: Vec<TableNode>,
+    pub expanded: bool,
+    pub lazy: bool,
+}
+
+impl TableNode {
+    pub fn new(id: &str, cells: Vec<&str>) -> Self {
+        Self {
+            id: id.to_string(),
+            cells: cells.into_iter().map(|cell| cell.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+    pub fn with_children(mut self, children: Vec<TableNode>) -> Self {
+        self.children = children;
+        self
+    }
+    /// mark the node as lazy, children will be loaded when it is expanded
+    pub fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+    /// the node can be expanded (has children or children need to be loaded)
+    pub fn is_expandable(&self) -> bool {
+        !self.children.is_empty() || self.lazy
+    }
+}
+
+/// # Table Flat Row
+/// A visible row after the tree is flattened, the virtual body only draws these rows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableFlatRow {
+    /// index path from the roots, `[1, 0]` means the first child of the second root
+    pub path: Vec<usize>,
+    pub depth: usize,
+    pub expandable: bool,
+    pub expanded: bool,
+}
+
+/// # Table Page
+/// The pagination config of GTable (virtual mode).
+/// Rows are sliced by root nodes, so the children are always on the same page as their parent.
+/// - `current`: current page, start from 1
+/// - `page_size`: count of root nodes in a page, `0` means no slicing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TablePage {
+    pub current: usize,
+    pub page_size: usize,
+}
+
+impl TablePage {
+    pub fn new(current: usize, page_size: usize) -> Self {
+        Self { current, page_size }
+    }
+    /// the range of root nodes in this page
+    pub fn range(&self, total: usize) -> Range<usize> {
+        if self.page_size == 0 {
+            return 0..total;
+        }
+        let start = (self.current.max(1) - 1) * self.page_size;
+        start.min(total)..(start + self.page_size).min(total)
+    }
+}
+
+/// # Sort Order
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// # Table Sort
+/// Sort the rows by the cell text of a data column, siblings are sorted in each level of the tree
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableSort {
+    /// index of the data column (index in `TableNode::cells`)
+    pub column: usize,
+    pub order: SortOrder,
+}
+
+impl TableSort {
+    pub fn new(column: usize, order: SortOrder) -> Self {
+        Self { column, order }
+    }
+}
+
+/// compare two cells, numbers come before text, numbers are compared by value and the others by text
+/// (a total order, so it is safe for `sort_by`)
+pub fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>().ok(), b.trim().parse::<f64>().ok()) {
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// # Table Filter
+/// The filter of a data column, set from the filter popup of header cells
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableFilter {
+    /// the cell contains the text (case insensitive)
+    Text(String),
+    /// the cell is one of the values
+    Values(Vec<String>),
+    /// the cell is a number in the range (both sides are inclusive)
+    Range { min: Option<f64>, max: Option<f64> },
+}
+
+impl TableFilter {
+    /// an inactive filter matches all cells, it should be removed
+    pub fn is_active(&self) -> bool {
+        match self {
+            TableFilter::Text(text) => !text.is_empty(),
+            TableFilter::Values(_) => true,
+            TableFilter::Range { min, max } => min.is_some() || max.is_some(),
+        }
+    }
+    pub fn matches(&self, cell: &str) -> bool {
+        match self {
+            TableFilter::Text(text) => cell.to_lowercase().contains(&text.to_lowercase()),
+            TableFilter::Values(values) => values.iter().any(|value| value == cell),
+            TableFilter::Range { min, max } => match cell.trim().parse::<f64>() {
+                Ok(value) => {
+                    min.map_or(true, |min| value >= min) && max.map_or(true, |max| value <= max)
+                }
+                Err(_) => false,
+            },
+        }
+    }
+}
+
+/// # Table View
+/// How the data model is presented: column order, column visibility, filters and sort.
+/// Export (csv/tsv) uses the same view, so what you see is what you get.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableView {
+    /// data column index of each displayed column, hidden columns are not in the list,
+    /// empty means all columns in data order
+    pub columns: Vec<usize>,
+    pub sort: Option<TableSort>,
+    /// filters of data columns, key is the data column index
+    pub filters: BTreeMap<usize, TableFilter>,
+}
+
+impl TableView {
+    /// get the displayed cells from the data cells
+    pub fn project<T: Clone + Default>(&self, cells: &[T]) -> Vec<T> {
+        if self.columns.is_empty() {
+            return cells.to_vec();
+        }
+        self.columns
+            .iter()
+            .map(|column| cells.get(*column).cloned().unwrap_or_default())
+            .collect()
+    }
+    /// count of displayed columns, `usize::MAX` means all
+    pub fn column_count(&self) -> usize {
+        if self.columns.is_empty() {
+            usize::MAX
+        } else {
+            self.columns.len()
+        }
+    }
+    /// the cells match all filters
+    pub fn matches(&self, cells: &[String]) -> bool {
+        self.filters.iter().all(|(column, filter)| {
+            filter.matches(cells.get(*column).map_or("", |cell| cell.as_str()))
+        })
+    }
+    /// the node is kept by filters if itself or any of its children matches
+    pub fn keep(&self, node: &TableNode) -> bool {
+        self.matches(&node.cells) || node.children.iter().any(|child| self.keep(child))
+    }
+    /// get the indexes of nodes after filtering and sorting
+    pub fn order(&self, nodes: &[TableNode]) -> Vec<usize> {
+        let mut indexes: Vec<usize> = (0..nodes.len())
+            .filter(|index| self.filters.is_empty() || self.keep(&nodes[*index]))
+            .collect();
+        if let Some(TableSort { column, order }) = self.sort {
+            let cell = |index: usize| nodes[index].cells.get(column).map_or("", |cell| cell.as_str());
+            // stable sort, equal rows keep the data order
+            indexes.sort_by(|a, b| {
+                let ordering = compare_cells(cell(*a), cell(*b));
+                match order {
+                    SortOrder::Asc => ordering,
+                    SortOrder::Desc => ordering.reverse(),
+                }
+            });
+        }
+        indexes
+    }
+}
+
+/// # Table Tree
+/// The hierarchical data model of GTable (virtual mode)
+#[derive(Debug, Clone, Default)]
+pub struct TableTree {
+    pub roots: Vec<TableNode>,
+}
+
+impl TableTree {
+    pub fn new(roots: Vec<TableNode>) -> Self {
+        Self { roots }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+    /// flatten the tree to the visible rows (children of collapsed nodes are skipped)
+    pub fn flatten(&self) -> Vec<TableFlatRow> {
+        self.flatten_range(0..self.roots.len())
+    }
+    /// flatten the roots in range and their visible children, used by pagination
+    pub fn flatten_range(&self, range: Range<usize>) -> Vec<TableFlatRow> {
+        let view = TableView::default();
+        let mut rows = vec![];
+        for index in range {
+            if let Some(node) = self.roots.get(index) {
+                Self::flatten_node(node, &mut vec![index], &view, &mut rows);
+            }
+        }
+        rows
+    }
+    /// ## Flatten the tree with view
+    /// the siblings are sorted by the view, then the roots are sliced by the page
+    pub fn flatten_view(&self, view: &TableView, page: Option<TablePage>) -> Vec<TableFlatRow> {
+        let mut roots = view.order(&self.roots);
+        if let Some(page) = page {
+            roots = roots[page.range(roots.len())].to_vec();
+        }
+        let mut rows = vec![];
+        for index in roots {
+            Self::flatten_node(&self.roots[index], &mut vec![index], view, &mut rows);
+        }
+        rows
+    }
+    fn flatten_node(
+        node: &TableNode,
+        path: &mut Vec<usize>,
+        view: &TableView,
+        rows: &mut Vec<TableFlatRow>,
+    ) {
+        rows.push(TableFlatRow {
+            path: path.clone(),
+            depth: path.len() - 1,
+            expandable: node.is_expandable(),
+            expanded: node.expanded,
+        });
+        if node.expanded {
+            for index in view.order(&node.children) {
+                path.push(index);
+                Self::flatten_node(&node.children[index], path, view, rows);
+                path.pop();
+            }
+        }
+    }
+    /// get the displayed cells of all rows (children of collapsed nodes are included), used by export
+    pub fn view_rows(&self, view: &TableView) -> Vec<Vec<String>> {
+        fn walk(nodes: &[TableNode], view: &TableView, rows: &mut Vec<Vec<String>>) {
+            for index in view.order(nodes) {
+                rows.push(view.project(&nodes[index].cells));
+                walk(&nodes[index].children, view, rows);
+            }
+        }
+        let mut rows = vec![];
+        walk(&self.roots, view, &mut rows);
+        rows
+    }
+    /// get the unique values of a data column (sorted), used by the values filter
+    pub fn column_values(&self, column: usize) -> Vec<String> {
+        fn walk(nodes: &[TableNode], column: usize, values: &mut Vec<String>) {
+            for node in nodes {
+                if let Some(cell) = node.cells.get(column) {
+                    values.push(cell.to_string());
+                }
+                walk(&node.children, column, values);
+            }
+        }
+        let mut values = vec![];
+        walk(&self.roots, column, &mut values);
+        values.sort_by(|a, b| compare_cells(a, b));
+        values.dedup();
+        values
+    }
+    pub fn get(&self, path: &[usize]) -> Option<&TableNode> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.get(*first)?;
+        for index in rest {
+            node = node.children.get(*index)?;
+        }
+        Some(node)
+    }
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut TableNode> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.get_mut(*first)?;
+        for index in rest {
+            node = node.children.get_mut(*index)?;
+        }
+        Some(node)
+    }
+    /// toggle the node, return the new expanded state
+    pub fn toggle(&mut self, path: &[usize]) -> Option<bool> {
+        let node = self.get_mut(path)?;
+        if !node.is_expandable() {
+            return None;
+        }
+        node.expanded = !node.expanded;
+        Some(node.expanded)
+    }
+    /// set the children of a node (usually after lazy loading), the node will not be lazy anymore
+    pub fn set_children(&mut self, path: &[usize], children: Vec<TableNode>) -> bool {
+        if let Some(node) = self.get_mut(path) {
+            node.children = children;
+            node.lazy = false;
+            true
+        } else {
+            false
+        }
+    }
+    /// expand all nodes which have been loaded (lazy nodes are not loaded by this method)
+    pub fn expand_all(&mut self) {
+        Self::set_expanded(&mut self.roots, true);
+    }
+    pub fn collapse_all(&mut self) {
+        Self::set_expanded(&mut self.roots, false);
+    }
+    fn set_expanded(nodes: &mut Vec<TableNode>, expanded: bool) {
+        for node in nodes.iter_mut() {
+            if !node.children.is_empty() {
+                node.expanded = expanded;
+                Self::set_expanded(&mut node.children, expanded);
+            } else if !expanded {
+                node.expanded = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_tree {
+    use super::{
+        compare_cells, SortOrder, TableFilter, TableNode, TablePage, TableSort, TableTree,
+        TableView,
+    };
+
+    fn tree() -> TableTree {
+        TableTree::new(vec![
+            TableNode::new("src", vec!["src", "dir"]).with_children(vec![
+                TableNode::new("lib", vec!["lib.rs", "file"]),
+                TableNode::new("components", vec!["components", "dir"]).lazy(),
+            ]),
+            TableNode::new("readme", vec!["README.md", "file"]),
+        ])
+    }
+
+    #[test]
+    fn flatten() {
+        let mut tree = tree();
+        assert_eq!(tree.flatten().len(), 2);
+        assert_eq!(tree.toggle(&[0]), Some(true));
+        let rows = tree.flatten();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[2].path, vec![0, 1]);
+        assert_eq!(rows[2].depth, 1);
+        assert!(rows[2].expandable);
+        assert_eq!(tree.toggle(&[1]), None);
+    }
+
+    #[test]
+    fn expand_all() {
+        let mut tree = tree();
+        tree.expand_all();
+        assert_eq!(tree.flatten().len(), 4);
+        tree.set_children(&[0, 1], vec![TableNode::new("mod", vec!["mod.rs", "file"])]);
+        tree.expand_all();
+        assert_eq!(tree.flatten().len(), 5);
+        tree.collapse_all();
+        assert_eq!(tree.flatten().len(), 2);
+    }
+
+    #[test]
+    fn page() {
+        let mut tree = tree();
+        tree.expand_all();
+        let range = TablePage::new(2, 1).range(tree.roots.len());
+        assert_eq!(range, 1..2);
+        assert_eq!(tree.flatten_range(range).len(), 1);
+        assert_eq!(tree.flatten_range(0..1).len(), 3);
+        assert_eq!(TablePage::new(3, 1).range(2), 2..2);
+    }
+
+    #[test]
+    fn view() {
+        let mut tree = tree();
+        tree.expand_all();
+        let view = TableView {
+            columns: vec![1, 0],
+            sort: Some(TableSort::new(0, SortOrder::Asc)),
+            ..Default::default()
+        };
+        let rows = tree.flatten_view(&view, None);
+        assert_eq!(rows[0].path, vec![1]);
+        assert_eq!(rows[1].path, vec![0]);
+        assert_eq!(rows[2].path, vec![0, 1]);
+        let cells = &tree.get(&rows[0].path).unwrap().cells;
+        assert_eq!(view.project(cells), vec!["file", "README.md"]);
+        let rows = tree.flatten_view(&view, Some(TablePage::new(1, 1)));
+        assert_eq!(rows.len(), 1);
+    }
+
+    #[test]
+    fn sort_mixed() {
+        let mut cells = vec!["10", "a", "9", "", "NaN", "b2"];
+        cells.sort_by(|a, b| compare_cells(a, b));
+        assert_eq!(cells, vec!["9", "10", "NaN", "", "a", "b2"]);
+        cells.reverse();
+        cells.sort_by(|a, b| compare_cells(a, b));
+        assert_eq!(cells, vec!["9", "10", "NaN", "", "a", "b2"]);
+    }
+
+    #[test]
+    fn filter() {
+        let mut tree = tree();
+        tree.expand_all();
+        let mut view = TableView::default();
+        view.filters.insert(0, TableFilter::Text("LIB".to_string()));
+        let rows = tree.flatten_view(&view, None);
+        // the parent is kept because its child matches
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].path, vec![0, 0]);
+        view.filters.insert(1, TableFilter::Values(vec!["dir".to_string()]));
+        assert!(tree.flatten_view(&view, None).is_empty());
+        assert_eq!(tree.column_values(1), vec!["dir", "file"]);
+        let range = TableFilter::Range {
+            min: Some(1.0),
+            max: None,
+        };
+        assert!(range.matches("1.5") && !range.matches("0") && !range.matches("a"));
+    }
+
+    #[test]
+    fn filter_mixed() {
+        let tree = TableTree::new(
+            ["10", "a", "9", "", "NaN", "b2", "9"]
+                .iter()
+                .enumerate()
+                .map(|(index, cell)| TableNode::new(&index.to_string(), vec![*cell]))
+                .collect(),
+        );
+        assert_eq!(tree.column_values(0), vec!["9", "10", "NaN", "", "a", "b2"]);
+        let mut view = TableView::default();
+        view.filters.insert(
+            0,
+            TableFilter::Values(vec!["9".to_string(), "a".to_string()]),
+        );
+        let rows = tree.flatten_view(&view, None);
+        assert_eq!(rows.len(), 3);
+    }
+}
@@ -1,17 +1,48 @@
 use makepad_widgets::*;
 
-use crate::shader::draw_view::DrawGView;
+use crate::{
+    shader::{draw_icon_pixel::DrawGIconPixel, draw_view::DrawGView},
+    themes::{size_scale, GSize},
+    utils::ThemeColor,
+};
 
-use super::row::GTableRowRef;
+use super::{
+    event::{GTableEvent, GTableTreeParam},
+    row::{GTableRowRef, GTableRowWidgetRefExt},
+    types::{FrozenBounds, TableFlatRow, TableFrozen, TableNode, TablePage, TableTree, TableView},
+};
 
 live_design! {
+    import makepad_draw::shader::std::*;
     GVTableBodyBase = {{GVTableBody}}{
         padding: 0.0,
         margin: 0.0,
         flow: Down,
+        draw_chevron: {
+            instance expanded: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let c = self.rect_size * 0.5;
+                sdf.rotate(self.expanded * 1.5707963, c.x, c.y);
+                sdf.move_to(c.x - 2.0, c.y - 4.0);
+                sdf.line_to(c.x + 2.0, c.y);
+                sdf.line_to(c.x - 2.0, c.y + 4.0);
+                sdf.stroke(self.color, 1.2);
+                return sdf.result;
+            }
+        }
     }
 }
 
+/// A loader which provides the children of a lazy node.
+/// Return `None` if the children can not be provided now,
+/// then `GTableEvent::LoadChildren` will be called and you should use `set_children` later.
+pub type TableLoader = Box<dyn FnMut(&TableNode) -> Option<Vec<TableNode>>>;
+
+/// # GVTableBody
+/// The virtual body of GTable, rows are generated from the `TableTree` data model.
+/// Only the visible rows are drawn, and the row widgets are reused when scrolling.
+/// The row template is set by `row`, each cell of the template should have a `text` child.
 #[derive(Live, Widget)]
 pub struct GVTableBody {
     #[live(true)]
@@ -23,32 +54,198 @@ pub struct GVTableBody {
     pub walk: Walk,
     #[layout]
     pub layout: Layout,
+    /// row widgets, key is the index of the visible slot
     #[rust]
     pub children: ComponentMap<LiveId, GTableRowRef>,
+    /// row template
+    #[live]
+    pub row: Option<LivePtr>,
+    #[live(36.0)]
+    pub row_height: f64,
+    /// the size preset of the rows, scales `row_height`
+    #[live]
+    pub size: GSize,
+    /// indent of each depth in tree
+    #[live(16.0)]
+    pub indent: f64,
+    #[live(12.0)]
+    pub chevron_size: f64,
+    #[live]
+    pub chevron_color: Option<Vec4>,
+    #[live]
+    pub draw_chevron: DrawGIconPixel,
+    // scroll ---------------------
+    #[live]
+    pub scroll_bars: Option<LivePtr>,
+    #[rust]
+    pub scroll_bars_obj: Option<Box<ScrollBars>>,
+    // data -----------------------
+    #[rust]
+    pub tree: TableTree,
+    #[rust]
+    pub flat: Vec<TableFlatRow>,
+    #[rust]
+    pub loader: Option<TableLoader>,
+    /// only the roots in this page are shown, `None` shows all
+    #[rust]
+    pub page: Option<TablePage>,
+    /// column order, visibility and sort
+    #[rust]
+    pub view: TableView,
+    /// the visible chevrons, (rect, index of flat)
+    #[rust]
+    chevrons: Vec<(Rect, usize)>,
+    #[rust]
+    pub frozen: TableFrozen,
+    #[rust]
+    pub viewport_width: f64,
+    #[rust]
+    pub content_width: f64,
 }
 
 impl Widget for GVTableBody {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        self.draw_table_body.begin(cx, walk, self.layout);
-        for (_index, (_id, child)) in self.children.iter().enumerate() {
-            if child.is_visible() {
-                let child_walk = child.walk(cx);
-                let _ = child.draw_walk(cx, scope, child_walk);
+        if !self.visible {
+            return DrawStep::done();
+        }
+        let scroll = if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            scroll_bars.begin_nav_area(cx);
+            scroll_bars.get_scroll_pos()
+        } else {
+            self.layout.scroll
+        };
+        self.frozen.scroll_x = scroll.x;
+        self.draw_table_body
+            .begin(cx, walk, self.layout.with_scroll(dvec2(0.0, scroll.y)));
+        let viewport = cx.turtle().rect();
+        self.viewport_width = viewport.size.x;
+        self.content_width = 0.0;
+        self.chevrons.clear();
+
+        // only draw the rows in viewport, if height is `Fit`, draw all (GVTBody is `Fill`)
+        let total = self.flat.len();
+        let row_height = self.row_height();
+        let (start, end) = if viewport.size.y.is_nan() || row_height <= 0.0 {
+            (0, total)
+        } else {
+            let start = ((scroll.y / row_height).floor() as usize).min(total);
+            let count = (viewport.size.y / row_height).ceil() as usize + 1;
+            (start, (start + count).min(total))
+        };
+
+        cx.walk_turtle(Walk {
+            width: Size::Fixed(0.0),
+            height: Size::Fixed(start as f64 * row_height),
+            ..Default::default()
+        });
+
+        // the rows of the slots out of the range are dropped, so they do not get events
+        let slots = (end - start) as u64;
+        self.children.retain(|id, _| id.0 < slots);
+        if self.row.is_some() {
+            for (slot, index) in (start..end).enumerate() {
+                let row_ptr = self.row;
+                let row = self
+                    .children
+                    .get_or_insert(cx, LiveId(slot as u64), |cx| {
+                        WidgetRef::new_from_ptr(cx, row_ptr).as_gtable_row()
+                    })
+                    .clone();
+                let flat = &self.flat[index];
+                let indent = flat.depth as f64 * self.indent;
+                if let Some(node) = self.tree.get(&flat.path) {
+                    row.borrow_mut().map(|mut row| {
+                        row.frozen = self.frozen;
+                        row.scaled = true;
+                        row.set_cells(&self.view.project(&node.cells));
+                        row.show_cells(self.view.column_count());
+                        row.set_indent(cx, indent + self.chevron_size + 4.0);
+                        self.content_width = self.content_width.max(row.content_width(cx));
+                    });
+                }
+                let row_walk = Walk {
+                    height: Size::Fixed(row_height),
+                    ..row.walk(cx)
+                };
+                let _ = row.draw_walk(cx, scope, row_walk);
+
+                if flat.expandable {
+                    let cell = row
+                        .borrow()
+                        .map(|row| row.cell_area(0))
+                        .unwrap_or(Area::Empty)
+                        .rect(cx);
+                    let rect = Rect {
+                        pos: dvec2(
+                            cell.pos.x + indent + 4.0,
+                            cell.pos.y + (cell.size.y - self.chevron_size) * 0.5,
+                        ),
+                        size: dvec2(self.chevron_size, self.chevron_size),
+                    };
+                    let expanded = if flat.expanded { 1.0 } else { 0.0 };
+                    self.draw_chevron
+                        .apply_over(cx, live! {expanded: (expanded)});
+                    self.draw_chevron.draw_abs(cx, rect);
+                    self.chevrons.push((rect, index));
+                }
             }
         }
+
+        cx.walk_turtle(Walk {
+            width: Size::Fixed(self.content_width),
+            height: Size::Fixed((total - end) as f64 * row_height),
+            ..Default::default()
+        });
+
+        if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            scroll_bars.draw_scroll_bars(cx);
+        }
         self.draw_table_body.end(cx);
+        if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            scroll_bars.set_area(self.draw_table_body.area());
+            scroll_bars.end_nav_area(cx);
+        }
         DrawStep::done()
     }
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            let mut actions = Vec::new();
+            scroll_bars.handle_main_event(cx, event, scope, &mut actions);
+            if actions.len().gt(&0) {
+                self.redraw(cx);
+            }
+        }
         for (_index, (_id, child)) in self.children.iter().enumerate() {
             if child.is_visible() {
                 child.handle_event(cx, event, scope);
             }
         }
+        if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            scroll_bars.handle_scroll_event(cx, event, scope, &mut Vec::new());
+        }
+    }
+    fn is_visible(&self) -> bool {
+        self.visible
     }
 }
 
-impl LiveHook for GVTableBody {}
+impl LiveHook for GVTableBody {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if self.scroll_bars.is_some() {
+            if self.scroll_bars_obj.is_none() {
+                self.scroll_bars_obj =
+                    Some(Box::new(ScrollBars::new_from_ptr(cx, self.scroll_bars)));
+            }
+        }
+        let chevron_color = self.chevron_color.use_or("#667085");
+        self.draw_chevron.apply_over(
+            cx,
+            live! {
+                color: (chevron_color),
+            },
+        );
+    }
+}
 
 impl GVTableBody {
     pub fn redraw(&mut self, cx: &mut Cx) {
@@ -59,4 +256,116 @@ impl GVTableBody {
             });
         }
     }
+    /// the drawn height of a row, `row_height` scaled by the size and the density
+    pub fn row_height(&self) -> f64 {
+        self.row_height * size_scale(self.size)
+    }
+    pub fn scroll_pos(&self) -> DVec2 {
+        self.scroll_bars_obj
+            .as_ref()
+            .map(|scroll_bars| scroll_bars.get_scroll_pos())
+            .unwrap_or(self.layout.scroll)
+    }
+    pub fn max_scroll_x(&self) -> f64 {
+        (self.content_width - self.viewport_width).max(0.0)
+    }
+    pub fn frozen_bounds(&self) -> Option<FrozenBounds> {
+        self.children
+            .iter()
+            .find_map(|(_, row)| row.borrow().and_then(|row| row.frozen_bounds))
+    }
+    /// refresh the visible rows after the tree changed
+    pub fn refresh(&mut self, cx: &mut Cx) {
+        self.flat = self.tree.flatten_view(&self.view, self.page);
+        self.redraw(cx);
+    }
+    pub fn set_view(&mut self, cx: &mut Cx, view: TableView) {
+        self.view = view;
+        self.refresh(cx);
+    }
+    /// set the page and scroll back to the top
+    pub fn set_page(&mut self, cx: &mut Cx, page: Option<TablePage>) {
+        if self.page == page {
+            return;
+        }
+        self.page = page;
+        if let Some(scroll_bars) = &mut self.scroll_bars_obj {
+            scroll_bars.set_scroll_pos(cx, dvec2(self.frozen.scroll_x, 0.0));
+        }
+        self.refresh(cx);
+    }
+    pub fn set_tree(&mut self, cx: &mut Cx, roots: Vec<TableNode>) {
+        self.tree = TableTree::new(roots);
+        self.refresh(cx);
+    }
+    pub fn set_children(&mut self, cx: &mut Cx, path: &[usize], children: Vec<TableNode>) {
+        if self.tree.set_children(path, children) {
+            self.refresh(cx);
+        }
+    }
+    pub fn expand_all(&mut self, cx: &mut Cx) {
+        self.tree.expand_all();
+        self.refresh(cx);
+    }
+    pub fn collapse_all(&mut self, cx: &mut Cx) {
+        self.tree.collapse_all();
+        self.refresh(cx);
+    }
+    /// ## Toggle a node
+    /// if the node is lazy, the loader is called to get children,
+    /// if there is no loader or loader back None, `LoadChildren` event is dispatched
+    pub fn toggle(
+        &mut self,
+        cx: &mut Cx,
+        path: &[usize],
+        dispatch_action: &mut dyn FnMut(&mut Cx, GTableEvent),
+    ) {
+        let expanded = match self.tree.toggle(path) {
+            Some(expanded) => expanded,
+            None => return,
+        };
+        let node = self.tree.get(path).unwrap();
+        let param = GTableTreeParam {
+            id: node.id.to_string(),
+            path: path.to_vec(),
+        };
+        if expanded && node.lazy {
+            let children = self.loader.as_mut().and_then(|loader| loader(node));
+            if let Some(children) = children {
+                self.tree.set_children(path, children);
+            } else {
+                dispatch_action(cx, GTableEvent::LoadChildren(param.clone()));
+            }
+        }
+        self.refresh(cx);
+        dispatch_action(
+            cx,
+            if expanded {
+                GTableEvent::Expanded(param)
+            } else {
+                GTableEvent::Collapsed(param)
+            },
+        );
+    }
+    /// handle the tree event (chevron clicked), this should be called after `handle_event`
+    pub fn handle_tree_event(
+        &mut self,
+        cx: &mut Cx,
+        event: &Event,
+        dispatch_action: &mut dyn FnMut(&mut Cx, GTableEvent),
+    ) {
+        if let Hit::FingerUp(e) = event.hits(cx, self.draw_table_body.area()) {
+            if !e.is_over {
+                return;
+            }
+            let target = self
+                .chevrons
+                .iter()
+                .find(|(rect, _)| rect.contains(e.abs))
+                .map(|(_, index)| self.flat[*index].path.clone());
+            if let Some(path) = target {
+                self.toggle(cx, &path, dispatch_action);
+            }
+        }
+    }
 }
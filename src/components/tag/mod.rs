@@ -12,9 +12,14 @@ use crate::{
     active_event, animatie_fn, check_event_scope, default_handle_animation,
     default_hit_finger_down, default_hit_hover_in, default_hit_hover_out, event_option,
     play_animation, ref_area, ref_area_ext, ref_event_option, ref_redraw, ref_render, set_event,
-    set_scope_path, set_text_and_visible_fn, widget_area,
+    set_scope_path, set_text_and_visible_fn, sync_theme, widget_area,
+};
+use crate::{
+    shader::draw_view::DrawGView,
+    themes::{
+        contrast_border, scale_layout, scale_walk, size_scale, token_or, GSize, GTheme, Themes,
+    },
 };
-use crate::{shader::draw_view::DrawGView, themes::Themes};
 use makepad_widgets::*;
 
 live_design! {
@@ -85,10 +90,19 @@ live_design! {
     }
 }
 
+/// the props the tokens fill in keep the same defaults as `GButton`
+const BORDER_WIDTH: f32 = 0.0;
+const BORDER_RADIUS: f32 = 2.0;
+const SPREAD_RADIUS: f32 = 0.0;
+const BLUR_RADIUS: f32 = 4.8;
+
 #[derive(Live, Widget)]
 pub struct GTag {
     #[live]
     pub theme: Themes,
+    /// the size preset
+    #[live]
+    pub size: GSize,
     #[live]
     pub background_color: Option<Vec4>,
     #[live(true)]
@@ -107,16 +121,16 @@ pub struct GTag {
     pub text_focus_color: Option<Vec4>,
     #[live]
     pub border_color: Option<Vec4>,
-    #[live(0.0)]
-    pub border_width: f32,
-    #[live(2.0)]
-    pub border_radius: f32,
+    #[live]
+    pub border_width: Option<f32>,
+    #[live]
+    pub border_radius: Option<f32>,
     #[live]
     pub shadow_color: Option<Vec4>,
-    #[live(0.0)]
-    pub spread_radius: f32,
-    #[live(4.8)]
-    pub blur_radius: f32,
+    #[live]
+    pub spread_radius: Option<f32>,
+    #[live]
+    pub blur_radius: Option<f32>,
     #[live]
     pub shadow_offset: Vec2,
     // text ----------------------------
@@ -188,10 +202,13 @@ pub struct GTag {
     pub event_key: bool,
     #[rust]
     pub scope_path: Option<HeapLiveIdPath>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 impl Widget for GTag {
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
@@ -199,10 +216,13 @@ impl Widget for GTag {
         let font = get_font_family(&self.font_family, cx);
         self.draw_text.text_style.font = font;
 
-        self.icon_walk.height = Size::Fixed(self.font_size);
-        self.icon_walk.width = Size::Fixed(self.font_size);
+        let scale = size_scale(self.size);
+        self.icon_walk.height = Size::Fixed(self.font_size * scale);
+        self.icon_walk.width = Size::Fixed(self.font_size * scale);
         // self.text_walk.margin.top = self.font_size / 4.0;
-        let _ = self.draw_tag.begin(cx, walk, self.layout);
+        let _ = self
+            .draw_tag
+            .begin(cx, scale_walk(walk, scale), scale_layout(self.layout, scale));
         let _ = self.draw_icon.draw_walk(cx, self.icon_walk);
 
         let _ = self
@@ -441,20 +461,34 @@ impl GTag {
         let stroke_hover_color = self.stroke_hover_color.get(self.theme, 25);
         let stroke_focus_color = self.stroke_focus_color.get(self.theme, 100);
         let background_visible = self.background_visible.to_f32();
+        // ------------------ tokens (see `ThemeTokens`) ---------------------------------
+        let tokens = GTheme::tokens();
+        let border_width = token_or(self.border_width, BORDER_WIDTH, tokens.border_width);
+        let border_radius = token_or(self.border_radius, BORDER_RADIUS, tokens.border_radius);
+        let spread_radius = token_or(self.spread_radius, SPREAD_RADIUS, tokens.spread_radius);
+        let blur_radius = token_or(self.blur_radius, BLUR_RADIUS, tokens.blur_radius);
+        // ------------------ size (see `GSize`) -----------------------------------------
+        let scale = size_scale(self.size);
+        let border_radius = border_radius * scale as f32;
+        let font_size = self.font_size * scale;
+        // ------------------ contrast check (see `GTheme::set_contrast_check`) ------------
+        let fails = self.background_visible
+            && !GTheme::check_contrast("GTag", text_color, bg_color, font_size);
+        let (border_color, border_width) = contrast_border(fails, border_color, border_width);
         self.draw_tag.apply_over(
             cx,
             live! {
                 background_color: (bg_color),
                 background_visible: (background_visible),
                 border_color: (border_color),
-                border_width: (self.border_width),
-                border_radius: (self.border_radius),
+                border_width: (border_width),
+                border_radius: (border_radius),
                 focus_color: (focus_color),
                 hover_color: (hover_color),
                 shadow_color: (shadow_color),
                 shadow_offset: (self.shadow_offset),
-                spread_radius: (self.spread_radius),
-                blur_radius: (self.blur_radius)
+                spread_radius: (spread_radius),
+                blur_radius: (blur_radius)
             },
         );
         self.draw_icon.apply_over(
@@ -466,7 +500,7 @@ impl GTag {
                 brightness: (self.icon_brightness),
                 curve: (self.icon_curve),
                 linearize: (self.icon_linearize),
-                scale: (self.icon_scale),
+                scale: (self.icon_scale * scale),
                 draw_depth: (self.icon_draw_depth),
             },
         );
@@ -479,7 +513,7 @@ impl GTag {
                 stroke_hover_color: (text_hover_color),
                 stroke_focus_color: (text_focus_color),
                 text_style: {
-                    font_size: (self.font_size),
+                    font_size: (font_size),
                     // brightness: (default_text_style.brightness),
                     // curve: (default_text_style.curve),
                     // line_spacing: (self.line_spacing),
@@ -0,0 +1,212 @@
+mod register;
+
+pub use register::register;
+
+use std::{cell::RefCell, rc::Rc};
+
+use makepad_widgets::*;
+
+use crate::shader::manual::Position;
+
+use super::{
+    drop_down::{place, Placement, PlacementOptions, PopupMenuGlobal},
+    label::GLabelWidgetRefExt,
+    popup::GPopup,
+};
+
+live_design! {
+    GToolTipHostBase = {{GToolTipHost}}{}
+}
+
+/// The hovered area which has a `tooltip`, shared by the widgets and the host
+#[derive(Debug, Default)]
+pub struct ToolTipState {
+    pub target: Option<(Area, String)>,
+    pub changed: bool,
+}
+
+#[derive(Default, Clone)]
+pub struct GToolTipGlobal {
+    pub state: Rc<RefCell<ToolTipState>>,
+}
+
+/// # ToolTip Host
+/// Draw the `tooltip` text of GView/GButton with the popup (a `GToolTip` popup) in `PopupMenuGlobal`,
+/// the popup container should have a `text` label. `GWindow` has one.
+#[derive(Live, LiveRegister)]
+pub struct GToolTipHost {
+    #[live]
+    pub popup: Option<LivePtr>,
+    #[live]
+    pub position: Position,
+    #[live(6.0)]
+    pub offset: f32,
+    /// seconds to wait before showing
+    #[live(0.5)]
+    pub show_delay: f64,
+    #[rust]
+    pub target: Option<Area>,
+    #[rust]
+    pub opened: bool,
+    #[rust]
+    pub timer: Timer,
+    #[rust(true)]
+    pub redraw_flag: bool,
+    #[rust]
+    pub placement: Option<Placement>,
+}
+
+impl LiveHook for GToolTipHost {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if self.popup.is_none() || !apply.from.is_from_doc() {
+            return;
+        }
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut global_map = global.map.borrow_mut();
+        global_map.retain(|k, _| cx.live_registry.borrow().generation_valid(*k));
+        let popup = self.popup.unwrap();
+        global_map.get_or_insert(cx, popup, |cx| GPopup::new_from_ptr(cx, Some(popup)));
+    }
+}
+
+impl GToolTipHost {
+    /// show the tooltip of the area (after `show_delay`), call it when the pointer enters the area
+    pub fn show(cx: &mut Cx, area: Area, text: &str) {
+        let global = cx.global::<GToolTipGlobal>().clone();
+        let mut state = global.state.borrow_mut();
+        state.target.replace((area, text.to_string()));
+        state.changed = true;
+    }
+    /// hide the tooltip if it belongs to the area, call it when the pointer leaves the area
+    pub fn hide(cx: &mut Cx, area: Area) {
+        let global = cx.global::<GToolTipGlobal>().clone();
+        let mut state = global.state.borrow_mut();
+        if state.target.as_ref().map_or(false, |(target, _)| *target == area) {
+            state.target = None;
+            state.changed = true;
+        }
+    }
+    fn stop_timer(&mut self, cx: &mut Cx) {
+        if !self.timer.is_empty() {
+            cx.stop_timer(self.timer);
+            self.timer = Timer::empty();
+        }
+    }
+    fn set_text(&mut self, cx: &mut Cx, text: &str) {
+        let popup = match self.popup {
+            Some(popup) => popup,
+            None => return,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        if let Some(popup) = map.get_mut(&popup) {
+            popup.get_mut().glabel(id!(text)).set_text(text);
+        }
+    }
+    /// return true if the tooltip is shown or hidden (the window should redraw)
+    pub fn handle_event(&mut self, cx: &mut Cx, event: &Event) -> bool {
+        let mut redraw = false;
+        let global = cx.global::<GToolTipGlobal>().clone();
+        let mut state = global.state.borrow_mut();
+        if state.changed {
+            state.changed = false;
+            let target = state.target.clone();
+            drop(state);
+            self.stop_timer(cx);
+            match target {
+                Some((area, text)) => {
+                    self.set_text(cx, &text);
+                    self.target.replace(area);
+                    if self.opened {
+                        // move to the new target at once
+                        self.placement = None;
+                        redraw = true;
+                    } else {
+                        self.timer = cx.start_timeout(self.show_delay);
+                    }
+                }
+                None => {
+                    self.target = None;
+                    redraw = self.opened;
+                    self.opened = false;
+                }
+            }
+        } else {
+            drop(state);
+        }
+        if self.timer.is_event(event).is_some() {
+            self.timer = Timer::empty();
+            if self.target.is_some() {
+                self.opened = true;
+                self.placement = None;
+                redraw = true;
+            }
+        }
+        // any press hides the tooltip until the pointer enters again
+        if let Event::MouseDown(_) = event {
+            self.stop_timer(cx);
+            redraw = redraw || self.opened;
+            self.opened = false;
+        }
+        redraw
+    }
+    pub fn draw(&mut self, cx: &mut Cx2d, scope: &mut Scope) {
+        let (popup, target) = match (self.popup, self.target, self.opened) {
+            (Some(popup), Some(target), true) => (popup, target),
+            _ => return,
+        };
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        let popup = match map.get_mut(&popup) {
+            Some(popup) => popup,
+            None => return,
+        };
+        let anchor = target.rect(cx);
+        let (position, angle_offset) = self.placement.map_or(
+            (self.position, self.position.angle_offset(anchor.size)),
+            |placement| (placement.position, placement.angle_offset),
+        );
+        if self.placement.is_none() {
+            // shown or moved to a new target
+            popup.raise();
+        }
+        popup.begin(cx);
+        popup.draw_container(
+            cx,
+            scope,
+            Some(position),
+            angle_offset,
+            &mut self.redraw_flag,
+        );
+        let size = popup.container_area().rect(cx).size;
+        let viewport = Rect {
+            pos: DVec2::default(),
+            size: cx.current_pass_size(),
+        };
+        let placement = place(
+            self.position,
+            anchor,
+            size,
+            viewport,
+            &PlacementOptions {
+                offset: self.offset as f64,
+                offset_x: 0.0,
+                offset_y: 0.0,
+                flip: true,
+                shift: true,
+                constrain_height: false,
+                arrow: true,
+                padding: 8.0,
+            },
+        );
+        popup.end(cx, scope, target, placement.shift);
+        // the arrow is drawn before the size is known, see `GDropDown`
+        if self.placement.map_or(true, |last| {
+            last.position != placement.position || last.angle_offset != placement.angle_offset
+        }) {
+            self.redraw_flag = true;
+            cx.redraw_area(target);
+        }
+        self.placement.replace(placement);
+    }
+}
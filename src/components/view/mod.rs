@@ -12,17 +12,19 @@ use crate::{
     active_event, animatie_fn, event_option, play_animation, ref_area, ref_event_option,
     ref_redraw_mut, ref_render, set_event, set_scope_path,
     shader::draw_view::DrawGView,
-    themes::Themes,
+    sync_theme,
+    themes::{token_or, GTheme, Themes},
     utils::{set_cursor, BoolToF32, ThemeColor},
     widget_origin_fn,
 };
 
+use super::{label::GLabelWidgetRefExt, tooltip::GToolTipHost};
+
 live_design! {
     import makepad_draw::shader::std::*;
     GLOBAL_DURATION = 0.25
 
     GViewBase = {{GView}}{
-        spread_radius: 0.0,
         clip_x: false,
         clip_y: false,
         animator: {
@@ -98,13 +100,13 @@ live_design! {
 /// | live   | `hover_color`       | The color of the view on hover                   | `Option<Vec4>`     | `None`   |
 /// | live   | `focus_color`       | The color of the view on focus                   | `Option<Vec4>`     | `None`   |
 /// | live   | `border_color`      | The color of the view’s border                   | `Option<Vec4>`     | `None`   |
-/// | live   | `border_width`      | The width of the border                          | `f32`              | `0.0`    |
-/// | live   | `border_radius`     | The radius for rounded corners                   | `f32`              | `2.0`    |
+/// | live   | `border_width`      | The width of the border, `None` uses the token or `0.0` | `Option<f32>` | `None` |
+/// | live   | `border_radius`     | The radius for rounded corners, `None` uses the token or `2.0` | `Option<f32>` | `None` |
 /// | live   | `visible`           | Controls the visibility of the component         | `bool`             | `true`   |
 /// | live   | `background_visible`| Controls the visibility of the background        | `bool`             | `true`   |
 /// | live   | `shadow_color`      | The color of the shadow                          | `Option<Vec4>`     | `None`   |
-/// | live   | `spread_radius`     | The radius of the shadow spread                  | `f32`              | `4.8`    |
-/// | live   | `blur_radius`       | The radius of the shadow blur                    | `f32`              | `4.8`    |
+/// | live   | `spread_radius`     | The radius of the shadow spread, `None` uses the token or `0.0` | `Option<f32>` | `None` |
+/// | live   | `blur_radius`       | The radius of the shadow blur, `None` uses the token or `4.8` | `Option<f32>` | `None` |
 /// | live   | `shadow_offset`     | The offset of the shadow                         | `Vec2`             | `(0.0, 0.0)` |
 /// | live   | `cursor`            | The cursor to display when hovering over the view| `Option<MouseCursor>` | `None` |
 /// | live   | `animation_key`     | Boolean to enable animations                     | `bool`             | `false`  |
@@ -119,6 +121,12 @@ live_design! {
 /// | layout | `align`             | Alignment for content                           | `Align`            | `Align::default()` |
 /// | layout | `flow`              | Flow direction of the content                   | `Flow`             | `Flow::default()` |
 /// | layout | `spacing`           | Spacing between elements                        | `f64`              | `0.0`    |
+/// the built-in defaults of the border and the shadow when they are not set and no token is loaded
+const BORDER_WIDTH: f32 = 0.0;
+const BORDER_RADIUS: f32 = 2.0;
+const SPREAD_RADIUS: f32 = 0.0;
+const BLUR_RADIUS: f32 = 4.8;
+
 #[derive(Live, LiveRegisterWidget, WidgetRef, WidgetSet)]
 pub struct GView {
     #[live(Themes::Dark)]
@@ -131,20 +139,20 @@ pub struct GView {
     pub focus_color: Option<Vec4>,
     #[live]
     pub border_color: Option<Vec4>,
-    #[live(0.0)]
-    pub border_width: f32,
-    #[live(2.0)]
-    pub border_radius: f32,
+    #[live]
+    pub border_width: Option<f32>,
+    #[live]
+    pub border_radius: Option<f32>,
     #[live(true)]
     pub visible: bool,
     #[live(true)]
     pub background_visible: bool,
     #[live]
     pub shadow_color: Option<Vec4>,
-    #[live(4.8)]
-    pub spread_radius: f32,
-    #[live(4.8)]
-    pub blur_radius: f32,
+    #[live]
+    pub spread_radius: Option<f32>,
+    #[live]
+    pub blur_radius: Option<f32>,
     #[live]
     pub shadow_offset: Vec2,
     #[live]
@@ -156,6 +164,9 @@ pub struct GView {
     pub scroll_bars: Option<LivePtr>,
     #[rust]
     pub scroll_bars_obj: Option<Box<ScrollBars>>,
+    /// the height of the content in the last draw, it is bigger than the view when the view scrolls
+    #[rust]
+    pub content_height: f64,
     // control ---------------------
     #[live(true)]
     pub grab_key_focus: bool,
@@ -221,8 +232,13 @@ pub struct GView {
     pub fix_flag: bool,
     #[live(false)]
     pub block_child_events: bool,
+    /// a text shown in a tooltip when hovering, see `GToolTipHost`
+    #[live]
+    pub tooltip: String,
     #[rust]
     live_update_order: SmallVec<[LiveId;1]>,
+    #[rust]
+    pub theme_version: u64,
 }
 
 pub struct ViewTextureCache {
@@ -446,6 +462,9 @@ impl Widget for GView {
             Hit::FingerHoverIn(e) => {
                 let _ = set_cursor(cx, self.cursor.as_ref());
                 self.play_animation(cx, id!(hover.on));
+                if !self.tooltip.is_empty() {
+                    GToolTipHost::show(cx, self.area(), &self.tooltip);
+                }
                 self.active_hover_in(cx, e);
             }
             Hit::FingerHoverOver(e) => {
@@ -453,6 +472,9 @@ impl Widget for GView {
             }
             Hit::FingerHoverOut(e) => {
                 self.play_animation(cx, id!(hover.off));
+                if !self.tooltip.is_empty() {
+                    GToolTipHost::hide(cx, self.area());
+                }
                 self.active_hover_out(cx, e);
             }
             Hit::FingerUp(e) => {
@@ -475,6 +497,7 @@ impl Widget for GView {
         }
     }
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        sync_theme!(self, cx);
         if !self.visible {
             return DrawStep::done();
         }
@@ -610,6 +633,7 @@ impl Widget for GView {
                 }
                 self.draw_state.set(DrawState::DeferWalk(step + 1));
             } else {
+                self.content_height = cx.turtle().used().y;
                 if let Some(scroll_bars) = &mut self.scroll_bars_obj {
                     scroll_bars.draw_scroll_bars(cx);
                 }
@@ -764,6 +788,9 @@ impl Widget for GView {
             Hit::FingerHoverIn(e) => {
                 let _ = set_cursor(cx, self.cursor.as_ref());
                 self.play_animation(cx, id!(hover.on));
+                if !self.tooltip.is_empty() {
+                    GToolTipHost::show(cx, self.area(), &self.tooltip);
+                }
                 self.active_hover_in(cx, e);
             }
             Hit::FingerHoverOver(e) => {
@@ -771,6 +798,9 @@ impl Widget for GView {
             }
             Hit::FingerHoverOut(e) => {
                 self.play_animation(cx, id!(hover.off));
+                if !self.tooltip.is_empty() {
+                    GToolTipHost::hide(cx, self.area());
+                }
                 self.active_hover_out(cx, e);
             }
             Hit::FingerUp(e) => {
@@ -1003,6 +1033,19 @@ impl GView {
         let border_color = self.border_color.get(self.theme, 600);
         // ------------------ is background_visible --------------------------------------------
         let background_visible = self.background_visible.to_f32();
+        // ------------------ contrast check (see `GTheme::set_contrast_check`) ------------
+        // the labels of the view are checked on its background
+        for (_, child) in self.children.iter() {
+            if let Some(mut label) = child.as_glabel().borrow_mut() {
+                label.contrast_background = self.background_visible.then_some(bg_color);
+            }
+        }
+        // ------------------ tokens (see `ThemeTokens`) ---------------------------------
+        let tokens = GTheme::tokens();
+        let border_width = token_or(self.border_width, BORDER_WIDTH, tokens.border_width);
+        let border_radius = token_or(self.border_radius, BORDER_RADIUS, tokens.border_radius);
+        let spread_radius = token_or(self.spread_radius, SPREAD_RADIUS, tokens.spread_radius);
+        let blur_radius = token_or(self.blur_radius, BLUR_RADIUS, tokens.blur_radius);
         // ------------------ check scroll bar -------------------------------------------
         if self.scroll_bars.is_some() {
             if self.scroll_bars_obj.is_none() {
@@ -1017,14 +1060,14 @@ impl GView {
                 background_color: (bg_color),
                 background_visible: (background_visible),
                 border_color: (border_color),
-                border_width: (self.border_width),
-                border_radius: (self.border_radius),
+                border_width: (border_width),
+                border_radius: (border_radius),
                 focus_color: (focus_color),
                 hover_color: (hover_color),
                 shadow_color: (shadow_color),
                 shadow_offset: (self.shadow_offset),
-                spread_radius: (self.spread_radius),
-                blur_radius: (self.blur_radius)
+                spread_radius: (spread_radius),
+                blur_radius: (blur_radius)
             },
         );
         // self.draw_view.redraw(cx);
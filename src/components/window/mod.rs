@@ -9,9 +9,12 @@ use nav_control::NavControl;
 pub use register::register;
 
 use super::{
+    dialog::GDialogHost,
     image::GImageWidgetExt,
     label::GLabelWidgetExt,
+    notification::{center::GNotificationCenterHost, GNotificationHost},
     tool_btn::{types::GOsType, GToolButtonWidgetExt},
+    tooltip::GToolTipHost,
     view::{GView, GViewWidgetExt},
 };
 
@@ -103,6 +106,18 @@ pub struct GWindow {
     pub current_os: OsType,
     #[live(true)]
     pub event_key: bool,
+    /// shows the dialogs opened by `GDialog::alert/confirm/prompt`
+    #[live]
+    pub dialogs: GDialogHost,
+    /// shows the `tooltip` of GView/GButton
+    #[live]
+    pub tooltips: GToolTipHost,
+    /// shows the toasts pushed by `GNotification::push`
+    #[live]
+    pub notifications: GNotificationHost,
+    /// shows the history of `GNotification` in a drawer, see `GNotification::open_center`
+    #[live]
+    pub notification_center: GNotificationCenterHost,
 }
 
 impl Widget for GWindow {
@@ -112,6 +127,10 @@ impl Widget for GWindow {
             return DrawStep::done();
         }
         let _ = self.deref_widget.draw_walk(cx, scope, walk)?;
+        self.notification_center.draw(cx, scope);
+        self.notifications.draw(cx, scope);
+        self.tooltips.draw(cx, scope);
+        self.dialogs.draw(cx, scope);
         self.end(cx);
         DrawStep::done()
     }
@@ -195,7 +214,19 @@ impl Widget for GWindow {
             cx.widget_action(uid, &scope.path, WindowAction::EventForOtherWindow);
             return;
         } else {
+            self.dialogs.handle_event(cx, event, scope);
+            // the toasts are above the content, they take the finger first
+            if self.notifications.handle_event(cx, event, scope) {
+                self.main_draw_list.redraw(cx);
+            }
             self.deref_widget.handle_event(cx, event, scope);
+            // after the content, so the click which closes the drawer does not reach the bell
+            if self.notification_center.handle_event(cx, event, scope) {
+                self.main_draw_list.redraw(cx);
+            }
+            if self.tooltips.handle_event(cx, event) {
+                self.main_draw_list.redraw(cx);
+            }
         }
 
         if let Event::Actions(actions) = event {
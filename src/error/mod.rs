@@ -8,6 +8,10 @@ pub enum GError {
     ConflictHeight,
     /// called when widget width is fixed and bigger than max width or smaller than min width.
     ConflictWidth,
+    /// called when a color is not `#RRGGBB` or `#RRGGBBAA`.
+    InvalidColor,
+    /// called when a palette does not have exactly eleven levels (25 ~ 900).
+    PaletteLevels,
 }
 
 impl Error for GError {}
@@ -24,6 +28,43 @@ impl Display for GError {
             GError::ConflictWidth => f.write_str(
                 "Widget width is fixed and bigger than max width or smaller than min width.",
             ),
+            GError::InvalidColor => f.write_str("Invalid color, use `#RRGGBB` or `#RRGGBBAA`."),
+            GError::PaletteLevels => {
+                f.write_str("A palette needs eleven colors, from level 25 to level 900.")
+            }
+        }
+    }
+}
+
+/// # Token Error
+/// called when a design-tokens file cannot be loaded (see `ThemeTokens`),
+/// the tokens are named by their path, such as `color.brand.500`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenError {
+    /// the file cannot be read
+    Io(String),
+    /// the file is not valid JSON or TOML
+    Parse(String),
+    /// the token is not known by the loader (a typo or an unsupported token)
+    Unknown(String),
+    /// the token is required but not given
+    Missing(String),
+    /// the token has a wrong type or value
+    Invalid { token: String, expected: &'static str },
+}
+
+impl Error for TokenError {}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::Io(e) => write!(f, "Cannot read the tokens file: {}", e),
+            TokenError::Parse(e) => write!(f, "Cannot parse the tokens file: {}", e),
+            TokenError::Unknown(token) => write!(f, "Unknown token `{}`.", token),
+            TokenError::Missing(token) => write!(f, "Missing token `{}`.", token),
+            TokenError::Invalid { token, expected } => {
+                write!(f, "Invalid token `{}`, expected {}.", token, expected)
+            }
         }
     }
 }
@@ -31,10 +31,16 @@ pub fn live_design(cx: &mut Cx) {
     crate::components::input::register(cx);
     crate::components::popup::register(cx);
     crate::components::drop_down::register(cx);
+    crate::components::pagination::register(cx);
     crate::components::table::register(cx);
     crate::components::tool_btn::register(cx);
+    crate::components::dialog::register(cx);
+    crate::components::notification::register(cx);
+    crate::components::tooltip::register(cx);
     crate::components::window::register(cx);
     crate::components::select::register(cx);
+    crate::components::cascader::register(cx);
+    crate::components::context_menu::register(cx);
     crate::components::tabbar::register(cx);
     crate::components::router::register(cx);
     crate::components::menu::register(cx);
@@ -26,7 +26,7 @@ use ui::UI;
 
 use crate::error::GError;
 
-#[derive(Live, LiveHook, Clone, Debug)]
+#[derive(Live, LiveHook, Clone, Debug, PartialEq)]
 #[live_ignore]
 #[repr(u32)]
 pub enum IconType {
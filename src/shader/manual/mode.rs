@@ -29,6 +29,8 @@ pub enum TriggerMode {
     Click = shader_enum(1),
     Hover = shader_enum(2),
     Press = shader_enum(3),
+    /// secondary mouse button down
+    RightClick = shader_enum(4),
 }
 
 impl TriggerMode{
@@ -41,6 +43,9 @@ impl TriggerMode{
     pub fn is_press(&self) -> bool {
         matches!(self, TriggerMode::Press)
     }
+    pub fn is_right_click(&self) -> bool {
+        matches!(self, TriggerMode::RightClick)
+    }
 }
 
 /// Popup Close Mode
@@ -52,8 +57,11 @@ pub enum CloseMode {
     Virtual = shader_enum(1),
     #[pick]
     /// Only Outer Can Close Popup, always use when you have no close button in the popup
+    /// (Escape also closes the modal popup: Dialog, Drawer)
+    /// a click in a popup opened from this popup is not outer, see `PopupStack`
     Out = shader_enum(2),
-    
+    /// Only Escape can close the popup, click the outer does nothing
+    Escape = shader_enum(3),
 }
 
 impl Default for CloseMode {
@@ -62,6 +70,15 @@ impl Default for CloseMode {
     }
 }
 
+impl CloseMode {
+    pub fn close_on_out(&self) -> bool {
+        matches!(self, CloseMode::Out)
+    }
+    pub fn close_on_escape(&self) -> bool {
+        matches!(self, CloseMode::Out | CloseMode::Escape)
+    }
+}
+
 
 
 /// The `ComponentMode` enum represents the different modes for a component
@@ -74,6 +91,27 @@ pub enum ComponentMode {
     Virtual = shader_enum(2),
 }
 
+/// The `FilterMode` enum represents the filter affordance of a table header cell
+/// - Text: filter by the text contained in the cell
+/// - Values: filter by a checklist of the values in the column
+/// - Range: filter by a numeric range
+#[derive(Live, LiveHook, PartialEq, Eq, Clone, Copy, Debug)]
+#[live_ignore]
+#[repr(u32)]
+pub enum FilterMode {
+    #[pick]
+    None = shader_enum(1),
+    Text = shader_enum(2),
+    Values = shader_enum(3),
+    Range = shader_enum(4),
+}
+
+impl Default for FilterMode {
+    fn default() -> Self {
+        FilterMode::None
+    }
+}
+
 /// The `UploadMode` enum represents the different modes for uploading
 #[derive(Live, LiveHook, PartialEq, Eq, Clone, Copy, Debug)]
 #[live_ignore]
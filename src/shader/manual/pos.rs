@@ -29,7 +29,7 @@ pub enum Position4 {
     Bottom,
 }
 
-#[derive(Copy, Clone, Debug, Live, LiveHook)]
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
 #[live_ignore]
 #[repr(u32)]
 pub enum Position {
@@ -71,4 +71,43 @@ impl Position {
             | Position::BottomRight => (size.x / 2.0) as f32,
         }
     }
+    /// is the popup placed above or below the anchor
+    pub fn is_vertical(&self) -> bool {
+        matches!(
+            self,
+            Position::Top
+                | Position::TopLeft
+                | Position::TopRight
+                | Position::Bottom
+                | Position::BottomLeft
+                | Position::BottomRight
+        )
+    }
+    /// the opposite side, the alignment is kept
+    pub fn flip(&self) -> Self {
+        match self {
+            Position::Left => Position::Right,
+            Position::LeftTop => Position::RightTop,
+            Position::LeftBottom => Position::RightBottom,
+            Position::Right => Position::Left,
+            Position::RightTop => Position::LeftTop,
+            Position::RightBottom => Position::LeftBottom,
+            Position::Top => Position::Bottom,
+            Position::TopLeft => Position::BottomLeft,
+            Position::TopRight => Position::BottomRight,
+            Position::Bottom => Position::Top,
+            Position::BottomLeft => Position::TopLeft,
+            Position::BottomRight => Position::TopRight,
+        }
+    }
+    /// the start aligned position of the same side,
+    /// the angle offset of it is counted from the left/top edge
+    pub fn to_start(&self) -> Self {
+        match self {
+            Position::Left | Position::LeftTop | Position::LeftBottom => Position::LeftTop,
+            Position::Right | Position::RightTop | Position::RightBottom => Position::RightTop,
+            Position::Top | Position::TopLeft | Position::TopRight => Position::TopLeft,
+            Position::Bottom | Position::BottomLeft | Position::BottomRight => Position::BottomLeft,
+        }
+    }
 }
@@ -1,8 +1,18 @@
-use std::fmt::Display;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
 use makepad_widgets::*;
 
-use crate::color_v_trait;
+use crate::{color_v_trait, error::GError};
+
+mod size;
+mod tokens;
+
+pub use size::*;
+pub use tokens::*;
 
 #[derive(Copy, Clone, Debug, Live, LiveHook)]
 #[live_ignore]
@@ -14,6 +24,9 @@ pub enum Themes {
     Warning,
     Success,
     Info,
+    /// a palette registered by `GTheme::register_palette`, `theme: Custom(brand)`
+    #[live(LiveId(0))]
+    Custom(LiveId),
 }
 
 impl Default for Themes {
@@ -31,34 +44,51 @@ impl ToLiveValue for Themes {
             Themes::Warning => LiveValue::BareEnum(live_id!(Warning)),
             Themes::Success => LiveValue::BareEnum(live_id!(Success)),
             Themes::Info => LiveValue::BareEnum(live_id!(Info)),
+            // a tuple variant is not a single value, set the `theme` field instead of applying it over
+            Themes::Custom(name) => LiveValue::Id(*name),
         }
     }
 }
 
 impl Themes {
+    /// the color of the level, `Dark` follows the theme mode (see `GTheme::set_mode`)
     pub fn get(&self, v: u32) -> Vec4 {
         match self {
+            Themes::Dark if GTheme::mode() == ThemeMode::Light => ThemeLight::v(v),
             Themes::Dark => ThemeDark::v(v),
             Themes::Primary => ThemePrimary::v(v),
             Themes::Error => ThemeError::v(v),
             Themes::Warning => ThemeWarning::v(v),
             Themes::Success => ThemeSuccess::v(v),
             Themes::Info => ThemeInfo::v(v),
+            // an unregistered palette falls back to Primary
+            Themes::Custom(name) => {
+                GTheme::palette(*name).map_or_else(|| ThemePrimary::v(v), |palette| palette.get(v))
+            }
         }
     }
+    /// the hex of a built-in palette, a `Custom` palette is generated at runtime
+    /// so it gives the fallback (Primary) hex, use `hex_string` for it
     pub fn hex(&self, v: u32) -> &'static str {
         match self {
+            Themes::Dark if GTheme::mode() == ThemeMode::Light => ThemeLight::hex(v),
             Themes::Dark => ThemeDark::hex(v),
-            Themes::Primary => ThemePrimary::hex(v),
+            Themes::Primary | Themes::Custom(_) => ThemePrimary::hex(v),
             Themes::Error => ThemeError::hex(v),
             Themes::Warning => ThemeWarning::hex(v),
             Themes::Success => ThemeSuccess::hex(v),
             Themes::Info => ThemeInfo::hex(v),
         }
     }
+    /// the hex of any palette, including a registered `Custom` one
+    pub fn hex_string(&self, v: u32) -> String {
+        match self {
+            Themes::Custom(_) => vec4_to_hex(self.get(v)),
+            _ => self.hex(v).to_string(),
+        }
+    }
     pub fn to_vec(&self) -> Vec<Vec4> {
-        let levels = vec![25, 50, 100, 200, 300, 400, 500, 600, 700, 800, 900];
-        levels.iter().map(|v| self.get(*v)).collect()
+        LEVELS.iter().map(|v| self.get(*v)).collect()
     }
 }
 
@@ -71,6 +101,221 @@ impl Display for Themes {
             Themes::Warning => write!(f, "Warning"),
             Themes::Success => write!(f, "Success"),
             Themes::Info => write!(f, "Info"),
+            Themes::Custom(name) => write!(f, "Custom({})", name),
+        }
+    }
+}
+
+/// # Theme Mode
+/// The whole app is dark or light, in `Light` mode the widgets with `theme: Dark`
+/// resolve their colors from `ThemeLight` instead of `ThemeDark`.
+/// The colors set explicitly (`background_color: #22272F`) are kept in both modes.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub fn toggle(&self) -> Self {
+        match self {
+            ThemeMode::Dark => ThemeMode::Light,
+            ThemeMode::Light => ThemeMode::Dark,
+        }
+    }
+}
+
+/// The global theme context, `version` changes every time the context is changed
+/// so the widgets know when to resolve their colors again
+#[derive(Debug, Clone, Default)]
+pub struct ThemeContext {
+    pub mode: ThemeMode,
+    /// the palettes of `Themes::Custom`
+    pub palettes: HashMap<LiveId, Palette>,
+    /// the default widget props of the loaded tokens file
+    pub tokens: WidgetTokens,
+    /// the size preset of all widgets, see `GTheme::set_density`
+    pub density: Density,
+    /// the contrast check of the debug mode, see `GTheme::set_contrast_check`
+    pub contrast_check: Option<WcagLevel>,
+    /// the failing (widget, text, background) combinations which are logged
+    pub contrast_failures: HashSet<(String, String, String)>,
+    pub version: u64,
+}
+
+thread_local! {
+    // colors are resolved where no `Cx` is at hand (`ThemeColor::get`),
+    // so the context lives beside the palettes instead of in `cx.global`
+    static THEME_CONTEXT: RefCell<ThemeContext> = RefCell::new(ThemeContext::default());
+}
+
+/// # Theme
+/// Switch the theme of all G-widgets at runtime:
+/// ```rust
+/// // a dark mode toggle
+/// GTheme::toggle_mode(cx);
+/// ```
+/// every widget resolves its colors again before its next draw (see `sync_theme!`)
+pub struct GTheme;
+
+impl GTheme {
+    pub fn mode() -> ThemeMode {
+        THEME_CONTEXT.with(|context| context.borrow().mode)
+    }
+    pub fn version() -> u64 {
+        THEME_CONTEXT.with(|context| context.borrow().version)
+    }
+    pub fn set_mode(cx: &mut Cx, mode: ThemeMode) {
+        if Self::mode() != mode {
+            Self::update(cx, |context| context.mode = mode);
+        }
+    }
+    pub fn toggle_mode(cx: &mut Cx) {
+        Self::set_mode(cx, Self::mode().toggle());
+    }
+    /// ## Register a palette
+    /// the widgets with `theme: Custom(name)` use it, a registered name is replaced
+    /// ```rust
+    /// GTheme::register_palette(cx, live_id!(brand), Palette::from_seed(hex_to_vec4("#7F56D9")));
+    /// ```
+    pub fn register_palette(cx: &mut Cx, name: LiveId, palette: Palette) {
+        Self::update(cx, |context| {
+            context.palettes.insert(name, palette);
+        });
+    }
+    pub fn unregister_palette(cx: &mut Cx, name: LiveId) {
+        Self::update(cx, |context| {
+            context.palettes.remove(&name);
+        });
+    }
+    pub fn palette(name: LiveId) -> Option<Palette> {
+        THEME_CONTEXT.with(|context| context.borrow().palettes.get(&name).copied())
+    }
+    /// ## Contrast check (debug mode)
+    /// GTag checks its text against its background (after the theme colors are resolved),
+    /// GLabel is checked on the background passed down by its parent (GButton, GView), a label without it is not checked,
+    /// a failing combination is logged once and GButton/GTag get a `CONTRAST_FLAG` border.
+    /// ```rust
+    /// GTheme::set_contrast_check(cx, Some(WcagLevel::AA));
+    /// ```
+    pub fn set_contrast_check(cx: &mut Cx, level: Option<WcagLevel>) {
+        Self::update(cx, |context| {
+            context.contrast_check = level;
+            context.contrast_failures.clear();
+        });
+    }
+    pub fn contrast_check() -> Option<WcagLevel> {
+        THEME_CONTEXT.with(|context| context.borrow().contrast_check)
+    }
+    /// return false if the contrast check is on and the text fails it, the failure is logged once
+    pub fn check_contrast(widget: &str, color: Vec4, background: Vec4, font_size: f64) -> bool {
+        let level = match Self::contrast_check() {
+            Some(level) => level,
+            None => return true,
+        };
+        let ratio = contrast_ratio(color, background);
+        let min_ratio = level.min_ratio(font_size >= LARGE_TEXT);
+        if ratio >= min_ratio {
+            return true;
+        }
+        let (color, background) = (vec4_to_hex(color), vec4_to_hex(background));
+        let is_new = THEME_CONTEXT.with(|context| {
+            context.borrow_mut().contrast_failures.insert((
+                widget.to_string(),
+                color.clone(),
+                background.clone(),
+            ))
+        });
+        if is_new {
+            log!(
+                "{}: text {} on {} has a contrast of {:.2}:1, WCAG {:?} needs {}:1",
+                widget,
+                color,
+                background,
+                ratio,
+                level,
+                min_ratio
+            );
+        }
+        false
+    }
+    /// change the context and redraw all widgets with the new colors
+    pub fn update<F>(cx: &mut Cx, f: F)
+    where
+        F: FnOnce(&mut ThemeContext),
+    {
+        Self::change(f);
+        cx.redraw_all();
+    }
+    /// change the context and bump the version
+    fn change<F>(f: F)
+    where
+        F: FnOnce(&mut ThemeContext),
+    {
+        THEME_CONTEXT.with(|context| {
+            let mut context = context.borrow_mut();
+            f(&mut context);
+            context.version += 1;
+        });
+    }
+}
+
+/// the levels of a theme
+pub const LEVELS: [u32; 11] = [25, 50, 100, 200, 300, 400, 500, 600, 700, 800, 900];
+
+/// # Palette
+/// The colors of the eleven levels (`LEVELS`) of a custom theme,
+/// given level by level or generated from one seed color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub levels: [Vec4; 11],
+}
+
+impl Palette {
+    pub fn new(levels: [Vec4; 11]) -> Self {
+        Self { levels }
+    }
+    /// the hex colors from level 25 to 900
+    pub fn from_hex(levels: &[&str]) -> Result<Self, GError> {
+        if levels.len() != LEVELS.len() {
+            return Err(GError::PaletteLevels);
+        }
+        let mut colors = [Vec4::default(); 11];
+        for (color, hex) in colors.iter_mut().zip(levels) {
+            *color = try_hex_to_vec4(hex)?;
+        }
+        Ok(Self::new(colors))
+    }
+    /// ## Generate from a seed
+    /// the seed is level 500, the other levels keep its hue and step the perceptual lightness
+    /// (OKLab) towards white and black, the chroma is reduced near both ends
+    pub fn from_seed(seed: Vec4) -> Self {
+        // how far each level goes from the seed towards white (negative: towards black)
+        const STEPS: [f32; 11] = [
+            0.96, 0.9, 0.76, 0.56, 0.36, 0.16, 0.0, -0.2, -0.42, -0.64, -0.82,
+        ];
+        const LIGHTEST: f32 = 0.985;
+        const DARKEST: f32 = 0.28;
+        let (l, a, b) = srgb_to_oklab(seed);
+        let mut levels = [seed; 11];
+        for (color, step) in levels.iter_mut().zip(STEPS) {
+            if step == 0.0 {
+                continue;
+            }
+            let (target, chroma) = if step > 0.0 {
+                (l + (LIGHTEST - l) * step, 1.0 - step * 0.85)
+            } else {
+                (l + (l - DARKEST) * step, 1.0 + step * 0.3)
+            };
+            *color = oklab_to_srgb_clipped(target, a * chroma, b * chroma, seed.w);
+        }
+        Self::new(levels)
+    }
+    pub fn get(&self, v: u32) -> Vec4 {
+        match LEVELS.iter().position(|level| *level == v) {
+            Some(index) => self.levels[index],
+            None => panic!("invalid target"),
         }
     }
 }
@@ -126,6 +371,31 @@ impl ThemeDark {
     pub const _900: &'static str = "#0f1115";
 }
 
+// -------- color-light ----------------------------------------
+// the counterpart of color-dark in `ThemeMode::Light`
+#[derive(Debug, Clone)]
+pub struct ThemeLight(Vec4);
+
+impl Default for ThemeLight {
+    fn default() -> Self {
+        Self(hex_to_vec4(Self::_500))
+    }
+}
+
+impl ThemeLight {
+    pub const _25: &'static str = "#667085";
+    pub const _50: &'static str = "#98A2B3";
+    pub const _100: &'static str = "#D0D5DD";
+    pub const _200: &'static str = "#E4E7EC";
+    pub const _300: &'static str = "#EAECF0";
+    pub const _400: &'static str = "#F2F4F7";
+    pub const _500: &'static str = "#FFFFFF";
+    pub const _600: &'static str = "#F9FAFB";
+    pub const _700: &'static str = "#F2F4F7";
+    pub const _800: &'static str = "#EAECF0";
+    pub const _900: &'static str = "#D0D5DD";
+}
+
 // -------- color-primary --------------------------------------
 // COLOR_PRIMARY_25 = #F5FEFF;
 // COLOR_PRIMARY_50 = #ECFDFF;
@@ -307,32 +577,176 @@ color_v_trait!(ThemeSuccess);
 color_v_trait!(ThemeWarning);
 color_v_trait!(ThemeInfo);
 color_v_trait!(ThemeDark);
+color_v_trait!(ThemeLight);
 
 pub fn hex_to_vec4(hex: &str) -> Vec4 {
+    try_hex_to_vec4(hex).unwrap_or_else(|_| panic!("invalid hex color: {}", hex))
+}
+
+/// parse `#RRGGBB` or `#RRGGBBAA`
+pub fn try_hex_to_vec4(hex: &str) -> Result<Vec4, GError> {
     // 去掉开头的 '#' 符号
     let hex = hex.trim_start_matches('#');
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return Err(GError::InvalidColor);
+    }
+    // 解析 RGBA 值
+    let channel = |index: usize| {
+        hex.get(index..index + 2)
+            .map_or(Ok(255), |channel| u8::from_str_radix(channel, 16))
+            .map(|channel| channel as f32 / 255.0)
+            .map_err(|_| GError::InvalidColor)
+    };
+    Ok(Vec4 {
+        x: channel(0)?,
+        y: channel(2)?,
+        z: channel(4)?,
+        w: channel(6)?,
+    })
+}
 
-    // 解析 RGB 值
-    let (r, g, b, a) = if hex.len() == 6 {
-        let r = u8::from_str_radix(&hex[0..2], 16).unwrap();
-        let g = u8::from_str_radix(&hex[2..4], 16).unwrap();
-        let b = u8::from_str_radix(&hex[4..6], 16).unwrap();
-        (r, g, b, 255)
-    } else if hex.len() == 8 {
-        let r = u8::from_str_radix(&hex[0..2], 16).unwrap();
-        let g = u8::from_str_radix(&hex[2..4], 16).unwrap();
-        let b = u8::from_str_radix(&hex[4..6], 16).unwrap();
-        let a = u8::from_str_radix(&hex[6..8], 16).unwrap();
-        (r, g, b, a)
+/// `#RRGGBB`, or `#RRGGBBAA` if it is not opaque
+pub fn vec4_to_hex(color: Vec4) -> String {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let hex = format!(
+        "#{:02X}{:02X}{:02X}",
+        channel(color.x),
+        channel(color.y),
+        channel(color.z)
+    );
+    if channel(color.w) == 255 {
+        hex
     } else {
-        panic!("invalid hex color: {}", hex);
-    };
+        format!("{}{:02X}", hex, channel(color.w))
+    }
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
 
+/// the OKLab (L, a, b) of a sRGB color
+pub fn srgb_to_oklab(color: Vec4) -> (f32, f32, f32) {
+    let (r, g, b) = (
+        srgb_to_linear(color.x),
+        srgb_to_linear(color.y),
+        srgb_to_linear(color.z),
+    );
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    (
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    )
+}
+
+/// the linear sRGB of an OKLab color, may be out of gamut
+fn oklab_to_linear(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    (
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    )
+}
+
+/// the sRGB of an OKLab color, the chroma is reduced until it is in gamut
+pub fn oklab_to_srgb_clipped(l: f32, a: f32, b: f32, alpha: f32) -> Vec4 {
+    let in_gamut = |(r, g, b): (f32, f32, f32)| {
+        [r, g, b].iter().all(|c| (-0.0001..=1.0001).contains(c))
+    };
+    let mut rgb = oklab_to_linear(l, a, b);
+    if !in_gamut(rgb) {
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..16 {
+            let mid = (low + high) / 2.0;
+            if in_gamut(oklab_to_linear(l, a * mid, b * mid)) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        rgb = oklab_to_linear(l, a * low, b * low);
+    }
     Vec4 {
-        x: r as f32 / 255.0,
-        y: g as f32 / 255.0,
-        z: b as f32 / 255.0,
-        w: a as f32 / 255.0,
+        x: linear_to_srgb(rgb.0.clamp(0.0, 1.0)),
+        y: linear_to_srgb(rgb.1.clamp(0.0, 1.0)),
+        z: linear_to_srgb(rgb.2.clamp(0.0, 1.0)),
+        w: alpha,
+    }
+}
+
+/// the font size from which the text is large for WCAG (18pt)
+pub const LARGE_TEXT: f64 = 18.0;
+/// the border color of a widget failing the contrast check
+pub const CONTRAST_FLAG: &str = "#FF00FF";
+
+/// # WCAG Level
+/// The minimum contrast ratio of text and its background
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WcagLevel {
+    /// 4.5:1, 3:1 for large text
+    #[default]
+    AA,
+    /// 7:1, 4.5:1 for large text
+    AAA,
+}
+
+impl WcagLevel {
+    pub fn min_ratio(&self, large_text: bool) -> f32 {
+        match (self, large_text) {
+            (WcagLevel::AA, false) => 4.5,
+            (WcagLevel::AA, true) => 3.0,
+            (WcagLevel::AAA, false) => 7.0,
+            (WcagLevel::AAA, true) => 4.5,
+        }
+    }
+}
+
+/// the WCAG relative luminance of a sRGB color, `0.0` (black) ~ `1.0` (white)
+pub fn relative_luminance(color: Vec4) -> f32 {
+    0.2126 * srgb_to_linear(color.x)
+        + 0.7152 * srgb_to_linear(color.y)
+        + 0.0722 * srgb_to_linear(color.z)
+}
+
+/// the WCAG contrast ratio (`1.0` ~ `21.0`) of a text color on a background,
+/// a translucent text is blended over the background first
+pub fn contrast_ratio(color: Vec4, background: Vec4) -> f32 {
+    let alpha = color.w.clamp(0.0, 1.0);
+    let blend = |c: f32, b: f32| c * alpha + b * (1.0 - alpha);
+    let color = Vec4 {
+        x: blend(color.x, background.x),
+        y: blend(color.y, background.y),
+        z: blend(color.z, background.z),
+        w: 1.0,
+    };
+    let (a, b) = (relative_luminance(color), relative_luminance(background));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// the border of a widget, or the `CONTRAST_FLAG` border if it fails the contrast check
+pub fn contrast_border(fails: bool, color: Vec4, width: f32) -> (Vec4, f32) {
+    if fails {
+        (hex_to_vec4(CONTRAST_FLAG), width.max(2.0))
+    } else {
+        (color, width)
     }
 }
 
@@ -341,23 +755,129 @@ pub fn get_color(theme: Themes, color: Option<&Vec4>, v: u32) -> Vec4 {
     return if let Some(target) = color {
         target.clone()
     } else {
-        match theme {
-            Themes::Dark => ThemeDark::v(v),
-            Themes::Primary => ThemePrimary::v(v),
-            Themes::Error => ThemeError::v(v),
-            Themes::Warning => ThemeWarning::v(v),
-            Themes::Success => ThemeSuccess::v(v),
-            Themes::Info => ThemeInfo::v(v),
-        }
+        theme.get(v)
     };
 }
 
 #[cfg(test)]
 mod test_themes {
-    use crate::themes::hex_to_vec4;
+    use makepad_widgets::{LiveId, Vec4};
+
+    use crate::{
+        sync_theme,
+        themes::{
+            contrast_ratio, hex_to_vec4, srgb_to_oklab, try_hex_to_vec4, vec4_to_hex, GTheme,
+            Palette, ThemeMode, ThemePrimary, Themes, WcagLevel, LEVELS, THEME_CONTEXT,
+        },
+    };
     #[test]
     fn v4() {
         let v = hex_to_vec4("#F4F4F6");
         dbg!(v);
     }
+    #[test]
+    fn mode() {
+        assert_eq!(Themes::Dark.hex(500), "#22272F");
+        THEME_CONTEXT.with(|context| context.borrow_mut().mode = ThemeMode::Light);
+        assert_eq!(Themes::Dark.hex(500), "#FFFFFF");
+        // the other themes are the same in both modes
+        assert_eq!(Themes::Primary.hex(500), "#06AED4");
+        assert_eq!(ThemeMode::Light.toggle(), ThemeMode::Dark);
+    }
+    #[test]
+    fn hex() {
+        assert_eq!(vec4_to_hex(hex_to_vec4("#06AED4")), "#06AED4");
+        assert_eq!(vec4_to_hex(hex_to_vec4("#10182826")), "#10182826");
+        assert!(try_hex_to_vec4("#12345").is_err());
+        assert!(try_hex_to_vec4("#GG0000").is_err());
+    }
+    #[test]
+    fn palettes() {
+        let seed = hex_to_vec4("#7F56D9");
+        let palette = Palette::from_seed(seed);
+        assert_eq!(palette.get(500), seed);
+        // lighter to darker
+        let lightness: Vec<f32> = LEVELS
+            .iter()
+            .map(|v| srgb_to_oklab(palette.get(*v)).0)
+            .collect();
+        assert!(lightness.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(palette
+            .levels
+            .iter()
+            .all(|c| [c.x, c.y, c.z].iter().all(|v| (0.0..=1.0).contains(v))));
+        // registered palettes are used by `Custom`
+        let brand = Themes::Custom(LiveId(1));
+        assert_eq!(brand.hex_string(500), "#06AED4");
+        THEME_CONTEXT.with(|context| {
+            context
+                .borrow_mut()
+                .palettes
+                .insert(LiveId(1), palette)
+        });
+        assert_eq!(brand.hex_string(500), "#7F56D9");
+        assert_eq!(brand.hex(500), ThemePrimary::_500);
+        assert!(Palette::from_hex(&["#FFFFFF"; 10]).is_err());
+        assert!(Palette::from_hex(&["#FFFFFF"; 11]).is_ok());
+    }
+    #[test]
+    fn resync() {
+        // a widget with `theme: Custom(brand)` which resolves its color in `render`
+        struct Probe {
+            theme: Themes,
+            theme_version: u64,
+            color: Vec4,
+            renders: usize,
+        }
+        impl Probe {
+            fn render(&mut self, _cx: &mut ()) {
+                self.color = self.theme.get(500);
+                self.renders += 1;
+            }
+            fn draw(&mut self, cx: &mut ()) {
+                sync_theme!(self, cx);
+            }
+        }
+        let brand = LiveId(2);
+        let mut probe = Probe {
+            theme: Themes::Custom(brand),
+            theme_version: 0,
+            color: Vec4::default(),
+            renders: 0,
+        };
+        probe.draw(&mut ());
+        assert_eq!(probe.renders, 0);
+        // registering the palette bumps the version, the next draw renders with it
+        let seed = hex_to_vec4("#7F56D9");
+        GTheme::change(|context| {
+            context.palettes.insert(brand, Palette::from_seed(seed));
+        });
+        assert_eq!(GTheme::version(), 1);
+        probe.draw(&mut ());
+        assert_eq!((probe.color, probe.renders), (seed, 1));
+        probe.draw(&mut ());
+        assert_eq!(probe.renders, 1);
+    }
+    #[test]
+    fn contrast() {
+        let (white, black) = (hex_to_vec4("#FFFFFF"), hex_to_vec4("#000000"));
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 0.01);
+        // a transparent text is the background
+        assert!((contrast_ratio(hex_to_vec4("#00000000"), white) - 1.0).abs() < 0.01);
+        // #767676 on white is the lightest gray passing AA
+        let ratio = contrast_ratio(hex_to_vec4("#767676"), white);
+        assert!(ratio >= WcagLevel::AA.min_ratio(false));
+        assert!(ratio < WcagLevel::AAA.min_ratio(false));
+        // white text on a Warning button fails
+        let warning = Themes::Warning.get(500);
+        assert!(contrast_ratio(white, warning) < WcagLevel::AA.min_ratio(false));
+        assert!(GTheme::check_contrast("GTag", white, warning, 10.0));
+        THEME_CONTEXT.with(|context| context.borrow_mut().contrast_check = Some(WcagLevel::AA));
+        assert!(!GTheme::check_contrast("GTag", white, warning, 10.0));
+        assert!(GTheme::check_contrast("GTag", black, warning, 10.0));
+        // logged once
+        assert!(!GTheme::check_contrast("GTag", white, warning, 10.0));
+        THEME_CONTEXT.with(|context| assert_eq!(context.borrow().contrast_failures.len(), 1));
+    }
 }
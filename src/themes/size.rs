@@ -0,0 +1,123 @@
+use makepad_widgets::*;
+
+use super::{GTheme, THEME_CONTEXT};
+
+/// # Density
+/// The size preset of all G-widgets, a `Compact` admin screen is denser than the `Default` one.
+/// It scales the same props as `GSize` (see `size_scale`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Density {
+    Compact,
+    #[default]
+    Default,
+    Comfortable,
+}
+
+impl Density {
+    pub fn scale(&self) -> f64 {
+        match self {
+            Density::Compact => 0.8,
+            Density::Default => 1.0,
+            Density::Comfortable => 1.2,
+        }
+    }
+}
+
+/// # Size
+/// The size preset of a widget (`size: Small`), it scales the fixed height, padding, spacing,
+/// font_size, icon scale and border_radius of the widget together with the `Density`
+/// - The size is set per widget, a parent (such as `GView`) does not pass it down to its children,
+///   use the `Density` to scale a whole screen.
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum GSize {
+    Small,
+    #[pick]
+    Medium,
+    Large,
+}
+
+impl Default for GSize {
+    fn default() -> Self {
+        GSize::Medium
+    }
+}
+
+impl GSize {
+    pub fn scale(&self) -> f64 {
+        match self {
+            GSize::Small => 0.85,
+            GSize::Medium => 1.0,
+            GSize::Large => 1.2,
+        }
+    }
+}
+
+impl GTheme {
+    /// ## Density
+    /// scale all widgets, the props in `live_design!` are kept and scaled when drawing
+    /// ```rust
+    /// GTheme::set_density(cx, Density::Compact);
+    /// ```
+    pub fn set_density(cx: &mut Cx, density: Density) {
+        if Self::density() != density {
+            Self::update(cx, |context| context.density = density);
+        }
+    }
+    pub fn density() -> Density {
+        THEME_CONTEXT.with(|context| context.borrow().density)
+    }
+}
+
+/// the scale of a widget with the size in the current density
+pub fn size_scale(size: GSize) -> f64 {
+    size.scale() * GTheme::density().scale()
+}
+
+/// scale a fixed height, `Fit` and `Fill` are kept
+pub fn scale_walk(walk: Walk, scale: f64) -> Walk {
+    let height = match walk.height {
+        Size::Fixed(height) => Size::Fixed(height * scale),
+        height => height,
+    };
+    Walk { height, ..walk }
+}
+
+pub fn scale_padding(padding: Padding, scale: f64) -> Padding {
+    Padding {
+        left: padding.left * scale,
+        top: padding.top * scale,
+        right: padding.right * scale,
+        bottom: padding.bottom * scale,
+    }
+}
+
+/// scale the padding and spacing
+pub fn scale_layout(layout: Layout, scale: f64) -> Layout {
+    Layout {
+        padding: scale_padding(layout.padding, scale),
+        spacing: layout.spacing * scale,
+        ..layout
+    }
+}
+
+#[cfg(test)]
+mod test_size {
+    use makepad_widgets::{Size, Walk};
+
+    use super::{scale_walk, size_scale, Density, GSize, THEME_CONTEXT};
+
+    #[test]
+    fn scale() {
+        assert_eq!(size_scale(GSize::Medium), 1.0);
+        THEME_CONTEXT.with(|context| context.borrow_mut().density = Density::Compact);
+        assert!((size_scale(GSize::Small) - 0.68).abs() < 1e-9);
+        assert!((size_scale(GSize::Large) - 0.96).abs() < 1e-9);
+        let walk = Walk {
+            height: Size::Fixed(36.0),
+            ..Default::default()
+        };
+        assert_eq!(scale_walk(walk, 0.5).height, Size::Fixed(18.0));
+        assert_eq!(scale_walk(Walk::fit(), 0.5).height, Size::Fit);
+    }
+}
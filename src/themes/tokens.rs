@@ -0,0 +1,328 @@
+use std::path::Path;
+
+use makepad_widgets::*;
+use serde_json::{Map, Value};
+
+use crate::error::TokenError;
+
+use super::{try_hex_to_vec4, GTheme, Palette, LEVELS, THEME_CONTEXT};
+
+/// the groups of a tokens file
+const GROUPS: [&str; 5] = ["color", "radius", "border", "shadow", "font"];
+
+/// # Widget Tokens
+/// The default widget props of a tokens file, `None` keeps the built-in default of each widget.
+/// GButton, GView, GTag and GLabel use a token only for a prop which is not set (`None`, see `token_or`),
+/// so the props set by the app or by a `live_design!` template are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WidgetTokens {
+    /// `radius.border`
+    pub border_radius: Option<f32>,
+    /// `border.width`
+    pub border_width: Option<f32>,
+    /// `shadow.spread`
+    pub spread_radius: Option<f32>,
+    /// `shadow.blur`
+    pub blur_radius: Option<f32>,
+    /// `font.size`, also the `font_size` of `DefaultTextStyle`
+    pub font_size: Option<f64>,
+    /// `font.line_spacing`
+    pub line_spacing: Option<f64>,
+}
+
+/// the prop if it is set, else the token, else the built-in default of the widget
+pub fn token_or<T>(value: Option<T>, default: T, token: Option<T>) -> T {
+    value.or(token).unwrap_or(default)
+}
+
+/// # Theme Tokens
+/// The palettes and default widget props of a design-tokens file (JSON or TOML),
+/// load them with `GTheme::load_tokens` so a theme ships without recompiling the app.
+/// ```toml
+/// # a palette generated from a seed, used by `theme: Custom(brand)`
+/// [color.brand]
+/// seed = "#7F56D9"
+/// # or all eleven levels: 25 = "#FCFAFF", 50 = "#F9F5FF", ... 900 = "#42307D"
+///
+/// [radius]
+/// border = 4.0
+/// [border]
+/// width = 1.0
+/// [shadow]
+/// spread = 0.0
+/// blur = 6.0
+/// [font]
+/// size = 10.0
+/// line_spacing = 1.5
+/// ```
+/// - Every token is optional, but a palette needs a `seed` or all eleven levels.
+/// - A token may be wrapped as `{"$value": ...}`, the keys starting with `$` (`$type`, `$description`) are skipped.
+/// - An unknown token, a missing level or a wrong value is a `TokenError` naming the token.
+/// - Spacing (`padding`, `spacing`, `margin`) is not a token yet, a `spacing` group is an unknown token.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ThemeTokens {
+    /// the palettes by name
+    pub palettes: Vec<(String, Palette)>,
+    pub widget: WidgetTokens,
+}
+
+impl ThemeTokens {
+    pub fn from_json(src: &str) -> Result<Self, TokenError> {
+        let value: Value =
+            serde_json::from_str(src).map_err(|e| TokenError::Parse(e.to_string()))?;
+        Self::from_value(&value)
+    }
+    pub fn from_toml(src: &str) -> Result<Self, TokenError> {
+        let value: toml::Value =
+            toml::from_str(src).map_err(|e| TokenError::Parse(e.to_string()))?;
+        let value = serde_json::to_value(value).map_err(|e| TokenError::Parse(e.to_string()))?;
+        Self::from_value(&value)
+    }
+    /// read a `.json` or `.toml` file
+    pub fn from_file<P>(path: P) -> Result<Self, TokenError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let src = std::fs::read_to_string(path).map_err(|e| TokenError::Io(e.to_string()))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&src),
+            Some("toml") => Self::from_toml(&src),
+            _ => Err(TokenError::Io(format!(
+                "{} is not a `.json` or `.toml` file",
+                path.display()
+            ))),
+        }
+    }
+    pub fn from_value(value: &Value) -> Result<Self, TokenError> {
+        let mut tokens = Self::default();
+        for (key, value) in entries(group(value, "the file")?) {
+            if !GROUPS.contains(&key.as_str()) {
+                return Err(TokenError::Unknown(key.to_string()));
+            }
+            if key == "color" {
+                for (name, value) in entries(group(value, key)?) {
+                    let palette = palette(value, &format!("color.{}", name))?;
+                    tokens.palettes.push((name.to_string(), palette));
+                }
+                continue;
+            }
+            for (name, value) in entries(group(value, key)?) {
+                let token = format!("{}.{}", key, name);
+                let widget = &mut tokens.widget;
+                match (key.as_str(), name.as_str()) {
+                    ("radius", "border") => {
+                        widget.border_radius = Some(number(value, &token)? as f32)
+                    }
+                    ("border", "width") => {
+                        widget.border_width = Some(number(value, &token)? as f32)
+                    }
+                    ("shadow", "spread") => {
+                        widget.spread_radius = Some(number(value, &token)? as f32)
+                    }
+                    ("shadow", "blur") => widget.blur_radius = Some(number(value, &token)? as f32),
+                    ("font", "size") => widget.font_size = Some(number(value, &token)?),
+                    ("font", "line_spacing") => widget.line_spacing = Some(number(value, &token)?),
+                    _ => return Err(TokenError::Unknown(token)),
+                }
+            }
+        }
+        Ok(tokens)
+    }
+}
+
+impl GTheme {
+    /// ## Load tokens
+    /// register the palettes (by name, replacing the registered ones) and replace the widget tokens,
+    /// then redraw all widgets
+    /// ```rust
+    /// let tokens = ThemeTokens::from_file("theme/tokens.toml")?;
+    /// GTheme::load_tokens(cx, &tokens);
+    /// ```
+    pub fn load_tokens(cx: &mut Cx, tokens: &ThemeTokens) {
+        Self::update(cx, |context| {
+            for (name, palette) in tokens.palettes.iter() {
+                context.palettes.insert(LiveId::from_str(name), *palette);
+            }
+            context.tokens = tokens.widget;
+        });
+    }
+    pub fn load_tokens_file<P>(cx: &mut Cx, path: P) -> Result<(), TokenError>
+    where
+        P: AsRef<Path>,
+    {
+        let tokens = ThemeTokens::from_file(path)?;
+        Self::load_tokens(cx, &tokens);
+        Ok(())
+    }
+    /// the widget tokens of the loaded tokens file
+    pub fn tokens() -> WidgetTokens {
+        THEME_CONTEXT.with(|context| context.borrow().tokens)
+    }
+}
+
+/// the tokens of a group, without the `$` keys
+fn entries(group: &Map<String, Value>) -> impl Iterator<Item = (&String, &Value)> {
+    group.iter().filter(|(key, _)| !key.starts_with('$'))
+}
+
+fn group<'a>(value: &'a Value, token: &str) -> Result<&'a Map<String, Value>, TokenError> {
+    value.as_object().ok_or_else(|| TokenError::Invalid {
+        token: token.to_string(),
+        expected: "a group of tokens",
+    })
+}
+
+/// the value of a token, `{"$value": ...}` is unwrapped
+fn leaf(value: &Value) -> &Value {
+    value
+        .as_object()
+        .and_then(|object| object.get("$value"))
+        .unwrap_or(value)
+}
+
+fn number(value: &Value, token: &str) -> Result<f64, TokenError> {
+    leaf(value)
+        .as_f64()
+        .filter(|number| number.is_finite() && *number >= 0.0)
+        .ok_or_else(|| TokenError::Invalid {
+            token: token.to_string(),
+            expected: "a number not less than 0",
+        })
+}
+
+fn color(value: &Value, token: &str) -> Result<Vec4, TokenError> {
+    leaf(value)
+        .as_str()
+        .and_then(|hex| try_hex_to_vec4(hex).ok())
+        .ok_or_else(|| TokenError::Invalid {
+            token: token.to_string(),
+            expected: "a color `#RRGGBB` or `#RRGGBBAA`",
+        })
+}
+
+/// a seed color (`brand = "#7F56D9"` or `seed`), or all eleven levels
+fn palette(value: &Value, token: &str) -> Result<Palette, TokenError> {
+    if leaf(value).is_string() {
+        return color(value, token).map(Palette::from_seed);
+    }
+    let mut seed = None;
+    let mut levels = [None; 11];
+    for (key, value) in entries(group(value, token)?) {
+        let level_token = format!("{}.{}", token, key);
+        if key == "seed" {
+            seed = Some(color(value, &level_token)?);
+            continue;
+        }
+        match key
+            .parse::<u32>()
+            .ok()
+            .and_then(|v| LEVELS.iter().position(|level| *level == v))
+        {
+            Some(index) => levels[index] = Some(color(value, &level_token)?),
+            None => return Err(TokenError::Unknown(level_token)),
+        }
+    }
+    match seed {
+        Some(seed) if levels.iter().all(Option::is_none) => Ok(Palette::from_seed(seed)),
+        Some(_) => Err(TokenError::Invalid {
+            token: token.to_string(),
+            expected: "a `seed` or the eleven levels, not both",
+        }),
+        None if levels.iter().all(Option::is_none) => {
+            Err(TokenError::Missing(format!("{}.seed", token)))
+        }
+        None => {
+            let mut colors = [Vec4::default(); 11];
+            for (index, (color, level)) in colors.iter_mut().zip(levels).enumerate() {
+                *color = level
+                    .ok_or_else(|| TokenError::Missing(format!("{}.{}", token, LEVELS[index])))?;
+            }
+            Ok(Palette::new(colors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_tokens {
+    use crate::error::TokenError;
+
+    use super::{token_or, ThemeTokens};
+
+    #[test]
+    fn load() {
+        let tokens = ThemeTokens::from_toml(
+            r##"
+            [color.brand]
+            seed = "#7F56D9"
+            [radius]
+            border = 4
+            [font]
+            size = 10.5
+            "##,
+        )
+        .unwrap();
+        assert_eq!(tokens.palettes.len(), 1);
+        assert_eq!(tokens.palettes[0].0, "brand");
+        assert_eq!(tokens.widget.border_radius, Some(4.0));
+        assert_eq!(tokens.widget.font_size, Some(10.5));
+        assert_eq!(tokens.widget.blur_radius, None);
+        // the same tokens in json, with `$value`
+        let json = ThemeTokens::from_json(
+            r##"{
+                "color": {"brand": {"$value": "#7F56D9", "$type": "color"}},
+                "radius": {"border": {"$value": 4}},
+                "font": {"size": 10.5}
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(json, tokens);
+        // a prop which is set is kept, even if it is the built-in default
+        assert_eq!(token_or(None, 2.0, Some(4.0)), 4.0);
+        assert_eq!(token_or(Some(2.0), 2.0, Some(4.0)), 2.0);
+        assert_eq!(token_or(Some(6.0), 2.0, Some(4.0)), 6.0);
+        assert_eq!(token_or(None, 2.0, None), 2.0);
+    }
+
+    #[test]
+    fn errors() {
+        let err = |src: &str| ThemeTokens::from_json(src).unwrap_err();
+        assert_eq!(
+            err(r#"{"shadow": {"spred": 1}}"#),
+            TokenError::Unknown("shadow.spred".to_string())
+        );
+        assert_eq!(
+            err(r#"{"colour": {}}"#),
+            TokenError::Unknown("colour".to_string())
+        );
+        assert_eq!(
+            err(r#"{"spacing": {"padding": 8}}"#),
+            TokenError::Unknown("spacing".to_string())
+        );
+        assert_eq!(
+            err(r##"{"color": {"brand": {"25": "#FFFFFF"}}}"##),
+            TokenError::Missing("color.brand.50".to_string())
+        );
+        assert_eq!(
+            err(r#"{"color": {"brand": {}}}"#),
+            TokenError::Missing("color.brand.seed".to_string())
+        );
+        assert_eq!(
+            err(r##"{"color": {"brand": {"950": "#000000"}}}"##),
+            TokenError::Unknown("color.brand.950".to_string())
+        );
+        assert!(matches!(
+            err(r#"{"color": {"brand": "purple"}}"#),
+            TokenError::Invalid { .. }
+        ));
+        assert!(matches!(
+            err(r#"{"font": {"size": -1}}"#),
+            TokenError::Invalid { .. }
+        ));
+        assert!(matches!(err("{"), TokenError::Parse(_)));
+        assert_eq!(
+            err(r#"{"radius": {"border": "4px"}}"#).to_string(),
+            "Invalid token `radius.border`, expected a number not less than 0."
+        );
+    }
+}
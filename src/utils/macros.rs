@@ -505,3 +505,20 @@ macro_rules! check_event_scope {
         }
     };
 }
+
+/// # Sync Theme
+/// Resolve the theme colors again once the theme context is changed (see `GTheme::update`),
+/// call it at the beginning of `draw_walk`, the widget needs a `#[rust] theme_version: u64` field
+/// and resolves its colors in `render` (`after_apply` calls it as well).
+/// ```rust
+/// sync_theme!(self, cx);
+/// ```
+#[macro_export]
+macro_rules! sync_theme {
+    ($self: ident, $cx: ident) => {
+        if $self.theme_version != $crate::themes::GTheme::version() {
+            $self.theme_version = $crate::themes::GTheme::version();
+            $self.render($cx);
+        }
+    };
+}
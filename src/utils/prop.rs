@@ -2,7 +2,7 @@ use std::{path::PathBuf, str::FromStr};
 
 use makepad_widgets::{DVec2, LiveDependency, LiveId, Rect, Vec2, Vec4};
 
-use crate::themes::{get_color, hex_to_vec4, Themes};
+use crate::themes::{contrast_ratio, get_color, hex_to_vec4, relative_luminance, Themes, WcagLevel};
 
 // -------------------------------------------------------------------------------------------------
 /// This trait is used to get the color of the theme
@@ -21,6 +21,29 @@ impl ThemeColor for Option<Vec4> {
     }
 }
 
+// -------------------------------------------------------------------------------------------------
+/// This trait is used to check the WCAG contrast of a text color
+pub trait Contrast {
+    /// the relative luminance, `0.0` ~ `1.0`
+    fn luminance(&self) -> f32;
+    /// the contrast ratio on the background, `1.0` ~ `21.0`
+    fn contrast(&self, background: &Vec4) -> f32;
+    /// check the contrast ratio on the background with the level
+    fn passes(&self, background: &Vec4, level: WcagLevel, large_text: bool) -> bool;
+}
+
+impl Contrast for Vec4 {
+    fn luminance(&self) -> f32 {
+        relative_luminance(*self)
+    }
+    fn contrast(&self, background: &Vec4) -> f32 {
+        contrast_ratio(*self, *background)
+    }
+    fn passes(&self, background: &Vec4, level: WcagLevel, large_text: bool) -> bool {
+        self.contrast(background) >= level.min_ratio(large_text)
+    }
+}
+
 #[macro_export]
 macro_rules! color_v_trait {
     ($T:ty) => {
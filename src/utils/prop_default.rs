@@ -1,3 +1,5 @@
+use crate::themes::GTheme;
+
 #[derive(Clone)]
 pub struct DefaultTextStyle {
     pub brightness: f32,
@@ -10,13 +12,15 @@ pub struct DefaultTextStyle {
 }
 
 impl Default for DefaultTextStyle {
+    /// `font_size` and `line_spacing` follow the loaded tokens (see `ThemeTokens`)
     fn default() -> Self {
+        let tokens = GTheme::tokens();
         Self {
             brightness: 1.0,
             curve: 0.5,
-            line_spacing: 1.0,
+            line_spacing: tokens.line_spacing.unwrap_or(1.0),
             // top_drop: 1.1,
-            font_size: 9.0,
+            font_size: tokens.font_size.unwrap_or(9.0),
             height_factor: 1.3,
             line_scale: 0.88
         }